@@ -0,0 +1,109 @@
+//! Integration with the `actix-web` crate.
+//!
+//! Yarns can be returned from handlers, with the same content types as
+//! `String` and `Vec<u8>`, and extracted from request bodies.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use actix_web::http::header::CONTENT_TYPE;
+//! use actix_web::test::TestRequest;
+//! use actix_web::web::PayloadConfig;
+//! use actix_web::FromRequest;
+//! use actix_web::Responder;
+//!
+//! async fn echo(body: ByteYarn) -> Yarn {
+//!   yarn!("got {} bytes", body.len())
+//! }
+//!
+//! # tokio::runtime::Builder::new_current_thread()
+//! #   .build()
+//! #   .unwrap()
+//! #   .block_on(async {
+//! let (req, mut payload) = TestRequest::default()
+//!   .set_payload("hello!")
+//!   .to_http_parts();
+//! let body = ByteYarn::from_request(&req, &mut payload).await.unwrap();
+//! assert_eq!(body, b"hello!");
+//!
+//! let resp = echo(body).await.respond_to(&req);
+//! let content_type = resp.headers().get(CONTENT_TYPE).unwrap();
+//! assert_eq!(content_type, "text/plain; charset=utf-8");
+//!
+//! let (req, mut payload) = TestRequest::default()
+//!   .app_data(PayloadConfig::new(4))
+//!   .set_payload("hello!")
+//!   .to_http_parts();
+//! assert!(ByteYarn::from_request(&req, &mut payload).await.is_err());
+//! # });
+//! ```
+//!
+//! Extracting a yarn buffers the whole body, in the same way `Bytes` does, so
+//! the size of the body is limited by [`PayloadConfig`], which is 256 kB
+//! unless configured otherwise.
+//!
+//! [`PayloadConfig`]: actix_web::web::PayloadConfig
+
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::http::header::HeaderValue;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
+use actix_web::Error;
+use actix_web::FromRequest;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+
+use crate::YarnBox;
+
+impl<Buf> Responder for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Body = Bytes;
+
+  /// Converts a yarn into a `200 OK` response with the yarn as its body.
+  ///
+  /// String yarns are sent as `text/plain; charset=utf-8`, and byte yarns as
+  /// `application/octet-stream`. Static and heap-allocated yarns are sent
+  /// without copying.
+  fn respond_to(self, _: &HttpRequest) -> HttpResponse<Bytes> {
+    let content_type = match <Buf as crate::z::Sealed>::UTF8 {
+      true => "text/plain; charset=utf-8",
+      false => "application/octet-stream",
+    };
+
+    let bytes = Bytes::from(self.into_bytes().immortalize());
+    let mut resp = HttpResponse::with_body(StatusCode::OK, bytes);
+    resp
+      .headers_mut()
+      .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    resp
+  }
+}
+
+impl FromRequest for YarnBox<'static, [u8]> {
+  type Error = Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+
+  /// Buffers a request body into a yarn.
+  fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+    let bytes = Bytes::from_request(req, payload);
+    Box::pin(async move { bytes.await.map(Self::from) })
+  }
+}
+
+impl FromRequest for YarnBox<'static, str> {
+  type Error = Error;
+  type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+
+  /// Buffers a request body into a yarn, decoding it according to the
+  /// request's charset.
+  fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+    let string = String::from_request(req, payload);
+    Box::pin(async move { string.await.map(Self::from_string) })
+  }
+}
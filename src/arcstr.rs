@@ -0,0 +1,58 @@
+//! Integration with the `arcstr` crate.
+
+use arcstr::ArcStr;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> From<ArcStr> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts an `ArcStr` into a yarn.
+  ///
+  /// `ArcStr` literals stay static; other `ArcStr`s share their buffers, so
+  /// they must be copied (or inlined, if they are short enough).
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let lit = arcstr::literal!("an arcstr literal that is quite long");
+  /// let yarn = Yarn::from(lit);
+  /// assert!(yarn.to_ref().is_some());
+  /// ```
+  fn from(s: ArcStr) -> Self {
+    let raw = match ArcStr::as_static(&s) {
+      Some(s) => RawYarn::new(s.as_bytes()),
+      None => RawYarn::copy_slice(s.as_bytes()),
+    };
+
+    unsafe {
+      // SAFETY: both [u8] and str can be safely constructed from a str, and
+      // none of the raw yarns above borrow from `s`.
+      YarnBox::from_raw(raw)
+    }
+  }
+}
+
+impl From<YarnBox<'_, str>> for ArcStr {
+  /// Converts a yarn into an `ArcStr`, by copying it.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use arcstr::ArcStr;
+  ///
+  /// let arc = ArcStr::from(yarn!("arc"));
+  /// assert_eq!(arc, "arc");
+  /// ```
+  fn from(y: YarnBox<str>) -> Self {
+    ArcStr::from(y.as_str())
+  }
+}
+
+impl From<YarnRef<'_, str>> for ArcStr {
+  /// Converts a yarn into an `ArcStr`, by copying it.
+  fn from(y: YarnRef<str>) -> Self {
+    ArcStr::from(y.as_str())
+  }
+}
@@ -0,0 +1,139 @@
+//! Integration with the `arrow` crate, through `arrow-array`.
+//!
+//! Arrow's byte arrays can already be built from iterators of anything that
+//! implements `AsRef<str>` or `AsRef<[u8]>`, which includes yarns:
+//!
+//! ```
+//! # use byteyarn::*;
+//! use arrow_array::Array;
+//! use arrow_array::StringArray;
+//!
+//! let names = [yarn!("ferris"), yarn!("a name too long to be inlined")];
+//! let column = StringArray::from_iter_values(&names);
+//! assert_eq!(column.value(1), "a name too long to be inlined");
+//! ```
+//!
+//! [`ArrowIter`] goes the other way, viewing the values in a column as yarns
+//! that borrow Arrow's buffers.
+
+use std::iter::FusedIterator;
+
+use arrow_array::types::ByteArrayType;
+use arrow_array::Array;
+use arrow_array::GenericByteArray;
+
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+/// An iterator over the values of an Arrow `StringArray` or `BinaryArray`, as
+/// yarns.
+///
+/// The yarns borrow from the array's value buffer, so iterating does not copy.
+/// Null values are yielded as `None`.
+///
+/// ```
+/// # use byteyarn::*;
+/// use arrow_array::BinaryArray;
+/// use arrow_array::StringArray;
+///
+/// let column = StringArray::from(vec![Some("a"), None, Some("ccc")]);
+/// let yarns = ArrowIter::new(&column).collect::<Vec<_>>();
+/// assert_eq!(yarns, [Some(YarnRef::from("a")), None, Some("ccc".into())]);
+///
+/// // Copy the values out to outlive the column.
+/// let column = BinaryArray::from_vec(vec![b"\x00\x01", b"\x02"]);
+/// let owned = ArrowIter::new(&column)
+///   .map(|y| y.unwrap().to_box().immortalize())
+///   .collect::<Vec<ByteYarn>>();
+/// drop(column);
+/// assert_eq!(owned, [&[0, 1][..], &[2]]);
+/// ```
+pub struct ArrowIter<'a, T>
+where
+  T: ByteArrayType,
+  T::Native: crate::Buf,
+{
+  array: &'a GenericByteArray<T>,
+  start: usize,
+  end: usize,
+}
+
+impl<'a, T> ArrowIter<'a, T>
+where
+  T: ByteArrayType,
+  T::Native: crate::Buf,
+{
+  /// Returns an iterator over the values of `array`.
+  pub fn new(array: &'a GenericByteArray<T>) -> Self {
+    Self {
+      array,
+      start: 0,
+      end: array.len(),
+    }
+  }
+
+  fn get(&self, idx: usize) -> Option<YarnRef<'a, T::Native>> {
+    if self.array.is_null(idx) {
+      return None;
+    }
+
+    Some(YarnRef::new(unsafe {
+      // SAFETY: idx is in bounds, since start..end is a subrange of
+      // 0..array.len().
+      self.array.value_unchecked(idx)
+    }))
+  }
+}
+
+impl<'a, T> Iterator for ArrowIter<'a, T>
+where
+  T: ByteArrayType,
+  T::Native: crate::Buf,
+{
+  type Item = Option<YarnRef<'a, T::Native>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.start == self.end {
+      return None;
+    }
+
+    self.start += 1;
+    Some(self.get(self.start - 1))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.end - self.start;
+    (len, Some(len))
+  }
+}
+
+impl<T> DoubleEndedIterator for ArrowIter<'_, T>
+where
+  T: ByteArrayType,
+  T::Native: crate::Buf,
+{
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.start == self.end {
+      return None;
+    }
+
+    self.end -= 1;
+    Some(self.get(self.end))
+  }
+}
+
+impl<T> ExactSizeIterator for ArrowIter<'_, T>
+where
+  T: ByteArrayType,
+  T::Native: crate::Buf,
+{
+}
+
+impl<T> FusedIterator for ArrowIter<'_, T>
+where
+  T: ByteArrayType,
+  T::Native: crate::Buf,
+{
+}
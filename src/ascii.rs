@@ -0,0 +1,126 @@
+//! Integration with the `ascii` crate.
+
+use ascii::AsciiStr;
+use ascii::AsciiString;
+use ascii::FromAsciiError;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns this yarn as an [`AsciiStr`], if it is entirely ASCII.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let method = YarnRef::<[u8]>::new(b"GET");
+  /// assert_eq!(method.as_ascii().unwrap(), "GET");
+  ///
+  /// assert!(YarnRef::<str>::new("café").as_ascii().is_none());
+  /// ```
+  pub fn as_ascii(&self) -> Option<&AsciiStr> {
+    AsciiStr::from_ascii(self.as_bytes()).ok()
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns this yarn as an [`AsciiStr`], if it is entirely ASCII.
+  ///
+  /// See [`YarnRef::as_ascii()`].
+  pub fn as_ascii(&self) -> Option<&AsciiStr> {
+    AsciiStr::from_ascii(self.as_bytes()).ok()
+  }
+}
+
+impl<'a, Buf> From<&'a AsciiStr> for YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Borrows an `AsciiStr` as a yarn, without copying.
+  fn from(s: &'a AsciiStr) -> Self {
+    YarnRef::new(unsafe {
+      // SAFETY: ASCII is valid UTF-8, so it is a valid Buf.
+      YarnRef::<Buf>::raw2buf(s.as_bytes())
+    })
+  }
+}
+
+impl<'a, Buf> From<&'a AsciiStr> for YarnBox<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Borrows an `AsciiStr` as a yarn, without copying.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use ascii::AsciiStr;
+  ///
+  /// let field = AsciiStr::from_ascii("Content-Length: 42").unwrap();
+  /// let yarn = ByteYarn::from(field);
+  /// assert_eq!(yarn.kind(), YarnKind::Borrowed);
+  /// ```
+  fn from(s: &'a AsciiStr) -> Self {
+    YarnRef::<Buf>::from(s).to_box()
+  }
+}
+
+impl<Buf> From<AsciiString> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts an `AsciiString` into a yarn, reusing its allocation unless it
+  /// is short enough to inline.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use ascii::AsciiString;
+  ///
+  /// let s = AsciiString::from_ascii("x-request-id").unwrap();
+  /// assert_eq!(Yarn::from(s), "x-request-id");
+  /// ```
+  fn from(s: AsciiString) -> Self {
+    Self::from_string(s.into())
+  }
+}
+
+impl<Buf> TryFrom<YarnBox<'_, Buf>> for AsciiString
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = FromAsciiError<Vec<u8>>;
+
+  /// Converts a yarn into an `AsciiString`, if it is entirely ASCII.
+  ///
+  /// Heap yarns hand over their allocation.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use ascii::AsciiString;
+  ///
+  /// let s = AsciiString::try_from(yarn!("HTTP/{}", 1.1)).unwrap();
+  /// assert_eq!(s, "HTTP/1.1");
+  ///
+  /// let err = AsciiString::try_from(yarn!("naïve")).unwrap_err();
+  /// assert_eq!(err.ascii_error().valid_up_to(), 2);
+  /// ```
+  fn try_from(y: YarnBox<Buf>) -> Result<Self, Self::Error> {
+    AsciiString::from_ascii(y.into_vec())
+  }
+}
+
+impl<Buf> TryFrom<YarnRef<'_, Buf>> for AsciiString
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = FromAsciiError<Vec<u8>>;
+
+  /// Copies a yarn into an `AsciiString`, if it is entirely ASCII.
+  fn try_from(y: YarnRef<Buf>) -> Result<Self, Self::Error> {
+    AsciiString::from_ascii(y.to_vec())
+  }
+}
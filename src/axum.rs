@@ -0,0 +1,107 @@
+//! Integration with the `axum` crate.
+//!
+//! Yarns can be returned from handlers, with the same content types as
+//! `String` and `Vec<u8>`, and extracted from request bodies.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use axum_core::body::Body;
+//! use axum_core::extract::FromRequest;
+//! use axum_core::response::IntoResponse;
+//! use http::header::CONTENT_TYPE;
+//! use http::Request;
+//!
+//! async fn echo(body: ByteYarn) -> Yarn {
+//!   yarn!("got {} bytes", body.len())
+//! }
+//!
+//! # tokio::runtime::Builder::new_current_thread()
+//! #   .build()
+//! #   .unwrap()
+//! #   .block_on(async {
+//! let req = Request::new(Body::from("hello!"));
+//! let body = ByteYarn::from_request(req, &()).await.unwrap();
+//! assert_eq!(body, b"hello!");
+//!
+//! let resp = echo(body).await.into_response();
+//! assert_eq!(resp.headers()[CONTENT_TYPE], "text/plain; charset=utf-8");
+//! # });
+//! ```
+//!
+//! Extracting a yarn buffers the whole body, in the same way `Bytes` does, so
+//! the size of the body is limited by [`DefaultBodyLimit`], which is 2 MB
+//! unless configured otherwise.
+//!
+//! [`DefaultBodyLimit`]: axum_core::extract::DefaultBodyLimit
+
+use async_trait::async_trait;
+use axum_core::body::Body;
+use axum_core::extract::rejection::BytesRejection;
+use axum_core::extract::rejection::StringRejection;
+use axum_core::extract::FromRequest;
+use axum_core::extract::Request;
+use axum_core::response::IntoResponse;
+use axum_core::response::Response;
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use http::HeaderValue;
+
+use crate::YarnBox;
+
+impl<Buf> IntoResponse for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts a yarn into a `200 OK` response with the yarn as its body.
+  ///
+  /// String yarns are sent as `text/plain; charset=utf-8`, and byte yarns as
+  /// `application/octet-stream`. Static and heap-allocated yarns are sent
+  /// without copying.
+  fn into_response(self) -> Response {
+    let content_type = match <Buf as crate::z::Sealed>::UTF8 {
+      true => "text/plain; charset=utf-8",
+      false => "application/octet-stream",
+    };
+
+    let bytes = Bytes::from(self.into_bytes().immortalize());
+    let mut resp = Body::from(bytes).into_response();
+    resp
+      .headers_mut()
+      .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    resp
+  }
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for YarnBox<'static, [u8]>
+where
+  S: Send + Sync,
+{
+  type Rejection = BytesRejection;
+
+  /// Buffers a request body into a yarn.
+  async fn from_request(
+    req: Request,
+    state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    Bytes::from_request(req, state).await.map(Self::from)
+  }
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for YarnBox<'static, str>
+where
+  S: Send + Sync,
+{
+  type Rejection = StringRejection;
+
+  /// Buffers a request body into a yarn, rejecting it if it is not UTF-8.
+  async fn from_request(
+    req: Request,
+    state: &S,
+  ) -> Result<Self, Self::Rejection> {
+    String::from_request(req, state)
+      .await
+      .map(Self::from_string)
+  }
+}
@@ -4,12 +4,23 @@ use std::hash::Hash;
 use std::hash::Hasher;
 use std::marker::PhantomData;
 use std::mem;
+use std::mem::MaybeUninit;
 use std::ops::Deref;
+use std::rc::Rc;
+use std::slice;
 use std::str;
 use std::str::Utf8Error;
+use std::sync::Arc;
 
 use crate::raw::RawYarn;
+use crate::CharIndex;
+use crate::CharIndicesLossy;
+use crate::CharsLossy;
+use crate::FmtError;
+use crate::HexDump;
+use crate::TooLong;
 use crate::Utf8Chunks;
+use crate::YarnKind;
 use crate::YarnRef;
 
 #[cfg(doc)]
@@ -25,6 +36,42 @@ use crate::*;
 /// this will be `'static`.
 ///
 /// See the [crate documentation](crate) for general information.
+///
+/// # Hashing
+///
+/// A yarn hashes exactly like its contents as a `&Buf`, regardless of how it
+/// is stored, and so exactly like the equivalent `&str`, `String`, `&[u8]`,
+/// or `Vec<u8>`. This is a guarantee, not an implementation detail: it is what
+/// makes it correct to look up yarn-keyed maps by slice through [`Borrow`],
+/// and to mix yarns into code that hashes strings with the same hasher.
+/// The same holds for [`YarnRef`].
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::collections::hash_map::RandomState;
+/// use std::collections::HashMap;
+/// use std::hash::BuildHasher;
+///
+/// let state = RandomState::new();
+/// let long = "a string that is too long to inline";
+/// for yarn in [yarn!("short"), Yarn::from_static(long), yarn!("{long}")] {
+///   let s: &str = &yarn;
+///   assert_eq!(state.hash_one(&yarn), state.hash_one(s));
+///   assert_eq!(state.hash_one(&yarn), state.hash_one(s.to_string()));
+///   assert_eq!(state.hash_one(yarn.as_ref()), state.hash_one(s));
+///
+///   let bytes = yarn.clone().into_bytes();
+///   assert_eq!(state.hash_one(&bytes), state.hash_one(s.as_bytes()));
+///   assert_eq!(state.hash_one(&bytes), state.hash_one(s.as_bytes().to_vec()));
+/// }
+///
+/// let mut map = HashMap::new();
+/// map.insert(yarn!("key"), 1);
+/// assert_eq!(map.get("key"), Some(&1));
+/// assert_eq!(map.get(&String::from("key") as &str), Some(&1));
+/// ```
+///
+/// [`Borrow`]: std::borrow::Borrow
 #[repr(transparent)]
 pub struct YarnBox<'a, Buf = [u8]>
 where
@@ -67,6 +114,21 @@ where
     YarnRef::new(buf).to_box()
   }
 
+  /// Like [`YarnBox::new()`], but returns an error instead of panicking if
+  /// `buf` is too long to be represented by a yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let foo = Yarn::try_new("Byzantium").unwrap();
+  /// assert_eq!(foo.len(), 9);
+  /// ```
+  pub const fn try_new(buf: &'a Buf) -> Result<Self, TooLong> {
+    match YarnRef::try_new(buf) {
+      Ok(y) => Ok(y.to_box()),
+      Err(e) => Err(e),
+    }
+  }
+
   /// Returns a new yarn containing the contents of the given slice.
   ///
   /// This function will always return an inlined string, or `None` if the
@@ -167,6 +229,10 @@ where
 
   /// Returns a yarn by taking ownership of an allocation.
   ///
+  /// If `string` has spare capacity, it is shrunk to fit first: a yarn's heap
+  /// allocation is always exactly as long as its contents, so yarns never carry
+  /// slack and have no need for a `shrink_to_fit()`.
+  ///
   /// ```
   /// # use byteyarn::*;
   /// let str = String::from("big string box");
@@ -177,6 +243,26 @@ where
     Self::from_boxed_str(string.into())
   }
 
+  /// Like [`YarnBox::from_boxed_str()`], but returns an error instead of
+  /// panicking if `string` is too long to be represented by a yarn.
+  pub fn try_from_boxed_str(string: Box<str>) -> Result<Self, TooLong> {
+    TooLong::check(string.len(), RawYarn::MAX_LEN)?;
+    Ok(Self::from_boxed_str(string))
+  }
+
+  /// Like [`YarnBox::from_string()`], but returns an error instead of
+  /// panicking if `string` is too long to be represented by a yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = Yarn::try_from_string("big string box".into()).unwrap();
+  /// assert_eq!(yarn, "big string box");
+  /// ```
+  pub fn try_from_string(string: String) -> Result<Self, TooLong> {
+    TooLong::check(string.len(), RawYarn::MAX_LEN)?;
+    Ok(Self::from_string(string))
+  }
+
   /// Checks whether this yarn is empty.
   ///
   /// ```
@@ -205,6 +291,110 @@ where
     self.as_ref().len()
   }
 
+  /// Returns the storage class this yarn is currently using.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut yarn = Yarn::from_string("a very long heap-allocated string".into());
+  /// assert_eq!(yarn.kind(), YarnKind::Heap);
+  ///
+  /// yarn.leak();
+  /// assert_eq!(yarn.kind(), YarnKind::Static);
+  /// ```
+  pub const fn kind(&self) -> YarnKind {
+    YarnKind::of(self.raw)
+  }
+
+  /// Checks whether this yarn's contents are stored inline, without pointing
+  /// to a separate buffer.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// assert!(yarn!("smol").is_inlined());
+  /// assert!(!Yarn::from_static("a very long string literal").is_inlined());
+  /// ```
+  pub const fn is_inlined(&self) -> bool {
+    self.raw.is_small()
+  }
+
+  /// Returns the number of bytes of heap memory this yarn owns, if it owns
+  /// any.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = Yarn::from_string("a very long heap-allocated string".into());
+  /// assert_eq!(yarn.heap_len(), Some(33));
+  ///
+  /// assert_eq!(yarn!("smol").heap_len(), None);
+  /// assert_eq!(yarn.aliased().heap_len(), None);
+  /// ```
+  pub const fn heap_len(&self) -> Option<usize> {
+    match self.raw.on_heap() {
+      true => Some(self.raw.len()),
+      false => None,
+    }
+  }
+
+  /// Returns the alignment of this yarn's contents: the largest power of two
+  /// that their address is a multiple of.
+  ///
+  /// Yarns cannot promise any particular alignment. A heap-allocated yarn must
+  /// be freed with the same layout as a `Box<[u8]>`, whose alignment is 1,
+  /// and there is no room left in a yarn to record that it was allocated any
+  /// other way. Instead, consumers that need aligned data, such as SIMD
+  /// kernels and zero-copy casts, can check this, and only copy into an
+  /// aligned buffer when it is too small. In practice, most allocators return
+  /// memory that is aligned to at least 8 bytes.
+  ///
+  /// Inlined yarns store their contents inside of the yarn, so their alignment
+  /// changes when the yarn is moved.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// #[repr(align(64))]
+  /// struct Aligned([u8; 64]);
+  /// static DATA: Aligned = Aligned([0; 64]);
+  ///
+  /// let yarn = ByteYarn::from_static(&DATA.0);
+  /// assert!(yarn.align() >= 64);
+  /// assert_eq!(YarnRef::new(&DATA.0[4..]).align(), 4);
+  /// assert_eq!(YarnRef::new(&DATA.0[24..]).align(), 8);
+  /// ```
+  pub fn align(&self) -> usize {
+    self.as_ref().align()
+  }
+
+  /// Checks that this yarn's internal representation is consistent, panicking
+  /// if it is not.
+  ///
+  /// This checks the storage tag and length, and, for `str` yarns, that the
+  /// contents are valid UTF-8. A yarn built through this crate's API always
+  /// passes; this is intended for fuzzers and debuggers that want to catch
+  /// memory corruption as early as possible.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// yarn!("{}", 42).assert_invariants();
+  /// Yarn::from_string("a very long heap-allocated string".into()).assert_invariants();
+  /// ```
+  #[track_caller]
+  pub fn assert_invariants(&self) {
+    self.raw.assert_invariants(<Buf as crate::z::Sealed>::UTF8)
+  }
+
+  /// Returns a value whose `Debug` impl dumps this yarn's raw representation.
+  ///
+  /// The format is unstable, and is intended only for debugging.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let dump = format!("{:?}", yarn!("smol").debug_raw());
+  /// assert!(dump.starts_with("RawYarn { kind: SMALL, len: 4,"));
+  /// ```
+  pub fn debug_raw(&self) -> impl fmt::Debug {
+    self.raw
+  }
+
   /// Converts this yarn into a slice.
   ///
   /// ```
@@ -305,6 +495,11 @@ where
   pub fn into_boxed_bytes(self) -> Box<[u8]> {
     let mut raw = self.into_raw();
     if !raw.on_heap() {
+      #[cfg(feature = "copy_limit")]
+      if !raw.is_immortal() {
+        crate::copy_limit::check(raw.len());
+      }
+
       return raw.as_slice().into();
     }
 
@@ -330,6 +525,64 @@ where
     self.into_boxed_bytes().into()
   }
 
+  /// Converts this yarn into an [`Arc`] by copying it.
+  ///
+  /// An `Arc` keeps its reference counts in the same allocation as its data,
+  /// so even a heap-allocated yarn cannot be converted without copying.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use std::sync::Arc;
+  ///
+  /// let arc: Arc<str> = yarn!("{}-{}", "key", 42).into_arc();
+  /// assert_eq!(&*arc, "key-42");
+  /// ```
+  pub fn into_arc(self) -> Arc<Buf> {
+    self.as_ref().to_arc()
+  }
+
+  /// Converts this yarn into an [`Rc`] by copying it.
+  ///
+  /// See [`YarnBox::into_arc()`].
+  pub fn into_rc(self) -> Rc<Buf> {
+    self.as_ref().to_rc()
+  }
+
+  /// Converts this yarn into a box without copying, if it owns a heap
+  /// allocation; otherwise, returns the yarn unchanged.
+  ///
+  /// This is useful for handing a yarn off to an API that wants a `Box<str>`
+  /// or `Box<[u8]>`, when the yarn is likely to own one already.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let string = String::from("a very long heap-allocated string");
+  /// let ptr = string.as_ptr();
+  ///
+  /// let boxed: Box<str> = Yarn::from_string(string).try_into_box().unwrap();
+  /// assert_eq!(boxed.as_ptr(), ptr);
+  ///
+  /// assert!(yarn!("smol").try_into_box().is_err());
+  /// assert!(Yarn::from_static("a very long string literal").try_into_box().is_err());
+  /// ```
+  pub fn try_into_box(self) -> Result<Box<Buf>, Self> {
+    if !self.raw.on_heap() {
+      return Err(self);
+    }
+
+    let mut raw = self.into_raw();
+    unsafe {
+      // SAFETY: raw is on the heap, so this slice is a uniquely-owned heap
+      // allocation with the correct layout, as in into_boxed_bytes(). The
+      // safety rules of `Buf` make the pointer cast valid, and our bytes came
+      // out of a Buf.
+      let bytes: *mut [u8] = raw.as_mut_slice();
+      Ok(Box::from_raw(
+        *(&bytes as *const *mut [u8] as *const *mut Buf),
+      ))
+    }
+  }
+
   /// Converts this yarn into a byte yarn.
   pub const fn into_bytes(self) -> YarnBox<'a, [u8]> {
     unsafe {
@@ -365,6 +618,9 @@ where
       }
     }
 
+    #[cfg(feature = "copy_limit")]
+    crate::copy_limit::check(self.len());
+
     let raw = RawYarn::copy_slice(self.as_bytes());
     unsafe {
       // SAFETY: RawYarn::copy_slice always returns an immortal, uniquely-owned
@@ -392,6 +648,48 @@ where
     unsafe { Self::from_raw(RawYarn::concat(total_len, iter)) }
   }
 
+  /// Converts `\r\n` and lone `\r` line endings into `\n`.
+  ///
+  /// If this yarn contains no `\r` bytes, which is the common case, it is
+  /// returned untouched, without copying. Otherwise, the result is built in a
+  /// single pass.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = Yarn::from_static("one\r\ntwo\rthree\n\r\n");
+  /// assert_eq!(yarn.normalize_newlines(), "one\ntwo\nthree\n\n");
+  ///
+  /// let lit = Yarn::from_static("a long string with\nunix line endings\n");
+  /// let normal = lit.aliased().normalize_newlines();
+  /// assert_eq!(normal.as_ptr(), lit.as_ptr());
+  /// ```
+  pub fn normalize_newlines(self) -> Self {
+    let bytes = self.as_bytes();
+    let Some(first) = bytes.iter().position(|&b| b == b'\r') else {
+      return self;
+    };
+
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&bytes[..first]);
+
+    let mut rest = &bytes[first..];
+    while let Some(cr) = rest.iter().position(|&b| b == b'\r') {
+      out.extend_from_slice(&rest[..cr]);
+      out.push(b'\n');
+      rest = &rest[cr + 1..];
+      if let Some(b'\n') = rest.first() {
+        rest = &rest[1..];
+      }
+    }
+    out.extend_from_slice(rest);
+
+    unsafe {
+      // SAFETY: Replacing and removing ASCII bytes preserves UTF-8
+      // validity, and from_heap() returns a uniquely-owned raw yarn.
+      Self::from_raw(RawYarn::from_heap(out.into()))
+    }
+  }
+
   /// Tries to inline this yarn, if it's small enough.
   ///
   /// This operation has no directly visible side effects, and is only intended
@@ -403,6 +701,77 @@ where
     }
   }
 
+  /// Inlines this yarn if it owns a heap allocation that it does not need,
+  /// freeing that allocation.
+  ///
+  /// Some operations, such as [`YarnBox::into_stable()`], can leave a yarn
+  /// short enough to inline pinned to a heap allocation. Unlike
+  /// [`YarnBox::inline_in_place()`], this function returns whether an
+  /// allocation was freed, which is useful for tracking how much memory a
+  /// compaction pass over a large collection of yarns recovers.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut yarn = yarn!("smol").into_stable().into_inner();
+  /// assert_eq!(yarn.kind(), YarnKind::Heap);
+  ///
+  /// assert!(yarn.compact());
+  /// assert_eq!(yarn.kind(), YarnKind::Small);
+  /// assert_eq!(yarn, "smol");
+  ///
+  /// assert!(!yarn.compact());
+  /// ```
+  pub fn compact(&mut self) -> bool {
+    if !self.raw.on_heap() {
+      return false;
+    }
+
+    self.inline_in_place();
+    self.raw.is_small()
+  }
+
+  /// Takes this yarn's contents, leaving an empty yarn in its place.
+  ///
+  /// This is equivalent to `std::mem::take(self)`, and never allocates.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// struct Request {
+  ///   method: Yarn,
+  ///   path: Yarn,
+  /// }
+  ///
+  /// let mut req = Request {
+  ///   method: yarn!("GET"),
+  ///   path: yarn!("/{}", "index"),
+  /// };
+  /// let path = req.path.take();
+  /// assert_eq!(path, "/index");
+  /// assert!(req.path.is_empty());
+  /// assert_eq!(req.method, "GET");
+  /// ```
+  pub fn take(&mut self) -> Self {
+    mem::take(self)
+  }
+
+  /// Replaces this yarn with `yarn`, returning the old one.
+  ///
+  /// This is equivalent to `std::mem::replace(self, yarn)`; it is not named
+  /// `replace()` so that it does not hide [`str::replace()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut name = yarn!("draft");
+  /// let old = name.swap_out(yarn!("final"));
+  /// assert_eq!(old, "draft");
+  /// assert_eq!(name, "final");
+  ///
+  /// assert_eq!(name.replace("f", "F"), "Final");
+  /// ```
+  pub fn swap_out(&mut self, yarn: Self) -> Self {
+    mem::replace(self, yarn)
+  }
+
   /// Leaks any heap allocation associated with this yarn.
   ///
   /// The allocation is tagged as "static", so upcasting via
@@ -448,6 +817,50 @@ where
     Utf8Chunks::new(self.as_bytes())
   }
 
+  /// Returns an adaptor for formatting this yarn as a hex dump.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = ByteYarn::new(b"byteyarn \xF0\x9F\xA7\xB6\xF0\x9F\x90\x88\xE2\x80\x8D\xE2\xAC\x9B\n");
+  /// assert_eq!(yarn.hex_dump().to_string(), "\
+  /// 00000000  62 79 74 65 79 61 72 6e  20 f0 9f a7 b6 f0 9f 90  |byteyarn .......|
+  /// 00000010  88 e2 80 8d e2 ac 9b 0a                           |........|");
+  /// ```
+  pub fn hex_dump(&self) -> HexDump {
+    HexDump::new(self.as_bytes())
+  }
+
+  /// Checks whether this yarn starts with `prefix`, ignoring ASCII case.
+  ///
+  /// This does not allocate; non-ASCII bytes must match exactly.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let url = yarn!("HTTPS://example.com");
+  /// assert!(url.starts_with_ignore_ascii_case("https://"));
+  /// assert!(!url.starts_with_ignore_ascii_case("http://"));
+  ///
+  /// let request = ByteYarn::new(b"get /index.html HTTP/1.1");
+  /// assert!(request.starts_with_ignore_ascii_case(b"GET "));
+  /// ```
+  pub fn starts_with_ignore_ascii_case(&self, prefix: impl AsRef<Buf>) -> bool {
+    self.as_ref().starts_with_ignore_ascii_case(prefix)
+  }
+
+  /// Checks whether this yarn ends with `suffix`, ignoring ASCII case.
+  ///
+  /// This does not allocate; non-ASCII bytes must match exactly.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let file = yarn!("Photo.JPEG");
+  /// assert!(file.ends_with_ignore_ascii_case(".jpeg"));
+  /// assert!(!file.ends_with_ignore_ascii_case(".png"));
+  /// ```
+  pub fn ends_with_ignore_ascii_case(&self, suffix: impl AsRef<Buf>) -> bool {
+    self.as_ref().ends_with_ignore_ascii_case(suffix)
+  }
+
   /// Returns a new yarn wrapping the given raw yarn.
   ///
   /// # Safety
@@ -462,6 +875,18 @@ where
     }
   }
 
+  /// Returns a mutable reference to the raw yarn.
+  ///
+  /// # Safety
+  ///
+  /// The caller must not replace the raw yarn with one that violates the
+  /// requirements of [`YarnBox::from_raw()`], and must not leak a `HEAP` yarn
+  /// by overwriting it.
+  #[cfg(feature = "zeroize")]
+  pub(crate) unsafe fn raw_mut(&mut self) -> &mut RawYarn {
+    &mut self.raw
+  }
+
   /// Consumes self, inhibits the destructor, and returns the raw yarn.
   pub(crate) const fn into_raw(self) -> RawYarn {
     let raw = self.raw;
@@ -484,6 +909,15 @@ where
     YarnRef::from_static(buf).to_box()
   }
 
+  /// Like [`YarnBox::from_static()`], but returns an error instead of
+  /// panicking if `buf` is too long to be represented by a yarn.
+  pub const fn try_from_static(buf: &'static Buf) -> Result<Self, TooLong> {
+    match YarnRef::try_from_static(buf) {
+      Ok(y) => Ok(y.to_box()),
+      Err(e) => Err(e),
+    }
+  }
+
   /// Copies `buf` and returns an immortal yarn.
   ///
   /// This is a shorthand for `YarnBox::new(buf).immortalize()`, which is an
@@ -497,6 +931,17 @@ where
   /// let long = Yarn::copy("loooooooooooooooooong");
   /// assert_eq!(long, "loooooooooooooooooong");
   /// ```
+  ///
+  /// Yarns short enough to inline are always inlined, even ones built from
+  /// static strings, so copying a common short value like `"true"` or `"0"`
+  /// never allocates, and produces the same yarn that a cache of static yarns
+  /// would hand out.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// assert_eq!(Yarn::copy("true").kind(), YarnKind::Small);
+  /// assert_eq!(Yarn::from_static("true").kind(), YarnKind::Small);
+  /// ```
   pub fn copy(buf: &Buf) -> Self {
     YarnBox::new(buf).immortalize()
   }
@@ -529,16 +974,145 @@ impl<'a> YarnBox<'a, [u8]> {
 
   /// Returns a yarn by taking ownership of the given allocation.
   ///
+  /// As with [`YarnBox::from_string()`], any spare capacity in `bytes` is
+  /// released first.
+  ///
   /// ```
   /// # use byteyarn::*;
   /// let str = vec![0xf0, 0x9f, 0x90, 0x88, 0xe2, 0x80, 0x8d, 0xe2, 0xac, 0x9b];
   /// let yarn = ByteYarn::from_vec(str);
   /// assert_eq!(yarn, "🐈‍⬛".as_bytes());
+  ///
+  /// let mut bytes = Vec::with_capacity(1024);
+  /// bytes.extend_from_slice(b"a buffer with lots of slack");
+  /// let bytes = ByteYarn::from_vec(bytes).into_vec();
+  /// assert_eq!(bytes.capacity(), bytes.len());
   /// ```
   pub fn from_vec(bytes: Vec<u8>) -> Self {
     Self::from_boxed_bytes(bytes.into_boxed_slice())
   }
 
+  /// Like [`YarnBox::from_boxed_bytes()`], but returns an error instead of
+  /// panicking if `bytes` is too long to be represented by a yarn.
+  pub fn try_from_boxed_bytes(bytes: Box<[u8]>) -> Result<Self, TooLong> {
+    TooLong::check(bytes.len(), RawYarn::MAX_LEN)?;
+    Ok(Self::from_boxed_bytes(bytes))
+  }
+
+  /// Like [`YarnBox::from_vec()`], but returns an error instead of panicking
+  /// if `bytes` is too long to be represented by a yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = ByteYarn::try_from_vec(vec![0xff; 64]).unwrap();
+  /// assert_eq!(yarn.len(), 64);
+  /// ```
+  pub fn try_from_vec(bytes: Vec<u8>) -> Result<Self, TooLong> {
+    TooLong::check(bytes.len(), RawYarn::MAX_LEN)?;
+    Ok(Self::from_vec(bytes))
+  }
+
+  /// Builds a new byte yarn from the given formatting arguments
+  /// (see [`format_args!()`]), allocating only when absolutely necessary.
+  ///
+  /// This renders straight into the byte yarn's buffer, rather than building
+  /// a string yarn and converting it. In general, you'll want to use the
+  /// [`byarn!()`] macro, instead.
+  pub fn from_fmt(args: fmt::Arguments) -> Self {
+    unsafe { YarnBox::from_raw(RawYarn::from_fmt_args(args)) }
+  }
+
+  /// Returns a yarn of `len` bytes, which are filled in by `f`.
+  ///
+  /// If the yarn is too long to inline, this allocates exactly once, and `f`
+  /// writes directly into the yarn's final allocation. This lets decoders and
+  /// decompressors skip writing into a temporary buffer first.
+  ///
+  /// The buffer passed to `f` starts out zeroed; to skip that, use
+  /// [`YarnBox::from_fn_uninit()`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `len` is too long to be represented by a yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = ByteYarn::from_fn(32, |buf| {
+  ///   for (i, b) in buf.iter_mut().enumerate() {
+  ///     *b = b'a' + (i % 26) as u8;
+  ///   }
+  /// });
+  /// assert_eq!(yarn, b"abcdefghijklmnopqrstuvwxyzabcdef");
+  /// ```
+  pub fn from_fn(len: usize, f: impl FnOnce(&mut [u8])) -> Self {
+    Self::check_len(len);
+    if len <= RawYarn::SSO_LEN {
+      let mut buf = [0; RawYarn::SSO_LEN];
+      f(&mut buf[..len]);
+      return Self::from_inline_buf(&buf[..len]);
+    }
+
+    let mut buf = vec![0; len].into_boxed_slice();
+    f(&mut buf);
+    Self::from_boxed_bytes(buf)
+  }
+
+  /// Like [`YarnBox::from_fn()`], but does not zero the buffer before passing
+  /// it to `f`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `len` is too long to be represented by a yarn.
+  ///
+  /// # Safety
+  ///
+  /// `f` must initialize every byte of the buffer it is given.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = unsafe {
+  ///   ByteYarn::from_fn_uninit(20, |buf| {
+  ///     for b in buf {
+  ///       b.write(0xff);
+  ///     }
+  ///   })
+  /// };
+  /// assert_eq!(yarn, [0xff; 20]);
+  /// ```
+  pub unsafe fn from_fn_uninit(
+    len: usize,
+    f: impl FnOnce(&mut [MaybeUninit<u8>]),
+  ) -> Self {
+    Self::check_len(len);
+    if len <= RawYarn::SSO_LEN {
+      let mut buf = [MaybeUninit::uninit(); RawYarn::SSO_LEN];
+      f(&mut buf[..len]);
+      // SAFETY: The caller promised that f initialized buf[..len].
+      let buf = slice::from_raw_parts(buf.as_ptr().cast::<u8>(), len);
+      return Self::from_inline_buf(buf);
+    }
+
+    let mut buf = Vec::with_capacity(len);
+    f(&mut buf.spare_capacity_mut()[..len]);
+    // SAFETY: Ditto.
+    buf.set_len(len);
+    Self::from_vec(buf)
+  }
+
+  /// Panics if `len` is too long for a yarn, before any work is done to fill
+  /// it.
+  fn check_len(len: usize) {
+    if let Err(e) = TooLong::check(len, RawYarn::MAX_LEN) {
+      panic!("{e}");
+    }
+  }
+
+  /// Builds an inlined yarn out of a buffer known to be short enough.
+  fn from_inline_buf(buf: &[u8]) -> Self {
+    let raw = RawYarn::from_slice_inlined(buf).unwrap();
+    unsafe { Self::from_raw(raw) }
+  }
+
   /// Tries to convert this yarn into a UTF-8 yarn via [`str::from_utf8()`].
   ///
   /// ```
@@ -570,6 +1144,49 @@ impl<'a> YarnBox<'a, [u8]> {
     unsafe { Ok(YarnBox::from_raw(self.into_raw())) }
   }
 
+  /// Returns an iterator over the `char`s in this yarn, with each invalid
+  /// UTF-8 sequence replaced by U+FFFD REPLACEMENT CHARACTER.
+  ///
+  /// This decodes the yarn as it goes, rather than converting all of it up
+  /// front like [`String::from_utf8_lossy()`] does.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = ByteYarn::new(b"caf\xE9 \xF0\x9F\x90\x88 ok");
+  /// let chars = yarn.chars_lossy().collect::<String>();
+  /// assert_eq!(chars, "caf\u{FFFD} 🐈 ok");
+  ///
+  /// let vowels = yarn.chars_lossy().filter(|c| "aeiou".contains(*c)).count();
+  /// assert_eq!(vowels, 2);
+  /// ```
+  pub fn chars_lossy(&self) -> CharsLossy {
+    CharsLossy::new(self.as_bytes())
+  }
+
+  /// Returns an iterator over the `char`s in this yarn and their byte offsets
+  /// and lengths, with each invalid UTF-8 sequence replaced by U+FFFD
+  /// REPLACEMENT CHARACTER.
+  ///
+  /// Each item is `(offset, char, len)`, where `len` is the number of bytes
+  /// of the yarn the `char` was decoded from. Replacement characters cover the
+  /// whole invalid sequence, so the items tile the yarn, which makes them
+  /// suitable for pointing diagnostics at possibly-invalid input.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = ByteYarn::new(b"a\xF0\x9F\x90\xFFb");
+  /// let chars = yarn.char_indices_lossy().collect::<Vec<_>>();
+  /// assert_eq!(chars, [
+  ///   (0, 'a', 1),
+  ///   (1, '\u{FFFD}', 3),
+  ///   (4, '\u{FFFD}', 1),
+  ///   (5, 'b', 1),
+  /// ]);
+  /// ```
+  pub fn char_indices_lossy(&self) -> CharIndicesLossy {
+    CharIndicesLossy::new(self.as_bytes())
+  }
+
   /// Returns a mutable reference into this yarn's internal buffer.
   ///
   /// If the buffer is not uniquely owned (e.g., it is an alias of some other
@@ -626,6 +1243,116 @@ impl YarnBox<'_, str> {
     unsafe { YarnBox::from_raw(RawYarn::from_fmt_args(args)) }
   }
 
+  /// Like [`YarnBox::from_fmt()`], but returns an error instead of producing
+  /// a yarn if formatting fails or the result is too long to be represented.
+  ///
+  /// Formatting is cut short as soon as the output becomes too long, so
+  /// formatting an untrusted, enormous size does not allocate past the limit.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use std::fmt;
+  ///
+  /// let yarn = Yarn::try_from_fmt(format_args!("{}-{}", "id", 42));
+  /// assert_eq!(yarn.unwrap(), "id-42");
+  ///
+  /// struct Broken;
+  /// impl fmt::Display for Broken {
+  ///   fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+  ///     Err(fmt::Error)
+  ///   }
+  /// }
+  ///
+  /// let err = Yarn::try_from_fmt(format_args!("{}", Broken)).unwrap_err();
+  /// assert_eq!(err, FmtError::Fmt(fmt::Error));
+  /// ```
+  pub fn try_from_fmt(args: fmt::Arguments) -> Result<Self, FmtError> {
+    RawYarn::try_from_fmt_args(args)
+      .map(|raw| unsafe { YarnBox::from_raw(raw) })
+  }
+
+  /// Like [`YarnBox::from_fn()`], but checks that `f` wrote valid UTF-8.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `len` is too long to be represented by a yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = Yarn::from_fn_utf8(6, |buf| buf.copy_from_slice("ɑβγ".as_bytes()));
+  /// assert_eq!(yarn.unwrap(), "ɑβγ");
+  ///
+  /// assert!(Yarn::from_fn_utf8(1, |buf| buf[0] = 0xff).is_err());
+  /// ```
+  pub fn from_fn_utf8(
+    len: usize,
+    f: impl FnOnce(&mut [u8]),
+  ) -> Result<Self, Utf8Error> {
+    YarnBox::<[u8]>::from_fn(len, f).to_utf8()
+  }
+
+  /// Returns the number of `char`s in this yarn.
+  ///
+  /// This is equivalent to `yarn.chars().count()`, but considerably faster on
+  /// long yarns, since it counts a machine word's worth of bytes at a time
+  /// rather than decoding each character.
+  ///
+  /// The count is not cached: a yarn is exactly as large as a fat pointer, so
+  /// there is nowhere to put it. Callers that need it repeatedly should hold
+  /// onto it alongside the yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("{}", "héllo, wörld! 🧶 ".repeat(10));
+  /// assert_eq!(yarn.char_count(), yarn.chars().count());
+  /// assert_eq!(yarn.char_count(), 160);
+  /// ```
+  pub fn char_count(&self) -> usize {
+    self.as_ref().char_count()
+  }
+
+  /// Returns the byte offset of the `n`th character of this yarn.
+  ///
+  /// Returns the length of this yarn if `n` is the number of characters in
+  /// it, and `None` if `n` is greater than that. This scans the yarn from the
+  /// start; use [`Yarn::char_index()`] to answer many queries quickly.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("naïve café");
+  /// assert_eq!(yarn.char_to_byte_index(3), Some(4));
+  /// assert_eq!(yarn.char_to_byte_index(10), Some(12));
+  /// assert_eq!(yarn.char_to_byte_index(11), None);
+  /// ```
+  pub fn char_to_byte_index(&self, n: usize) -> Option<usize> {
+    self.as_ref().char_to_byte_index(n)
+  }
+
+  /// Returns the number of characters in this yarn before byte offset `idx`.
+  ///
+  /// Returns `None` if `idx` is out of bounds or not on a character boundary.
+  /// This scans the yarn from the start; use [`Yarn::char_index()`] to answer
+  /// many queries quickly.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("naïve café");
+  /// assert_eq!(yarn.byte_to_char_index(4), Some(3));
+  /// assert_eq!(yarn.byte_to_char_index(3), None);
+  /// assert_eq!(yarn.byte_to_char_index(12), Some(10));
+  /// ```
+  pub fn byte_to_char_index(&self, idx: usize) -> Option<usize> {
+    self.as_ref().byte_to_char_index(idx)
+  }
+
+  /// Builds an index for quickly translating between byte offsets and
+  /// character offsets in this yarn.
+  ///
+  /// See [`CharIndex`].
+  pub fn char_index(&self) -> CharIndex {
+    self.as_ref().char_index()
+  }
+
   /// Converts this yarn into a string slice.
   pub fn as_str(&self) -> &str {
     self.as_slice()
@@ -657,6 +1384,9 @@ where
   Buf: crate::Buf + ?Sized,
 {
   fn drop(&mut self) {
+    #[cfg(feature = "zeroize")]
+    self.raw.zeroize();
+
     unsafe { self.raw.destroy() }
   }
 }
@@ -692,8 +1422,12 @@ where
   Buf: crate::Buf + ?Sized,
   Slice: AsRef<Buf> + ?Sized,
 {
+  // NOTE: Short yarns are compared a word at a time. Comparisons of long
+  // yarns are already as fast as they can be: slice equality and ordering on
+  // bytes lower to bcmp/memcmp, which the platform's libc implements with
+  // vectorized, alignment-aware loops.
   fn eq(&self, that: &Slice) -> bool {
-    self.as_slice() == that.as_ref()
+    self.raw.eq_slice(YarnRef::buf2raw(that.as_ref()))
   }
 }
 
@@ -705,17 +1439,24 @@ where
   Slice: AsRef<Buf> + ?Sized,
 {
   fn partial_cmp(&self, that: &Slice) -> Option<Ordering> {
-    self.as_slice().partial_cmp(that.as_ref())
+    Some(self.raw.cmp_slice(YarnRef::buf2raw(that.as_ref())))
   }
 }
 
 impl<Buf: crate::Buf + ?Sized> Ord for YarnBox<'_, Buf> {
   fn cmp(&self, that: &Self) -> Ordering {
-    self.as_slice().cmp(that.as_slice())
+    self.raw.cmp_bytes(that.raw)
   }
 }
 
 impl<Buf: crate::Buf + ?Sized> Hash for YarnBox<'_, Buf> {
+  // NOTE: This must hash exactly like Buf; see "Hashing" in the type docs.
+  //
+  // This cannot feed the hasher a cached digest of the contents instead of the
+  // contents themselves, because Borrow<Buf> requires that a yarn hash exactly
+  // like its slice; otherwise, `HashMap<Yarn, _>::get("key")` would miss.
+  // There are also no spare bits to cache one in: the length word only has
+  // room for the tag, and HEAP yarns own a bare `Box<[u8]>`.
   fn hash<H: Hasher>(&self, state: &mut H) {
     self.as_slice().hash(state)
   }
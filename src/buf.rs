@@ -0,0 +1,203 @@
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A builder for large yarns that never reallocates.
+///
+/// A `YarnBuf` collects its contents in a list of fixed-size blocks, rather
+/// than in one buffer that doubles whenever it runs out of room. Once it is
+/// done, [`YarnBuf::freeze()`] copies the blocks into a single allocation of
+/// exactly the right size.
+///
+/// A doubling buffer like a [`String`] copies all of its contents each time
+/// it grows, and may leave up to half of its allocation unused, which then
+/// has to be copied away again to shrink it. When the size of the output is
+/// large and not known in advance, a `YarnBuf` is usually cheaper.
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::fmt::Write;
+///
+/// let mut buf = YarnBuf::<str>::with_block_size(8);
+/// for i in 0..5 {
+///   write!(buf, "line {i}\n").unwrap();
+/// }
+///
+/// let yarn = buf.freeze();
+/// assert_eq!(yarn, "line 0\nline 1\nline 2\nline 3\nline 4\n");
+/// ```
+pub struct YarnBuf<Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  blocks: Vec<Vec<u8>>,
+  block_size: usize,
+  len: usize,
+  _ph: PhantomData<Box<Buf>>,
+}
+
+impl<Buf> YarnBuf<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// The block size used by [`YarnBuf::new()`], in bytes.
+  pub const DEFAULT_BLOCK_SIZE: usize = 4096;
+
+  /// Returns a new, empty buffer.
+  pub const fn new() -> Self {
+    Self::with_block_size(Self::DEFAULT_BLOCK_SIZE)
+  }
+
+  /// Returns a new, empty buffer that allocates blocks of `block_size` bytes.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `block_size` is zero.
+  pub const fn with_block_size(block_size: usize) -> Self {
+    assert!(block_size > 0, "YarnBuf block size must be nonzero");
+    Self {
+      blocks: Vec::new(),
+      block_size,
+      len: 0,
+      _ph: PhantomData,
+    }
+  }
+
+  /// Returns the number of bytes written to this buffer so far.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Checks whether this buffer is empty.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Appends `buf` to the end of this buffer.
+  pub fn push(&mut self, buf: &Buf) {
+    let mut bytes = YarnRef::buf2raw(buf);
+    while !bytes.is_empty() {
+      let block = match self.blocks.last_mut() {
+        Some(block) if block.len() < block.capacity() => block,
+        _ => {
+          self.blocks.push(Vec::with_capacity(self.block_size));
+          self.blocks.last_mut().unwrap()
+        }
+      };
+
+      // A block is never filled past its capacity, so it never reallocates.
+      let n = bytes.len().min(block.capacity() - block.len());
+      block.extend_from_slice(&bytes[..n]);
+      bytes = &bytes[n..];
+      self.len += n;
+    }
+  }
+
+  /// Appends the given formatting arguments (see [`format_args!()`]) to the
+  /// end of this buffer.
+  ///
+  /// Formatted output is always UTF-8, so this works for byte buffers too.
+  /// Like [`yarn!()`], if a formatting trait implementation returns an error,
+  /// whatever it wrote before then is kept.
+  ///
+  /// In general, you'll want to use the [`yarn_write!()`] macro, instead.
+  pub fn push_fmt(&mut self, args: fmt::Arguments) {
+    struct Adapter<'a, Buf: crate::Buf + ?Sized>(&'a mut YarnBuf<Buf>);
+    impl<Buf: crate::Buf + ?Sized> fmt::Write for Adapter<'_, Buf> {
+      fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.push(unsafe {
+          // SAFETY: s is UTF-8, which is valid for either Buf.
+          YarnRef::<Buf>::raw2buf(s.as_bytes())
+        });
+        Ok(())
+      }
+    }
+
+    let _ = fmt::Write::write_fmt(&mut Adapter(self), args);
+  }
+
+  /// Copies the contents of this buffer into a new yarn.
+  ///
+  /// This allocates exactly once, unless the result is short enough to be
+  /// inlined, in which case it does not allocate at all.
+  pub fn freeze(self) -> YarnBox<'static, Buf> {
+    let blocks = self.blocks.iter().map(Vec::as_slice);
+    unsafe {
+      // SAFETY: The blocks add up to self.len bytes. If Buf is str, we only
+      // ever push valid UTF-8 into them; a character may be split between two
+      // blocks, but they are concatenated back together here.
+      YarnBox::from_raw(RawYarn::concat(self.len, blocks))
+    }
+  }
+}
+
+impl YarnBuf<str> {
+  /// Appends `c` to the end of this buffer.
+  pub fn push_char(&mut self, c: char) {
+    self.push(c.encode_utf8(&mut [0; 4]))
+  }
+}
+
+impl YarnBuf<[u8]> {
+  /// Appends `b` to the end of this buffer.
+  pub fn push_byte(&mut self, b: u8) {
+    self.push(&[b])
+  }
+}
+
+impl<Buf> Default for YarnBuf<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Buf> fmt::Debug for YarnBuf<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("YarnBuf")
+      .field("len", &self.len)
+      .field("blocks", &self.blocks.len())
+      .field("block_size", &self.block_size)
+      .finish()
+  }
+}
+
+impl fmt::Write for YarnBuf<str> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    self.push(s);
+    Ok(())
+  }
+
+  fn write_char(&mut self, c: char) -> fmt::Result {
+    self.push_char(c);
+    Ok(())
+  }
+}
+
+impl io::Write for YarnBuf<[u8]> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.push(buf);
+    Ok(buf.len())
+  }
+
+  fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+    self.push(buf);
+    Ok(())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
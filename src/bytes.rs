@@ -0,0 +1,118 @@
+//! Integration with the `bytes` crate.
+//!
+//! Among other things, this makes it cheap to use byte yarns with `prost`.
+//! Prost's `BytesAdapter` trait is sealed, so a generated message cannot hold
+//! a [`ByteYarn`] directly; instead, configure `prost-build` to generate
+//! [`Bytes`] fields with `Config::bytes(["."])`, and convert at the boundary.
+//! Converting a static or heap-allocated yarn into `Bytes` does not copy.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use bytes::Bytes;
+//!
+//! // Stand-in for a prost-generated message.
+//! struct Blob {
+//!   data: Bytes,
+//! }
+//!
+//! let payload = ByteYarn::from_vec(vec![0xaa; 64]);
+//! let ptr = payload.as_ptr();
+//!
+//! let msg = Blob { data: payload.into() };
+//! assert_eq!(msg.data.as_ptr(), ptr);
+//!
+//! let payload = YarnRef::from(&msg.data);
+//! assert_eq!(payload.as_ptr(), ptr);
+//! ```
+//!
+//! Yarns have no reference-counted representation, so there is no yarn
+//! equivalent of [`Bytes::slice()`]: a subyarn of a heap yarn must borrow from
+//! it. To hand out owned pieces of a large payload without copying, keep the
+//! payload as `Bytes`, slice that, and borrow each slice as a yarn where it is
+//! used.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use bytes::Bytes;
+//!
+//! let payload = Bytes::from(b"a large decoded payload".repeat(4));
+//! let field = payload.slice(2..23);
+//! drop(payload);
+//!
+//! let yarn = YarnRef::from(&field);
+//! assert_eq!(yarn, b"large decoded payload");
+//! assert_eq!(yarn.as_ptr(), field.as_ptr());
+//! ```
+
+// This module shares its name with the crate, so the crate needs a leading
+// `::` to not be ambiguous with the glob import below.
+use ::bytes::Bytes;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+impl<Buf> From<YarnBox<'_, Buf>> for Bytes
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts a yarn into `Bytes`.
+  ///
+  /// Static and heap-allocated yarns are handed over without copying; yarns
+  /// that borrow their contents or are inlined are copied.
+  fn from(y: YarnBox<'_, Buf>) -> Self {
+    let y = y.into_bytes();
+    match y.as_ref().as_static_slice() {
+      Some(s) => Bytes::from_static(s),
+      None => Bytes::from(y.into_vec()),
+    }
+  }
+}
+
+impl<Buf> From<YarnRef<'_, Buf>> for Bytes
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts a yarn into `Bytes`.
+  ///
+  /// This copies the yarn unless it is static.
+  fn from(y: YarnRef<'_, Buf>) -> Self {
+    match y.into_bytes().as_static_slice() {
+      Some(s) => Bytes::from_static(s),
+      None => Bytes::copy_from_slice(y.as_bytes()),
+    }
+  }
+}
+
+impl From<Bytes> for YarnBox<'_, [u8]> {
+  /// Converts `Bytes` into a yarn.
+  ///
+  /// Short values are inlined. Long values are copied, unless `bytes` uniquely
+  /// owns a buffer that it can give up; to avoid copying altogether, borrow
+  /// `bytes` as a [`YarnRef`] instead.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use bytes::Bytes;
+  ///
+  /// let yarn = ByteYarn::from(Bytes::from_static(b"gzip"));
+  /// assert!(yarn.is_inlined());
+  /// assert_eq!(yarn, b"gzip");
+  /// ```
+  fn from(bytes: Bytes) -> Self {
+    if let Some(raw) = RawYarn::from_slice_inlined(&bytes) {
+      return unsafe { YarnBox::from_raw(raw) };
+    }
+    YarnBox::from_vec(bytes.into())
+  }
+}
+
+impl<'a> From<&'a Bytes> for YarnRef<'a, [u8]> {
+  /// Borrows `Bytes` as a yarn, without copying.
+  fn from(bytes: &'a Bytes) -> Self {
+    YarnRef::new(bytes)
+  }
+}
@@ -0,0 +1,115 @@
+//! Integration with the `camino` crate.
+
+use camino::Utf8Path;
+use camino::Utf8PathBuf;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<'a> YarnRef<'a, str> {
+  /// Returns this yarn as a [`Utf8Path`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let path = YarnRef::<str>::new("src/lib.rs");
+  /// assert_eq!(path.to_utf8_path().extension(), Some("rs"));
+  /// ```
+  pub fn to_utf8_path(&self) -> &Utf8Path {
+    Utf8Path::new(self.as_str())
+  }
+}
+
+impl YarnBox<'_, str> {
+  /// Returns this yarn as a [`Utf8Path`].
+  ///
+  /// See [`YarnRef::to_utf8_path()`].
+  pub fn to_utf8_path(&self) -> &Utf8Path {
+    Utf8Path::new(self.as_str())
+  }
+}
+
+impl AsRef<Utf8Path> for YarnRef<'_, str> {
+  fn as_ref(&self) -> &Utf8Path {
+    self.to_utf8_path()
+  }
+}
+
+impl AsRef<Utf8Path> for YarnBox<'_, str> {
+  fn as_ref(&self) -> &Utf8Path {
+    self.to_utf8_path()
+  }
+}
+
+impl<'a, Buf> From<&'a Utf8Path> for YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Borrows a `Utf8Path` as a yarn, without copying.
+  fn from(path: &'a Utf8Path) -> Self {
+    YarnRef::new(unsafe {
+      // SAFETY: Utf8Path is always valid UTF-8, so it is a valid Buf.
+      YarnRef::<Buf>::raw2buf(path.as_str().as_bytes())
+    })
+  }
+}
+
+impl<'a, Buf> From<&'a Utf8Path> for YarnBox<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Borrows a `Utf8Path` as a yarn, without copying.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use camino::Utf8Path;
+  ///
+  /// static TARGET: &str = "target/debug/build";
+  /// let yarn = Yarn::from(Utf8Path::new(TARGET));
+  /// assert_eq!(yarn.kind(), YarnKind::Borrowed);
+  /// ```
+  fn from(path: &'a Utf8Path) -> Self {
+    YarnRef::<Buf>::from(path).to_box()
+  }
+}
+
+impl<Buf> From<Utf8PathBuf> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts a `Utf8PathBuf` into a yarn, reusing its allocation unless it is
+  /// short enough to inline.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use camino::Utf8PathBuf;
+  ///
+  /// let path = Utf8PathBuf::from("/usr").join("local").join("bin");
+  /// assert_eq!(Yarn::from(path), "/usr/local/bin");
+  /// ```
+  fn from(path: Utf8PathBuf) -> Self {
+    Self::from_string(path.into_string())
+  }
+}
+
+impl From<YarnBox<'_, str>> for Utf8PathBuf {
+  /// Converts a yarn into a `Utf8PathBuf`, handing over its allocation if it
+  /// is on the heap.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use camino::Utf8PathBuf;
+  ///
+  /// let path = Utf8PathBuf::from(yarn!("{}/{}", "src", "main.rs"));
+  /// assert_eq!(path.file_name(), Some("main.rs"));
+  /// ```
+  fn from(y: YarnBox<str>) -> Self {
+    Utf8PathBuf::from(y.into_string())
+  }
+}
+
+impl From<YarnRef<'_, str>> for Utf8PathBuf {
+  /// Copies a yarn into a `Utf8PathBuf`.
+  fn from(y: YarnRef<str>) -> Self {
+    Utf8PathBuf::from(y.as_str())
+  }
+}
@@ -0,0 +1,73 @@
+//! Integration with the `clap` crate.
+//!
+//! [`Yarn`]s can be used as arguments in the same way `String`s can, and on
+//! Unix, [`ByteYarn`]s can be used to accept arguments that are not UTF-8,
+//! like `OsString`s. Most flag values are short, so they are inlined.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use clap::Parser;
+//!
+//! #[derive(Parser)]
+//! struct Args {
+//!   #[arg(long)]
+//!   name: Yarn,
+//!   #[arg(long)]
+//!   tags: Vec<Yarn>,
+//! }
+//!
+//! let args = Args::parse_from(["app", "--name", "widget", "--tags", "a"]);
+//! assert_eq!(args.name, "widget");
+//! assert!(args.name.is_inlined());
+//! assert_eq!(args.tags, ["a"]);
+//! ```
+//!
+//! [`Yarn`]: crate::Yarn
+//! [`ByteYarn`]: crate::ByteYarn
+
+use clap::builder::MapValueParser;
+use clap::builder::StringValueParser;
+use clap::builder::TypedValueParser;
+use clap::builder::ValueParserFactory;
+
+use crate::YarnBox;
+
+impl ValueParserFactory for YarnBox<'static, str> {
+  type Parser = MapValueParser<StringValueParser, fn(String) -> Self>;
+
+  fn value_parser() -> Self::Parser {
+    StringValueParser::new().map(Self::from_string)
+  }
+}
+
+#[cfg(unix)]
+impl ValueParserFactory for YarnBox<'static, [u8]> {
+  type Parser = MapValueParser<
+    clap::builder::OsStringValueParser,
+    fn(std::ffi::OsString) -> Self,
+  >;
+
+  /// Parses an argument as raw bytes, without requiring it to be UTF-8.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use std::ffi::OsStr;
+  /// use std::os::unix::ffi::OsStrExt;
+  ///
+  /// use clap::Parser;
+  ///
+  /// #[derive(Parser)]
+  /// struct Args {
+  ///   path: ByteYarn,
+  /// }
+  ///
+  /// let path = OsStr::from_bytes(b"caf\xe9.txt");
+  /// let args = Args::parse_from([OsStr::new("app"), path]);
+  /// assert_eq!(args.path, b"caf\xe9.txt");
+  /// ```
+  fn value_parser() -> Self::Parser {
+    use std::os::unix::ffi::OsStringExt;
+    clap::builder::OsStringValueParser::new()
+      .map(|s| Self::from_vec(s.into_vec()))
+  }
+}
@@ -0,0 +1,89 @@
+//! Integration with the `compact_str` crate.
+
+use std::mem;
+
+use compact_str::CompactString;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// The longest string a `CompactString` stores inline.
+const INLINE_LEN: usize = mem::size_of::<String>();
+
+impl<Buf> From<CompactString> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts a `CompactString` into a yarn.
+  ///
+  /// Static strings stay static, short strings are inlined, and heap strings
+  /// reuse their allocation where `compact_str` allows it.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use compact_str::CompactString;
+  ///
+  /// let lit = CompactString::const_new("a compact string that is quite long");
+  /// let yarn = Yarn::from(lit);
+  /// assert!(yarn.to_ref().is_some());
+  ///
+  /// let smol = Yarn::from(CompactString::new("smol"));
+  /// assert_eq!(smol, "smol");
+  /// ```
+  fn from(s: CompactString) -> Self {
+    let raw = if let Some(s) = s.as_static_str() {
+      RawYarn::new(s.as_bytes())
+    } else if let Some(inlined) = RawYarn::from_slice_inlined(s.as_bytes()) {
+      inlined
+    } else {
+      RawYarn::from_heap(s.into_string().into_bytes().into())
+    };
+
+    unsafe {
+      // SAFETY: both [u8] and str can be safely constructed from a str, and
+      // none of the raw yarns above borrow from `s`.
+      YarnBox::from_raw(raw)
+    }
+  }
+}
+
+impl From<YarnBox<'_, str>> for CompactString {
+  /// Converts a yarn into a `CompactString`.
+  ///
+  /// Static yarns are not copied, and heap yarns hand over their allocation
+  /// when they are too long for `CompactString` to inline.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use compact_str::CompactString;
+  ///
+  /// let lit = Yarn::from_static("a static string that is quite long");
+  /// let compact = CompactString::from(lit);
+  /// assert!(compact.as_static_str().is_some());
+  ///
+  /// let smol = CompactString::from(yarn!("smol"));
+  /// assert!(!smol.is_heap_allocated());
+  /// ```
+  fn from(y: YarnBox<str>) -> Self {
+    if let Some(s) = y.as_ref().as_static_slice() {
+      return CompactString::const_new(s);
+    }
+
+    if y.len() <= INLINE_LEN {
+      return CompactString::new(y.as_str());
+    }
+
+    CompactString::from(y.into_string())
+  }
+}
+
+impl From<YarnRef<'_, str>> for CompactString {
+  /// Converts a yarn into a `CompactString`, copying it unless it is static.
+  fn from(y: YarnRef<str>) -> Self {
+    match y.as_static_slice() {
+      Some(s) => CompactString::const_new(s),
+      None => CompactString::new(y.as_str()),
+    }
+  }
+}
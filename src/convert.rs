@@ -1,7 +1,12 @@
 use std::borrow::Borrow;
+use std::ffi::OsStr;
 use std::fmt;
+use std::path::Path;
+use std::rc::Rc;
 use std::str::Utf8Error;
+use std::sync::Arc;
 
+use crate::raw::RawYarn;
 use crate::YarnBox;
 use crate::YarnRef;
 
@@ -119,6 +124,49 @@ where
   }
 }
 
+impl<const N: usize> From<[u8; N]> for YarnBox<'_, [u8]> {
+  /// Converts a byte array into a yarn, which is inlined if it is short
+  /// enough.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mac = ByteYarn::from([0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6]);
+  /// assert!(mac.is_inlined());
+  /// assert_eq!([0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6], mac);
+  ///
+  /// let digest = ByteYarn::from([0xaa; 32]);
+  /// assert!(!digest.is_inlined());
+  /// assert_eq!(digest, [0xaa; 32]);
+  /// ```
+  fn from(bytes: [u8; N]) -> Self {
+    unsafe { Self::from_raw(RawYarn::copy_slice(&bytes)) }
+  }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for YarnBox<'a, [u8]> {
+  fn from(bytes: &'a [u8; N]) -> Self {
+    Self::new(bytes)
+  }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for YarnRef<'a, [u8]> {
+  fn from(bytes: &'a [u8; N]) -> Self {
+    Self::new(bytes)
+  }
+}
+
+impl<const N: usize> PartialEq<YarnBox<'_, [u8]>> for [u8; N] {
+  fn eq(&self, that: &YarnBox<[u8]>) -> bool {
+    self[..] == that[..]
+  }
+}
+
+impl<const N: usize> PartialEq<YarnRef<'_, [u8]>> for [u8; N] {
+  fn eq(&self, that: &YarnRef<[u8]>) -> bool {
+    self[..] == that[..]
+  }
+}
+
 impl From<Box<[u8]>> for YarnBox<'_, [u8]> {
   fn from(s: Box<[u8]>) -> Self {
     Self::from_boxed_bytes(s)
@@ -209,6 +257,86 @@ impl From<YarnRef<'_, str>> for String {
   }
 }
 
+impl<Buf> From<Arc<Buf>> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts an `Arc` into a yarn.
+  ///
+  /// A yarn cannot share an `Arc`'s allocation, so this copies, unless the
+  /// contents are short enough to inline.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use std::sync::Arc;
+  ///
+  /// let key: Arc<str> = Arc::from("user:1234");
+  /// let yarn = Yarn::from(key.clone());
+  /// assert!(yarn.is_inlined());
+  /// assert_eq!(yarn, *key);
+  /// ```
+  fn from(arc: Arc<Buf>) -> Self {
+    Self::from_arc_or_rc(&arc)
+  }
+}
+
+impl<Buf> From<Rc<Buf>> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts an `Rc` into a yarn.
+  ///
+  /// See the `From<Arc<Buf>>` implementation.
+  fn from(rc: Rc<Buf>) -> Self {
+    Self::from_arc_or_rc(&rc)
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from_arc_or_rc(buf: &Buf) -> Self {
+    unsafe { Self::from_raw(RawYarn::copy_slice(YarnRef::buf2raw(buf))) }
+  }
+}
+
+impl<Buf> From<YarnBox<'_, Buf>> for Arc<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnBox<Buf>) -> Self {
+    y.into_arc()
+  }
+}
+
+impl<Buf> From<YarnRef<'_, Buf>> for Arc<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnRef<Buf>) -> Self {
+    y.to_arc()
+  }
+}
+
+impl<Buf> From<YarnBox<'_, Buf>> for Rc<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnBox<Buf>) -> Self {
+    y.into_rc()
+  }
+}
+
+impl<Buf> From<YarnRef<'_, Buf>> for Rc<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnRef<Buf>) -> Self {
+    y.to_rc()
+  }
+}
+
 // AsRef / Borrow
 
 impl<Buf> AsRef<Buf> for YarnBox<'_, Buf>
@@ -229,6 +357,43 @@ where
   }
 }
 
+impl AsRef<OsStr> for YarnBox<'_, str> {
+  fn as_ref(&self) -> &OsStr {
+    self.as_str().as_ref()
+  }
+}
+
+impl AsRef<OsStr> for YarnRef<'_, str> {
+  fn as_ref(&self) -> &OsStr {
+    self.as_str().as_ref()
+  }
+}
+
+impl AsRef<Path> for YarnBox<'_, str> {
+  /// Views this yarn as a path, so that it can be passed directly to
+  /// filesystem APIs.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use std::path::Path;
+  ///
+  /// let dir = yarn!("{}/src", env!("CARGO_MANIFEST_DIR"));
+  /// assert!(std::fs::metadata(&dir).unwrap().is_dir());
+  ///
+  /// let file = Path::new(&dir).join(yarn!("lib.rs"));
+  /// assert!(file.ends_with("src/lib.rs"));
+  /// ```
+  fn as_ref(&self) -> &Path {
+    self.as_str().as_ref()
+  }
+}
+
+impl AsRef<Path> for YarnRef<'_, str> {
+  fn as_ref(&self) -> &Path {
+    self.as_str().as_ref()
+  }
+}
+
 impl<Buf> Borrow<Buf> for YarnBox<'_, Buf>
 where
   Buf: crate::Buf + ?Sized,
@@ -0,0 +1,64 @@
+//! A debugging aid that catches large copies of borrowed yarns.
+
+use std::env;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+#[cfg(doc)]
+use crate::*;
+
+/// The environment variable that sets the initial copy limit.
+const VAR: &str = "BYTEYARN_COPY_LIMIT";
+
+/// Sets the largest borrowed yarn, in bytes, that may be implicitly copied;
+/// `None` removes the limit.
+///
+/// Some operations, such as [`YarnBox::immortalize()`] and
+/// [`YarnBox::into_string()`], quietly copy a yarn's contents when it borrows
+/// them instead of owning them. In a pipeline that is meant to be zero-copy,
+/// such a copy is usually a bug. While a limit is set, copying a borrowed yarn
+/// longer than the limit panics, so that the backtrace points at the culprit.
+/// Copies of static yarns are not limited.
+///
+/// The initial limit is read from the `BYTEYARN_COPY_LIMIT` environment
+/// variable; if it is not set, there is no limit.
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::panic;
+///
+/// byteyarn::set_copy_limit(Some(16));
+///
+/// let text = String::from("a very long borrowed string");
+/// let yarn = YarnBox::<str>::new(&text);
+/// assert!(panic::catch_unwind(|| yarn.aliased().immortalize()).is_err());
+/// assert!(panic::catch_unwind(|| yarn.aliased().into_string()).is_err());
+///
+/// let lit = Yarn::from_static("a very long static string");
+/// assert_eq!(lit.into_string(), "a very long static string");
+///
+/// byteyarn::set_copy_limit(None);
+/// assert_eq!(yarn.immortalize(), text);
+/// ```
+pub fn set_copy_limit(limit: Option<usize>) {
+  self::limit().store(limit.unwrap_or(usize::MAX), Ordering::Relaxed);
+}
+
+/// Panics if copying a borrowed yarn of `len` bytes would exceed the limit.
+pub(crate) fn check(len: usize) {
+  let max = limit().load(Ordering::Relaxed);
+  assert!(
+    len <= max,
+    "implicitly copied a borrowed yarn of {len} bytes, which exceeds the copy \
+     limit of {max} bytes"
+  );
+}
+
+fn limit() -> &'static AtomicUsize {
+  static LIMIT: OnceLock<AtomicUsize> = OnceLock::new();
+  LIMIT.get_or_init(|| {
+    let limit = env::var(VAR).ok().and_then(|v| v.trim().parse().ok());
+    AtomicUsize::new(limit.unwrap_or(usize::MAX))
+  })
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A multiset of yarns, which counts how many times each yarn occurs.
+///
+/// Counting a yarn only allocates the first time that yarn is seen, and not
+/// even then if it is short enough to inline; afterwards, it is looked up by
+/// reference. This makes a `YarnCounter` well-suited for counting words or
+/// tokens in a large input, where most occurrences are repeats.
+///
+/// ```
+/// # use byteyarn::*;
+/// let text = "the cat sat on the mat with the hat";
+/// let counts = text.split(' ').collect::<YarnCounter<str>>();
+///
+/// assert_eq!(counts.get("the"), 3);
+/// assert_eq!(counts.get("cat"), 1);
+/// assert_eq!(counts.get("dog"), 0);
+/// assert_eq!(counts.len(), 7);
+/// assert_eq!(counts.total(), 9);
+/// ```
+pub struct YarnCounter<Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  counts: HashMap<YarnBox<'static, Buf>, usize>,
+  total: usize,
+}
+
+impl<Buf> YarnCounter<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  /// Returns a new, empty counter.
+  pub fn new() -> Self {
+    Self {
+      counts: HashMap::new(),
+      total: 0,
+    }
+  }
+
+  /// Returns the number of distinct yarns in this counter.
+  pub fn len(&self) -> usize {
+    self.counts.len()
+  }
+
+  /// Checks whether this counter is empty.
+  pub fn is_empty(&self) -> bool {
+    self.counts.is_empty()
+  }
+
+  /// Returns the sum of all counts in this counter.
+  pub fn total(&self) -> usize {
+    self.total
+  }
+
+  /// Removes every yarn from this counter.
+  pub fn clear(&mut self) {
+    self.counts.clear();
+    self.total = 0;
+  }
+
+  /// Counts one occurrence of `key`, returning its new count.
+  pub fn incr(&mut self, key: impl AsRef<Buf>) -> usize {
+    self.add(key, 1)
+  }
+
+  /// Counts `n` occurrences of `key`, returning its new count.
+  ///
+  /// `key` is only copied if it has not been counted before.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut counts = YarnCounter::<[u8]>::new();
+  /// assert_eq!(counts.add(b"GET", 10), 10);
+  /// assert_eq!(counts.incr(b"GET"), 11);
+  /// assert_eq!(counts.total(), 11);
+  /// ```
+  pub fn add(&mut self, key: impl AsRef<Buf>, n: usize) -> usize {
+    let key = key.as_ref();
+    self.total += n;
+    if let Some(count) = self.counts.get_mut(key) {
+      *count += n;
+      return *count;
+    }
+
+    self.counts.insert(YarnBox::copy(key), n);
+    n
+  }
+
+  /// Returns the number of times `key` has been counted.
+  pub fn get(&self, key: impl AsRef<Buf>) -> usize {
+    self.counts.get(key.as_ref()).copied().unwrap_or(0)
+  }
+
+  /// Removes `key` from this counter, returning its count.
+  pub fn remove(&mut self, key: impl AsRef<Buf>) -> usize {
+    let count = self.counts.remove(key.as_ref()).unwrap_or(0);
+    self.total -= count;
+    count
+  }
+
+  /// Returns an iterator over the yarns in this counter and their counts, in
+  /// arbitrary order.
+  pub fn iter(&self) -> impl Iterator<Item = (YarnRef<Buf>, usize)> + '_ {
+    self.counts.iter().map(|(k, &v)| (k.as_ref(), v))
+  }
+
+  /// Returns the `n` yarns with the highest counts, from most to least common.
+  ///
+  /// Yarns with equal counts are ordered by their bytes, so the result is
+  /// deterministic.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let counts = "a b c b c c d d d"
+  ///   .split(' ')
+  ///   .collect::<YarnCounter<str>>();
+  /// let top = counts.most_common(3);
+  /// assert_eq!(top, [("c".into(), 3), ("d".into(), 3), ("b".into(), 2)]);
+  /// ```
+  pub fn most_common(&self, n: usize) -> Vec<(YarnRef<Buf>, usize)> {
+    let mut entries = self.iter().collect::<Vec<_>>();
+    entries.sort_unstable_by(|(k1, v1), (k2, v2)| {
+      v2.cmp(v1).then_with(|| k1.as_bytes().cmp(k2.as_bytes()))
+    });
+    entries.truncate(n);
+    entries
+  }
+}
+
+impl<K, Buf> FromIterator<K> for YarnCounter<Buf>
+where
+  K: AsRef<Buf>,
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+    let mut counter = Self::new();
+    counter.extend(iter);
+    counter
+  }
+}
+
+impl<K, Buf> Extend<K> for YarnCounter<Buf>
+where
+  K: AsRef<Buf>,
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+    for key in iter {
+      self.incr(key);
+    }
+  }
+}
+
+impl<Buf> Default for YarnCounter<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Buf> Clone for YarnCounter<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn clone(&self) -> Self {
+    Self {
+      counts: self.counts.clone(),
+      total: self.total,
+    }
+  }
+}
+
+impl<Buf> fmt::Debug for YarnCounter<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
@@ -0,0 +1,233 @@
+//! Splitting CSV records into fields.
+
+use std::iter::FusedIterator;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<'a, Buf> YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns an iterator over the fields of the CSV record at the start of
+  /// this yarn.
+  ///
+  /// Fields are separated by `delimiter`, and may be wrapped in `quote`s, in
+  /// which case they may contain delimiters and line breaks, and a doubled
+  /// quote stands for a single one, as described in RFC 4180. The record ends
+  /// at the first line break outside of quotes; the rest of the yarn is
+  /// available from [`CsvFields::remainder()`].
+  ///
+  /// Fields are subyarns of this one, except for those that contain escaped
+  /// quotes, which must be copied to remove them.
+  ///
+  /// The parser is forgiving: a quote that appears after the start of a field
+  /// is an ordinary character, text after a closing quote is appended to the
+  /// field, and an unterminated quoted field runs to the end of the yarn.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `delimiter` or `quote` is not ASCII.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let csv = String::from(
+  ///   "id,name,motto\r\n\
+  ///    1,\"Doe, Jane Elizabeth\",\"always say \"\"please\"\"\"\r\n\
+  ///    2,Bob,\r\n",
+  /// );
+  /// let mut rest = YarnRef::<str>::new(&csv);
+  ///
+  /// let mut rows = Vec::new();
+  /// while !rest.is_empty() {
+  ///   let mut fields = rest.csv_fields(b',', b'"');
+  ///   rows.push(fields.by_ref().collect::<Vec<_>>());
+  ///   rest = fields.remainder();
+  /// }
+  ///
+  /// assert_eq!(rows, [
+  ///   ["id", "name", "motto"],
+  ///   ["1", "Doe, Jane Elizabeth", "always say \"please\""],
+  ///   ["2", "Bob", ""],
+  /// ]);
+  ///
+  /// // Only the field with escaped quotes had to be copied.
+  /// assert_eq!(rows[1][1].kind(), YarnKind::Borrowed);
+  /// assert_eq!(rows[1][2].kind(), YarnKind::Heap);
+  /// ```
+  pub fn csv_fields(self, delimiter: u8, quote: u8) -> CsvFields<'a, Buf> {
+    assert!(
+      delimiter.is_ascii() && quote.is_ascii(),
+      "CSV delimiter and quote must be ASCII",
+    );
+
+    CsvFields {
+      yarn: self,
+      pos: 0,
+      done: self.is_empty(),
+      delimiter,
+      quote,
+    }
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns an iterator over the fields of the CSV record at the start of
+  /// this yarn.
+  ///
+  /// See [`YarnRef::csv_fields()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = ByteYarn::from_static(b"GET;/index.html;'a;b'\n");
+  /// let fields = yarn.csv_fields(b';', b'\'').collect::<Vec<_>>();
+  /// assert_eq!(fields, [&b"GET"[..], b"/index.html", b"a;b"]);
+  /// ```
+  pub fn csv_fields(&self, delimiter: u8, quote: u8) -> CsvFields<Buf> {
+    self.as_ref().csv_fields(delimiter, quote)
+  }
+}
+
+/// An iterator over the fields of a CSV record.
+///
+/// See [`YarnRef::csv_fields()`].
+#[derive(Copy, Clone)]
+pub struct CsvFields<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  yarn: YarnRef<'a, Buf>,
+  pos: usize,
+  done: bool,
+  delimiter: u8,
+  quote: u8,
+}
+
+impl<'a, Buf> CsvFields<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns the part of the yarn that has not been parsed yet.
+  ///
+  /// Once this iterator is exhausted, this is whatever follows the record's
+  /// line break.
+  pub fn remainder(&self) -> YarnRef<'a, Buf> {
+    unsafe {
+      // SAFETY: pos is always just after an ASCII byte, or at either end of the
+      // yarn, so it is on a character boundary.
+      self.yarn.slice_unchecked(self.pos..self.yarn.len())
+    }
+  }
+
+  /// Returns the index of the first delimiter or line break at or after `i`.
+  fn field_end(&self, i: usize) -> usize {
+    let bytes = self.yarn.as_bytes();
+    bytes[i..]
+      .iter()
+      .position(|&b| b == self.delimiter || b == b'\n' || b == b'\r')
+      .map_or(bytes.len(), |n| i + n)
+  }
+
+  /// Parses a quoted field starting at `start`, returning it and the index
+  /// just past its end.
+  fn quoted(&self, start: usize) -> (YarnBox<'a, Buf>, usize) {
+    let yarn = self.yarn;
+    let bytes = yarn.as_bytes();
+
+    // Escaped quotes are the only thing that forces a copy, so the unescaped
+    // field is only built once one is found.
+    let mut unescaped = None::<Vec<u8>>;
+    let mut chunk = start + 1;
+    let mut i = chunk;
+    let content_end = loop {
+      let Some(n) = bytes[i..].iter().position(|&b| b == self.quote) else {
+        i = bytes.len();
+        break i;
+      };
+
+      let q = i + n;
+      i = q + 1;
+      if bytes.get(i) != Some(&self.quote) {
+        break q;
+      }
+
+      let buf = unescaped.get_or_insert_with(Vec::new);
+      buf.extend_from_slice(&bytes[chunk..i]);
+      i += 1;
+      chunk = i;
+    };
+
+    let end = self.field_end(i);
+    if unescaped.is_none() && i == end {
+      let field = unsafe {
+        // SAFETY: The field is delimited by ASCII quotes, so it is on
+        // character boundaries.
+        yarn.slice_unchecked(chunk..content_end)
+      };
+      return (field.to_box(), end);
+    }
+
+    let mut buf = unescaped.unwrap_or_default();
+    buf.extend_from_slice(&bytes[chunk..content_end]);
+    buf.extend_from_slice(&bytes[i..end]);
+    let field = unsafe {
+      // SAFETY: buf consists of pieces of a valid Buf that were split at ASCII
+      // bytes, so it is a valid Buf too.
+      YarnBox::from_raw(RawYarn::from_heap(buf.into()))
+    };
+    (field, end)
+  }
+}
+
+impl<'a, Buf> Iterator for CsvFields<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Item = YarnBox<'a, Buf>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+
+    let yarn = self.yarn;
+    let bytes = yarn.as_bytes();
+    let start = self.pos;
+    let (field, end) = match bytes.get(start) {
+      Some(&b) if b == self.quote => self.quoted(start),
+      _ => {
+        let end = self.field_end(start);
+        let field = unsafe {
+          // SAFETY: The field is delimited by ASCII bytes, so it is on
+          // character boundaries.
+          yarn.slice_unchecked(start..end)
+        };
+        (field.to_box(), end)
+      }
+    };
+
+    self.pos = match bytes.get(end) {
+      Some(&b) if b == self.delimiter => end + 1,
+      Some(b'\r') if bytes.get(end + 1) == Some(&b'\n') => {
+        self.done = true;
+        end + 2
+      }
+      Some(_) => {
+        self.done = true;
+        end + 1
+      }
+      None => {
+        self.done = true;
+        end
+      }
+    };
+
+    Some(field)
+  }
+}
+
+impl<Buf> FusedIterator for CsvFields<'_, Buf> where Buf: crate::Buf + ?Sized {}
@@ -0,0 +1,233 @@
+use std::fmt;
+
+use crate::elements::Decode;
+use crate::Pattern;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A cursor over a yarn, for writing lexers.
+///
+/// A cursor tracks a byte position in a source yarn, and steps over it one
+/// element at a time: `char`s for `str` yarns, and bytes for `[u8]` yarns.
+/// Whatever it steps over can be returned as a subyarn of the source, so
+/// tokens do not copy, unless the source is inlined.
+///
+/// ```
+/// # use byteyarn::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Token<'a> {
+///   Ident(YarnRef<'a, str>),
+///   Int(YarnRef<'a, str>),
+///   Punct(char),
+/// }
+///
+/// fn lex(src: YarnRef<str>) -> Vec<(usize, Token)> {
+///   let mut cur = src.cursor();
+///   let mut tokens = Vec::new();
+///   loop {
+///     cur.take_while(char::is_whitespace);
+///     let start = cur.pos();
+///     let Some(c) = cur.peek() else { break };
+///     let tok = if c.is_alphabetic() {
+///       Token::Ident(cur.take_while(char::is_alphanumeric))
+///     } else if c.is_ascii_digit() {
+///       Token::Int(cur.take_while(|c: char| c.is_ascii_digit()))
+///     } else {
+///       Token::Punct(cur.bump().unwrap())
+///     };
+///     tokens.push((start, tok));
+///   }
+///   tokens
+/// }
+///
+/// let src = yarn!("let answer = 42;");
+/// assert_eq!(lex(src.as_ref()), [
+///   (0, Token::Ident("let".into())),
+///   (4, Token::Ident("answer".into())),
+///   (11, Token::Punct('=')),
+///   (13, Token::Int("42".into())),
+///   (15, Token::Punct(';')),
+/// ]);
+/// ```
+#[derive(Copy, Clone)]
+pub struct YarnCursor<'a, Buf = [u8]>
+where
+  Buf: Decode + ?Sized,
+{
+  src: YarnRef<'a, Buf>,
+  pos: usize,
+}
+
+impl<'a, Buf> YarnCursor<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  /// Returns a new cursor at the start of `src`.
+  pub fn new(src: YarnRef<'a, Buf>) -> Self {
+    Self { src, pos: 0 }
+  }
+
+  /// Returns the yarn this cursor is over.
+  pub fn source(&self) -> YarnRef<'a, Buf> {
+    self.src
+  }
+
+  /// Returns the byte offset of this cursor in its source.
+  pub fn pos(&self) -> usize {
+    self.pos
+  }
+
+  /// Checks whether this cursor has reached the end of its source.
+  pub fn is_eof(&self) -> bool {
+    self.pos == self.src.len()
+  }
+
+  /// Returns the part of the source after this cursor.
+  pub fn rest(&self) -> YarnRef<'a, Buf> {
+    self.slice(self.pos, self.src.len())
+  }
+
+  /// Returns the part of the source from byte offset `start` up to this
+  /// cursor, such as the text of the token that was just lexed.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `start` is after this cursor or, if `Buf` is `str`, not on a
+  /// character boundary.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let src = YarnRef::<str>::new("x += 1");
+  /// let mut cur = src.cursor();
+  /// cur.bump();
+  /// cur.take_while(char::is_whitespace);
+  ///
+  /// let start = cur.pos();
+  /// cur.take_while(['+', '-', '=']);
+  /// assert_eq!(cur.since(start), "+=");
+  /// ```
+  pub fn since(&self, start: usize) -> YarnRef<'a, Buf> {
+    assert!(
+      start <= self.pos && Buf::is_boundary(self.src.as_slice(), start),
+      "cursor offset {start} is not a valid start for a span ending at {}",
+      self.pos,
+    );
+    self.slice(start, self.pos)
+  }
+
+  /// Returns the element after this cursor, without moving it.
+  pub fn peek(&self) -> Option<Buf::Item> {
+    Buf::decode(self.src.as_slice(), self.pos).map(|(item, _)| item)
+  }
+
+  /// Moves this cursor past the next element, returning it.
+  pub fn bump(&mut self) -> Option<Buf::Item> {
+    let (item, len) = Buf::decode(self.src.as_slice(), self.pos)?;
+    self.pos += len;
+    Some(item)
+  }
+
+  /// Moves this cursor past the next element if it matches `pat`, returning
+  /// whether it did.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut cur = YarnRef::<str>::new("->").cursor();
+  /// assert!(cur.eat('-'));
+  /// assert!(!cur.eat('-'));
+  /// assert!(cur.eat(['>', '=']));
+  /// assert!(cur.is_eof());
+  /// ```
+  pub fn eat(&mut self, mut pat: impl Pattern<Buf>) -> bool {
+    match Buf::decode(self.src.as_slice(), self.pos) {
+      Some((item, len)) if pat.matches(item) => {
+        self.pos += len;
+        true
+      }
+      _ => false,
+    }
+  }
+
+  /// Moves this cursor past every element matching `pat`, returning the part
+  /// of the source it moved over.
+  pub fn take_while(&mut self, mut pat: impl Pattern<Buf>) -> YarnRef<'a, Buf> {
+    let start = self.pos;
+    while let Some((item, len)) = Buf::decode(self.src.as_slice(), self.pos) {
+      if !pat.matches(item) {
+        break;
+      }
+      self.pos += len;
+    }
+    self.slice(start, self.pos)
+  }
+
+  /// Moves this cursor up to the next occurrence of `byte`, or to the end of
+  /// the source if there is none, returning the part of the source it moved
+  /// over.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `Buf` is `str` and `byte` is not ASCII.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut cur = YarnRef::<str>::new("// a comment\nnext").cursor();
+  /// assert_eq!(cur.take_until(b'\n'), "// a comment");
+  /// assert_eq!(cur.rest(), "\nnext");
+  /// ```
+  pub fn take_until(&mut self, byte: u8) -> YarnRef<'a, Buf> {
+    assert!(
+      Buf::BYTEWISE || byte.is_ascii(),
+      "cannot search a string yarn for the non-ASCII byte {byte:#04x}",
+    );
+
+    let start = self.pos;
+    let bytes = &self.src.as_bytes()[start..];
+    self.pos += bytes.iter().position(|&b| b == byte).unwrap_or(bytes.len());
+    self.slice(start, self.pos)
+  }
+
+  fn slice(&self, start: usize, end: usize) -> YarnRef<'a, Buf> {
+    unsafe {
+      // SAFETY: The cursor only ever stops on element boundaries, and callers
+      // check that start is on one.
+      self.src.slice_unchecked(start..end)
+    }
+  }
+}
+
+impl<'a, Buf> YarnRef<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  /// Returns a cursor at the start of this yarn.
+  ///
+  /// See [`YarnCursor`].
+  pub fn cursor(self) -> YarnCursor<'a, Buf> {
+    YarnCursor::new(self)
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  /// Returns a cursor at the start of this yarn.
+  ///
+  /// See [`YarnCursor`].
+  pub fn cursor(&self) -> YarnCursor<Buf> {
+    self.as_ref().cursor()
+  }
+}
+
+impl<Buf> fmt::Debug for YarnCursor<'_, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("YarnCursor")
+      .field("pos", &self.pos)
+      .field("rest", &self.rest())
+      .finish()
+  }
+}
@@ -0,0 +1,39 @@
+//! Integration with the `defmt` crate.
+//!
+//! Yarns are logged as string arguments, regardless of how they are stored;
+//! this never allocates or copies.
+
+use defmt::Format;
+use defmt::Formatter;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl Format for YarnBox<'_, str> {
+  fn format(&self, f: Formatter) {
+    self.as_ref().format(f)
+  }
+}
+
+impl Format for YarnRef<'_, str> {
+  fn format(&self, f: Formatter) {
+    defmt::write!(f, "{=str}", self.as_str())
+  }
+}
+
+impl Format for YarnBox<'_, [u8]> {
+  fn format(&self, f: Formatter) {
+    self.as_ref().format(f)
+  }
+}
+
+/// Byte yarns are logged as strings if they are valid UTF-8, and as byte
+/// slices otherwise.
+impl Format for YarnRef<'_, [u8]> {
+  fn format(&self, f: Formatter) {
+    match std::str::from_utf8(self.as_bytes()) {
+      Ok(s) => defmt::write!(f, "{=str}", s),
+      Err(_) => defmt::write!(f, "{=[u8]}", self.as_bytes()),
+    }
+  }
+}
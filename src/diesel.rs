@@ -0,0 +1,153 @@
+//! Integration with the `diesel` crate.
+//!
+//! String yarns map to [`Text`], and byte yarns to [`Binary`], so they can be
+//! used as fields of `Queryable` and `Insertable` structs in place of
+//! `String` and `Vec<u8>`. Loaded yarns are copies, unless they are short
+//! enough to inline.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use diesel::prelude::*;
+//! use diesel::sql_types::Binary;
+//! use diesel::sql_types::Text;
+//!
+//! let mut db = SqliteConnection::establish(":memory:").unwrap();
+//!
+//! let (name, data): (Yarn, ByteYarn) = diesel::select((
+//!   yarn!("user{}", 42).into_sql::<Text>(),
+//!   ByteYarn::new(b"\x00\x01").into_sql::<Binary>(),
+//! ))
+//! .get_result(&mut db)
+//! .unwrap();
+//!
+//! assert_eq!(name, "user42");
+//! assert!(name.is_inlined());
+//! assert_eq!(data, [0, 1]);
+//!
+//! let text = "a string too long to inline";
+//! let long: Yarn = diesel::select(text.into_sql::<Text>())
+//!   .get_result(&mut db)
+//!   .unwrap();
+//! assert_eq!(long, text);
+//! assert_eq!(long.kind(), YarnKind::Heap);
+//! ```
+
+use diesel::backend::Backend;
+use diesel::deserialize;
+use diesel::deserialize::FromSql;
+use diesel::deserialize::FromSqlRow;
+use diesel::expression::AsExpression;
+use diesel::serialize;
+use diesel::serialize::Output;
+use diesel::serialize::ToSql;
+use diesel::sql_types::Binary;
+use diesel::sql_types::Text;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+// Diesel's derives generate the `AsExpression` and `FromSqlRow` boilerplate,
+// the same way diesel does for `String` and `Vec<u8>`.
+#[allow(dead_code)]
+mod foreign {
+  use super::*;
+
+  #[derive(AsExpression, FromSqlRow)]
+  #[diesel(foreign_derive)]
+  #[diesel(sql_type = Text)]
+  struct YarnProxy(YarnBox<'static, str>);
+
+  #[derive(AsExpression)]
+  #[diesel(foreign_derive)]
+  #[diesel(sql_type = Text)]
+  struct YarnRefProxy<'a>(YarnRef<'a, str>);
+
+  #[derive(AsExpression, FromSqlRow)]
+  #[diesel(foreign_derive)]
+  #[diesel(sql_type = Binary)]
+  struct ByteYarnProxy(YarnBox<'static, [u8]>);
+
+  #[derive(AsExpression)]
+  #[diesel(foreign_derive)]
+  #[diesel(sql_type = Binary)]
+  struct ByteYarnRefProxy<'a>(YarnRef<'a, [u8]>);
+}
+
+impl<DB> ToSql<Text, DB> for YarnRef<'_, str>
+where
+  DB: Backend,
+  str: ToSql<Text, DB>,
+{
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    self.as_str().to_sql(out)
+  }
+}
+
+impl<DB> ToSql<Text, DB> for YarnBox<'_, str>
+where
+  DB: Backend,
+  str: ToSql<Text, DB>,
+{
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    self.as_str().to_sql(out)
+  }
+}
+
+impl<DB> ToSql<Binary, DB> for YarnRef<'_, [u8]>
+where
+  DB: Backend,
+  [u8]: ToSql<Binary, DB>,
+{
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    self.as_slice().to_sql(out)
+  }
+}
+
+impl<DB> ToSql<Binary, DB> for YarnBox<'_, [u8]>
+where
+  DB: Backend,
+  [u8]: ToSql<Binary, DB>,
+{
+  fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+    self.as_slice().to_sql(out)
+  }
+}
+
+impl<ST, DB> FromSql<ST, DB> for YarnBox<'_, str>
+where
+  DB: Backend,
+  *const str: FromSql<ST, DB>,
+{
+  fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+    let ptr = <*const str as FromSql<ST, DB>>::from_sql(bytes)?;
+    let str = unsafe {
+      // SAFETY: Diesel's pointer impls never return null, and the pointee
+      // lives as long as the raw value does.
+      &*ptr
+    };
+    Ok(unsafe {
+      // SAFETY: str is a valid str.
+      YarnBox::from_raw(RawYarn::copy_slice(str.as_bytes()))
+    })
+  }
+}
+
+impl<ST, DB> FromSql<ST, DB> for YarnBox<'_, [u8]>
+where
+  DB: Backend,
+  *const [u8]: FromSql<ST, DB>,
+{
+  fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+    let ptr = <*const [u8] as FromSql<ST, DB>>::from_sql(bytes)?;
+    let bytes = unsafe {
+      // SAFETY: Diesel's pointer impls never return null, and the pointee
+      // lives as long as the raw value does.
+      &*ptr
+    };
+    Ok(unsafe {
+      // SAFETY: Any bytes are a valid [u8].
+      YarnBox::from_raw(RawYarn::copy_slice(bytes))
+    })
+  }
+}
@@ -0,0 +1,56 @@
+//! Integration with the `ecow` crate.
+
+use ecow::EcoString;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> From<EcoString> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts an `EcoString` into a yarn.
+  ///
+  /// `EcoString`s share their heap buffers, so long strings must be copied;
+  /// short ones are inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use ecow::EcoString;
+  ///
+  /// let yarn = Yarn::from(EcoString::from("eco"));
+  /// assert_eq!(yarn, "eco");
+  /// ```
+  fn from(s: EcoString) -> Self {
+    unsafe {
+      // SAFETY: both [u8] and str can be safely constructed from a str, and
+      // copy_slice() returns a uniquely-owned raw yarn.
+      YarnBox::from_raw(RawYarn::copy_slice(s.as_bytes()))
+    }
+  }
+}
+
+impl From<YarnBox<'_, str>> for EcoString {
+  /// Converts a yarn into an `EcoString`.
+  ///
+  /// Short yarns become inline `EcoString`s; longer ones are copied.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use ecow::EcoString;
+  ///
+  /// let eco = EcoString::from(yarn!("eco"));
+  /// assert_eq!(eco, "eco");
+  /// ```
+  fn from(y: YarnBox<str>) -> Self {
+    EcoString::from(y.as_str())
+  }
+}
+
+impl From<YarnRef<'_, str>> for EcoString {
+  /// Converts a yarn into an `EcoString`.
+  fn from(y: YarnRef<str>) -> Self {
+    EcoString::from(y.as_str())
+  }
+}
@@ -0,0 +1,860 @@
+//! Owning iterators over the elements of a yarn.
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+mod z {
+  /// A buffer type whose elements can be decoded one at a time.
+  pub trait Decode: crate::Buf {
+    /// The element type: bytes for `[u8]`, and `char`s for `str`.
+    type Item: Copy;
+
+    /// Whether every element is exactly one byte long.
+    const BYTEWISE: bool;
+
+    /// Checks whether `idx` is a valid place to split this buffer.
+    fn is_boundary(buf: &Self, idx: usize) -> bool;
+
+    /// Decodes the element starting at byte offset `idx`, returning it and its
+    /// length in bytes.
+    fn decode(buf: &Self, idx: usize) -> Option<(Self::Item, usize)>;
+
+    /// Decodes the element ending at byte offset `end`, returning it and its
+    /// length in bytes.
+    fn decode_back(buf: &Self, end: usize) -> Option<(Self::Item, usize)>;
+
+    /// Returns the byte offset `n` elements after `idx`, or `None` if there
+    /// are fewer than `n` elements left.
+    fn advance(buf: &Self, mut idx: usize, n: usize) -> Option<usize> {
+      for _ in 0..n {
+        idx += Self::decode(buf, idx)?.1;
+      }
+      Some(idx)
+    }
+  }
+
+  impl Decode for [u8] {
+    type Item = u8;
+    const BYTEWISE: bool = true;
+
+    fn is_boundary(buf: &Self, idx: usize) -> bool {
+      idx <= buf.len()
+    }
+
+    fn decode(buf: &Self, idx: usize) -> Option<(u8, usize)> {
+      buf.get(idx).map(|&b| (b, 1))
+    }
+
+    fn decode_back(buf: &Self, end: usize) -> Option<(u8, usize)> {
+      buf.get(end.checked_sub(1)?).map(|&b| (b, 1))
+    }
+
+    fn advance(buf: &Self, idx: usize, n: usize) -> Option<usize> {
+      idx.checked_add(n).filter(|&end| end <= buf.len())
+    }
+  }
+
+  impl Decode for str {
+    type Item = char;
+    const BYTEWISE: bool = false;
+
+    fn is_boundary(buf: &Self, idx: usize) -> bool {
+      buf.is_char_boundary(idx)
+    }
+
+    fn decode(buf: &Self, idx: usize) -> Option<(char, usize)> {
+      let c = buf.get(idx..)?.chars().next()?;
+      Some((c, c.len_utf8()))
+    }
+
+    fn decode_back(buf: &Self, end: usize) -> Option<(char, usize)> {
+      let c = buf.get(..end)?.chars().next_back()?;
+      Some((c, c.len_utf8()))
+    }
+  }
+}
+
+pub(crate) use z::Decode;
+
+/// A pattern that matches single elements of a yarn, for use with
+/// [`YarnRef::trim_matches_yarn()`] and friends.
+///
+/// For `str` yarns, this is implemented for `char`s, sets of `char`s, and
+/// `FnMut(char) -> bool` closures. For `[u8]` yarns, it is implemented for
+/// bytes, sets of bytes, and `FnMut(u8) -> bool` closures.
+pub trait Pattern<Buf: Decode + ?Sized> {
+  /// Checks whether `item` matches this pattern.
+  fn matches(&mut self, item: Buf::Item) -> bool;
+}
+
+impl Pattern<str> for char {
+  fn matches(&mut self, item: char) -> bool {
+    *self == item
+  }
+}
+
+impl Pattern<str> for &[char] {
+  fn matches(&mut self, item: char) -> bool {
+    self.contains(&item)
+  }
+}
+
+impl<const N: usize> Pattern<str> for [char; N] {
+  fn matches(&mut self, item: char) -> bool {
+    self.contains(&item)
+  }
+}
+
+impl<const N: usize> Pattern<str> for &[char; N] {
+  fn matches(&mut self, item: char) -> bool {
+    self.contains(&item)
+  }
+}
+
+impl<F: FnMut(char) -> bool> Pattern<str> for F {
+  fn matches(&mut self, item: char) -> bool {
+    self(item)
+  }
+}
+
+impl Pattern<[u8]> for u8 {
+  fn matches(&mut self, item: u8) -> bool {
+    *self == item
+  }
+}
+
+impl Pattern<[u8]> for &[u8] {
+  fn matches(&mut self, item: u8) -> bool {
+    self.contains(&item)
+  }
+}
+
+impl<const N: usize> Pattern<[u8]> for [u8; N] {
+  fn matches(&mut self, item: u8) -> bool {
+    self.contains(&item)
+  }
+}
+
+impl<const N: usize> Pattern<[u8]> for &[u8; N] {
+  fn matches(&mut self, item: u8) -> bool {
+    self.contains(&item)
+  }
+}
+
+impl<F: FnMut(u8) -> bool> Pattern<[u8]> for F {
+  fn matches(&mut self, item: u8) -> bool {
+    self(item)
+  }
+}
+
+impl<'a, Buf> YarnRef<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  /// Splits this yarn in two at byte offset `idx`.
+  ///
+  /// Both halves are subyarns of this one, so this does not copy unless this
+  /// yarn is inlined.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `idx` is out of bounds or, if `Buf` is `str`, not on a
+  /// character boundary.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let frame = YarnRef::<[u8]>::new(b"\x00\x05hello, and the next frame");
+  /// let (header, rest) = frame.split_at(2);
+  /// let len = u16::from_be_bytes([header[0], header[1]]) as usize;
+  /// let (payload, rest) = rest.split_at(len);
+  /// assert_eq!(payload, b"hello");
+  /// assert_eq!(rest, b", and the next frame");
+  /// assert!(!rest.is_inlined());
+  ///
+  /// let word = YarnRef::<str>::new("naïve");
+  /// assert_eq!(word.split_at(4).0, "naï");
+  /// ```
+  ///
+  /// ```should_panic
+  /// # use byteyarn::*;
+  /// let word = YarnRef::<str>::new("naïve");
+  /// word.split_at(3); // Inside the "ï".
+  /// ```
+  pub fn split_at(self, idx: usize) -> (Self, Self) {
+    assert!(
+      Buf::is_boundary(self.as_slice(), idx),
+      "index {idx} is not a valid split point for a yarn of length {}",
+      self.len(),
+    );
+
+    unsafe {
+      // SAFETY: We just checked that idx is in bounds and on a character
+      // boundary.
+      (
+        self.slice_unchecked(0..idx),
+        self.slice_unchecked(idx..self.len()),
+      )
+    }
+  }
+
+  /// Drops the first `n` bytes of this yarn, in place.
+  ///
+  /// This does not copy unless this yarn is inlined, in which case the copy is
+  /// very cheap anyway; it is intended as the "consume input" step of a parser
+  /// loop.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n` is out of bounds or, if `Buf` is `str`, not on a character
+  /// boundary.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut input = YarnRef::<str>::new("λx. x");
+  /// input.advance(3);
+  /// assert_eq!(input, ". x");
+  /// ```
+  pub fn advance(&mut self, n: usize) {
+    *self = self.split_at(n).1;
+  }
+
+  /// Drops `prefix` from the start of this yarn, in place, if this yarn starts
+  /// with it.
+  ///
+  /// Returns whether `prefix` was removed.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut input = YarnRef::<str>::new("let x = 42;");
+  /// assert!(input.consume_prefix("let"));
+  /// assert!(!input.consume_prefix("mut"));
+  /// assert_eq!(input, " x = 42;");
+  /// ```
+  pub fn consume_prefix(&mut self, prefix: impl AsRef<Buf>) -> bool {
+    let prefix = YarnRef::buf2raw(prefix.as_ref());
+    if !self.as_bytes().starts_with(prefix) {
+      return false;
+    }
+
+    unsafe {
+      // SAFETY: prefix is a Buf, and therefore ends on a character boundary,
+      // and we just checked that it is a prefix of this yarn.
+      *self = self.slice_unchecked(prefix.len()..self.len());
+    }
+    true
+  }
+
+  /// Returns the subyarn with all leading and trailing elements that match
+  /// `pat` removed.
+  ///
+  /// This does not copy unless this yarn is inlined. Unlike the
+  /// `str::trim_*_matches()` methods, which are available through `Deref`,
+  /// this returns a yarn rather than a slice, but only matches single
+  /// elements.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let token = YarnRef::<str>::new("\"quoted\"");
+  /// assert_eq!(token.trim_matches_yarn('"'), "quoted");
+  ///
+  /// let list = YarnRef::<str>::new("[(1, 2)]");
+  /// assert_eq!(list.trim_matches_yarn(['[', ']', '(', ')']), "1, 2");
+  ///
+  /// let bytes = YarnRef::<[u8]>::new(b"\0\0data\0");
+  /// assert_eq!(bytes.trim_matches_yarn(0), b"data");
+  ///
+  /// // The str methods are still available, for substring patterns.
+  /// let key = YarnRef::<str>::new("--key--");
+  /// assert_eq!(key.trim_start_matches("-"), "key--");
+  /// assert_eq!(key.trim_end_matches("--"), "--key");
+  /// ```
+  pub fn trim_matches_yarn(self, mut pat: impl Pattern<Buf>) -> Self {
+    self.trim_start_by(&mut pat).trim_end_by(&mut pat)
+  }
+
+  /// Returns the subyarn with all leading elements that match `pat` removed.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let line = YarnRef::<str>::new("    indented");
+  /// assert_eq!(line.trim_start_matches_yarn(char::is_whitespace), "indented");
+  /// ```
+  pub fn trim_start_matches_yarn(self, mut pat: impl Pattern<Buf>) -> Self {
+    self.trim_start_by(&mut pat)
+  }
+
+  /// Returns the subyarn with all trailing elements that match `pat` removed.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let line = YarnRef::<[u8]>::new(b"GET / HTTP/1.1\r\n");
+  /// assert_eq!(line.trim_end_matches_yarn(b"\r\n"), b"GET / HTTP/1.1");
+  /// ```
+  pub fn trim_end_matches_yarn(self, mut pat: impl Pattern<Buf>) -> Self {
+    self.trim_end_by(&mut pat)
+  }
+
+  fn trim_start_by(self, pat: &mut impl Pattern<Buf>) -> Self {
+    let buf = self.as_slice();
+    let mut start = 0;
+    while let Some((item, len)) = Buf::decode(buf, start) {
+      if !pat.matches(item) {
+        break;
+      }
+      start += len;
+    }
+
+    unsafe {
+      // SAFETY: start only ever advances by whole elements, so it is in
+      // bounds and on a character boundary.
+      self.slice_unchecked(start..self.len())
+    }
+  }
+
+  fn trim_end_by(self, pat: &mut impl Pattern<Buf>) -> Self {
+    let buf = self.as_slice();
+    let mut end = self.len();
+    while let Some((item, len)) = Buf::decode_back(buf, end) {
+      if !pat.matches(item) {
+        break;
+      }
+      end -= len;
+    }
+
+    unsafe {
+      // SAFETY: end only ever retreats by whole elements, so it is in bounds
+      // and on a character boundary.
+      self.slice_unchecked(0..end)
+    }
+  }
+
+  /// Splits this yarn around the first element that matches `pat`, returning
+  /// the subyarns before and after it.
+  ///
+  /// Returns `None` if no element matches. This does not copy unless this
+  /// yarn is inlined. Unlike `str::split_once()`, which is available through
+  /// `Deref`, this returns yarns rather than slices, but only matches single
+  /// elements.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let pair = YarnRef::<str>::new("key=value=more");
+  /// assert_eq!(pair.split_once_yarn('='), Some(("key".into(), "value=more".into())));
+  ///
+  /// let url = YarnRef::<str>::new("https://example.com");
+  /// let (scheme, rest) = url.split_once_yarn(':').unwrap();
+  /// assert_eq!(scheme, "https");
+  /// assert_eq!(rest, "//example.com");
+  ///
+  /// assert_eq!(url.split_once_yarn(char::is_whitespace), None);
+  ///
+  /// // `str::split_once()` is still available, for substring patterns.
+  /// assert_eq!(url.split_once("://"), Some(("https", "example.com")));
+  /// ```
+  pub fn split_once_yarn(
+    self,
+    mut pat: impl Pattern<Buf>,
+  ) -> Option<(Self, Self)> {
+    let buf = self.as_slice();
+    let mut idx = 0;
+    while let Some((item, len)) = Buf::decode(buf, idx) {
+      if pat.matches(item) {
+        return Some(self.split_around(idx, idx + len));
+      }
+      idx += len;
+    }
+    None
+  }
+
+  /// Splits this yarn around the last element that matches `pat`, returning
+  /// the subyarns before and after it.
+  ///
+  /// Returns `None` if no element matches. This does not copy unless this
+  /// yarn is inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let path = YarnRef::<[u8]>::new(b"/usr/local/bin");
+  /// let (dir, name) = path.rsplit_once_yarn(b'/').unwrap();
+  /// assert_eq!(dir, b"/usr/local");
+  /// assert_eq!(name, b"bin");
+  ///
+  /// assert_eq!(path.rsplit_once_yarn(b'\\'), None);
+  /// ```
+  pub fn rsplit_once_yarn(
+    self,
+    mut pat: impl Pattern<Buf>,
+  ) -> Option<(Self, Self)> {
+    let buf = self.as_slice();
+    let mut idx = self.len();
+    while let Some((item, len)) = Buf::decode_back(buf, idx) {
+      if pat.matches(item) {
+        return Some(self.split_around(idx - len, idx));
+      }
+      idx -= len;
+    }
+    None
+  }
+
+  /// Returns the longest prefix this yarn shares with `that`, as a subyarn of
+  /// this yarn.
+  ///
+  /// For `str` yarns, the prefix ends on a character boundary, so it never
+  /// splits a character that the two yarns only partially share.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let path = YarnRef::<str>::new("/usr/local/bin");
+  /// assert_eq!(path.common_prefix("/usr/lib"), "/usr/l");
+  /// assert_eq!(path.common_prefix("relative"), "");
+  ///
+  /// // "é" and "ê" share their first byte, but not their second.
+  /// let word = YarnRef::<str>::new("café");
+  /// assert_eq!(word.common_prefix("cafê"), "caf");
+  /// assert_eq!(word.into_bytes().common_prefix("cafê".as_bytes()).len(), 4);
+  /// ```
+  pub fn common_prefix(self, that: impl AsRef<Buf>) -> Self {
+    let (a, b) = (self.as_bytes(), YarnRef::buf2raw(that.as_ref()));
+    let mut end = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    while !Buf::is_boundary(self.as_slice(), end) {
+      end -= 1;
+    }
+
+    unsafe {
+      // SAFETY: end is at most this yarn's length, and we just backed it up
+      // to a character boundary.
+      self.slice_unchecked(0..end)
+    }
+  }
+
+  /// Returns the longest suffix this yarn shares with `that`, as a subyarn of
+  /// this yarn.
+  ///
+  /// For `str` yarns, the suffix starts on a character boundary.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let file = YarnRef::<[u8]>::new(b"src/main.rs");
+  /// assert_eq!(file.common_suffix(b"tests/domain.rs"), b"main.rs");
+  ///
+  /// let word = YarnRef::<str>::new("naïve");
+  /// assert_eq!(word.common_suffix("nave"), "ve");
+  /// ```
+  pub fn common_suffix(self, that: impl AsRef<Buf>) -> Self {
+    let (a, b) = (self.as_bytes(), YarnRef::buf2raw(that.as_ref()));
+    let pairs = a.iter().rev().zip(b.iter().rev());
+    let mut start = a.len() - pairs.take_while(|(x, y)| x == y).count();
+    while !Buf::is_boundary(self.as_slice(), start) {
+      start += 1;
+    }
+
+    unsafe {
+      // SAFETY: start is at most this yarn's length, and we just moved it
+      // forward to a character boundary.
+      self.slice_unchecked(start..self.len())
+    }
+  }
+
+  fn split_around(self, start: usize, end: usize) -> (Self, Self) {
+    unsafe {
+      // SAFETY: start and end are the bounds of a whole element, so they are
+      // in bounds and on character boundaries.
+      (
+        self.slice_unchecked(0..start),
+        self.slice_unchecked(end..self.len()),
+      )
+    }
+  }
+
+  /// Returns an iterator over non-overlapping subyarns of `n` elements each:
+  /// `n` bytes for `[u8]` yarns, and `n` `char`s for `str` yarns.
+  ///
+  /// The last chunk is shorter if this yarn's length is not a multiple of
+  /// `n`. The chunks are subyarns of this one, so they do not copy unless this
+  /// yarn is inlined.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n` is zero.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = YarnRef::<str>::new("h€llo");
+  /// let chunks = yarn.chunk_yarns(2).collect::<Vec<_>>();
+  /// assert_eq!(chunks, ["h€", "ll", "o"]);
+  /// ```
+  pub fn chunk_yarns(self, n: usize) -> Chunks<'a, Buf> {
+    assert!(n != 0, "chunk size must be nonzero");
+    Chunks {
+      yarn: self,
+      idx: 0,
+      n,
+    }
+  }
+
+  /// Returns an iterator over overlapping subyarns of `n` elements each: `n`
+  /// bytes for `[u8]` yarns, and `n` `char`s for `str` yarns.
+  ///
+  /// If this yarn has fewer than `n` elements, the iterator is empty. The
+  /// windows are subyarns of this one, so they do not copy unless this yarn
+  /// is inlined.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n` is zero.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = YarnRef::<str>::new("h€llo");
+  /// let windows = yarn.window_yarns(3).collect::<Vec<_>>();
+  /// assert_eq!(windows, ["h€l", "€ll", "llo"]);
+  ///
+  /// assert_eq!(yarn.window_yarns(6).count(), 0);
+  /// ```
+  pub fn window_yarns(self, n: usize) -> Windows<'a, Buf> {
+    assert!(n != 0, "window size must be nonzero");
+    let end = Buf::advance(self.as_slice(), 0, n);
+    Windows {
+      yarn: self,
+      start: 0,
+      end,
+    }
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  /// Returns the subyarn with all leading and trailing elements that match
+  /// `pat` removed.
+  ///
+  /// See [`YarnRef::trim_matches_yarn()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("<{}>", "tag");
+  /// assert_eq!(yarn.trim_matches_yarn(['<', '>']), "tag");
+  /// assert_eq!(yarn.trim_start_matches("<t"), "ag>");
+  /// ```
+  pub fn trim_matches_yarn(&self, pat: impl Pattern<Buf>) -> YarnRef<Buf> {
+    self.as_ref().trim_matches_yarn(pat)
+  }
+
+  /// Returns the subyarn with all leading elements that match `pat` removed.
+  ///
+  /// See [`YarnRef::trim_start_matches_yarn()`].
+  pub fn trim_start_matches_yarn(
+    &self,
+    pat: impl Pattern<Buf>,
+  ) -> YarnRef<Buf> {
+    self.as_ref().trim_start_matches_yarn(pat)
+  }
+
+  /// Returns the subyarn with all trailing elements that match `pat` removed.
+  ///
+  /// See [`YarnRef::trim_end_matches_yarn()`].
+  pub fn trim_end_matches_yarn(&self, pat: impl Pattern<Buf>) -> YarnRef<Buf> {
+    self.as_ref().trim_end_matches_yarn(pat)
+  }
+
+  /// Splits this yarn in two at byte offset `idx`.
+  ///
+  /// See [`YarnRef::split_at()`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `idx` is out of bounds or, if `Buf` is `str`, not on a
+  /// character boundary.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("{}{}", "key", "value");
+  /// let (key, value) = yarn.split_at(3);
+  /// assert_eq!(key, "key");
+  /// assert_eq!(value, "value");
+  /// ```
+  pub fn split_at(&self, idx: usize) -> (YarnRef<Buf>, YarnRef<Buf>) {
+    self.as_ref().split_at(idx)
+  }
+
+  /// Splits this yarn in two at byte offset `idx`, consuming it.
+  ///
+  /// Unlike [`YarnBox::split_at()`], the halves do not borrow from this yarn,
+  /// so they can outlive it. This only works if this yarn is not on the heap,
+  /// since the halves cannot share ownership of a heap allocation; otherwise,
+  /// this yarn is returned unchanged as the error.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `idx` is out of bounds or, if `Buf` is `str`, not on a
+  /// character boundary.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// fn next_line(input: &mut ByteYarn) -> ByteYarn {
+  ///   let end = input.iter().position(|&b| b == b'\n').unwrap_or(input.len());
+  ///   let (line, rest) = std::mem::take(input).split_into(end).unwrap();
+  ///   *input = rest;
+  ///   line
+  /// }
+  ///
+  /// let mut input = ByteYarn::from_static(b"first line\nsecond line");
+  /// assert_eq!(next_line(&mut input), b"first line");
+  /// assert_eq!(input, b"\nsecond line");
+  ///
+  /// let heap = ByteYarn::from_vec(b"a heap-allocated yarn".to_vec());
+  /// assert!(heap.split_into(1).is_err());
+  /// ```
+  pub fn split_into(self, idx: usize) -> Result<(Self, Self), Self> {
+    let Some(yarn) = self.to_ref() else {
+      return Err(self);
+    };
+
+    let (a, b) = yarn.split_at(idx);
+    Ok((a.to_box(), b.to_box()))
+  }
+
+  /// Splits this yarn around the first element that matches `pat`.
+  ///
+  /// See [`YarnRef::split_once_yarn()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let header = yarn!("{}: {}", "Host", "example.com");
+  /// let (name, value) = header.split_once_yarn(':').unwrap();
+  /// assert_eq!(name, "Host");
+  /// assert_eq!(value.trim_start_matches_yarn(' '), "example.com");
+  /// ```
+  pub fn split_once_yarn(
+    &self,
+    pat: impl Pattern<Buf>,
+  ) -> Option<(YarnRef<Buf>, YarnRef<Buf>)> {
+    self.as_ref().split_once_yarn(pat)
+  }
+
+  /// Splits this yarn around the last element that matches `pat`.
+  ///
+  /// See [`YarnRef::rsplit_once_yarn()`].
+  pub fn rsplit_once_yarn(
+    &self,
+    pat: impl Pattern<Buf>,
+  ) -> Option<(YarnRef<Buf>, YarnRef<Buf>)> {
+    self.as_ref().rsplit_once_yarn(pat)
+  }
+
+  /// Returns the longest prefix this yarn shares with `that`.
+  ///
+  /// See [`YarnRef::common_prefix()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let keys = [yarn!("interned"), yarn!("internal"), yarn!("interval")];
+  /// let prefix = keys[1..]
+  ///   .iter()
+  ///   .fold(keys[0].as_ref(), |p, k| p.common_prefix(k));
+  /// assert_eq!(prefix, "inter");
+  /// ```
+  pub fn common_prefix(&self, that: impl AsRef<Buf>) -> YarnRef<Buf> {
+    self.as_ref().common_prefix(that)
+  }
+
+  /// Returns the longest suffix this yarn shares with `that`.
+  ///
+  /// See [`YarnRef::common_suffix()`].
+  pub fn common_suffix(&self, that: impl AsRef<Buf>) -> YarnRef<Buf> {
+    self.as_ref().common_suffix(that)
+  }
+
+  /// Returns an iterator over non-overlapping subyarns of `n` elements each.
+  ///
+  /// See [`YarnRef::chunk_yarns()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = ByteYarn::new(b"a frame of bytes");
+  /// let chunks = yarn.chunk_yarns(6).collect::<Vec<_>>();
+  /// assert_eq!(chunks, [&b"a fram"[..], b"e of b", b"ytes"]);
+  ///
+  /// // The slice methods are still available.
+  /// assert_eq!(yarn.chunks(6).count(), 3);
+  /// assert_eq!(yarn.windows(2).next(), Some(&b"a "[..]));
+  /// ```
+  pub fn chunk_yarns(&self, n: usize) -> Chunks<Buf> {
+    self.as_ref().chunk_yarns(n)
+  }
+
+  /// Returns an iterator over overlapping subyarns of `n` elements each.
+  ///
+  /// See [`YarnRef::window_yarns()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("n-grams");
+  /// let trigrams = yarn.window_yarns(3).collect::<Vec<_>>();
+  /// assert_eq!(trigrams, ["n-g", "-gr", "gra", "ram", "ams"]);
+  /// ```
+  pub fn window_yarns(&self, n: usize) -> Windows<Buf> {
+    self.as_ref().window_yarns(n)
+  }
+}
+
+/// An iterator over the elements of a yarn: bytes for `[u8]` yarns, and
+/// `char`s for `str` yarns.
+///
+/// Unlike [`std::slice::Iter`], this iterator holds onto a copy of the yarn,
+/// so it can be returned even when the yarn is inlined.
+#[derive(Copy, Clone)]
+pub struct Elements<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  yarn: YarnRef<'a, Buf>,
+  idx: usize,
+}
+
+#[cfg_attr(not(any(feature = "nom", feature = "winnow")), allow(dead_code))]
+impl<'a, Buf> Elements<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  pub(crate) fn new(yarn: YarnRef<'a, Buf>) -> Self {
+    Self { yarn, idx: 0 }
+  }
+
+  /// Returns the part of the yarn that has not been yielded yet.
+  pub fn rest(&self) -> YarnRef<'a, Buf> {
+    unsafe {
+      // SAFETY: self.idx only ever advances by whole elements, so it is in
+      // bounds and on a character boundary.
+      self.yarn.slice_unchecked(self.idx..self.yarn.len())
+    }
+  }
+}
+
+impl<Buf> Iterator for Elements<'_, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  type Item = Buf::Item;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (item, len) = Buf::decode(self.yarn.as_slice(), self.idx)?;
+    self.idx += len;
+    Some(item)
+  }
+}
+
+/// An iterator over the elements of a yarn and their byte offsets.
+///
+/// See [`Elements`].
+#[derive(Copy, Clone)]
+pub struct ElementIndices<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  inner: Elements<'a, Buf>,
+}
+
+#[cfg_attr(not(any(feature = "nom", feature = "winnow")), allow(dead_code))]
+impl<'a, Buf> ElementIndices<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  pub(crate) fn new(yarn: YarnRef<'a, Buf>) -> Self {
+    Self {
+      inner: Elements::new(yarn),
+    }
+  }
+
+  /// Returns the part of the yarn that has not been yielded yet.
+  pub fn rest(&self) -> YarnRef<'a, Buf> {
+    self.inner.rest()
+  }
+}
+
+impl<Buf> Iterator for ElementIndices<'_, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  type Item = (usize, Buf::Item);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let idx = self.inner.idx;
+    self.inner.next().map(|item| (idx, item))
+  }
+}
+
+/// An iterator over non-overlapping subyarns of a yarn.
+///
+/// See [`YarnRef::chunk_yarns()`].
+#[derive(Copy, Clone)]
+pub struct Chunks<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  yarn: YarnRef<'a, Buf>,
+  idx: usize,
+  n: usize,
+}
+
+impl<'a, Buf> Iterator for Chunks<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  type Item = YarnRef<'a, Buf>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let start = self.idx;
+    if start == self.yarn.len() {
+      return None;
+    }
+
+    self.idx = Buf::advance(self.yarn.as_slice(), start, self.n)
+      .unwrap_or(self.yarn.len());
+    unsafe {
+      // SAFETY: self.idx only ever advances by whole elements, so it is in
+      // bounds and on a character boundary.
+      Some(self.yarn.slice_unchecked(start..self.idx))
+    }
+  }
+}
+
+/// An iterator over overlapping subyarns of a yarn.
+///
+/// See [`YarnRef::window_yarns()`].
+#[derive(Copy, Clone)]
+pub struct Windows<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  yarn: YarnRef<'a, Buf>,
+  start: usize,
+  end: Option<usize>,
+}
+
+impl<'a, Buf> Iterator for Windows<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  type Item = YarnRef<'a, Buf>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (start, end) = (self.start, self.end?);
+    let buf = self.yarn.as_slice();
+
+    self.end = Buf::advance(buf, end, 1);
+    if self.end.is_some() {
+      self.start = Buf::advance(buf, start, 1).unwrap();
+    }
+
+    unsafe {
+      // SAFETY: start and end only ever advance by whole elements, so they
+      // are in bounds and on character boundaries.
+      Some(self.yarn.slice_unchecked(start..end))
+    }
+  }
+}
@@ -0,0 +1,121 @@
+use std::error::Error;
+use std::fmt;
+use std::str::Utf8Error;
+
+#[cfg(doc)]
+use crate::*;
+
+/// An error returned by the `try_` constructors when a buffer is too long to
+/// be represented by a yarn.
+///
+/// ```
+/// # use byteyarn::*;
+/// let err = TooLong { len: 5000, max: 4096 };
+/// assert_eq!(
+///   err.to_string(),
+///   "buffer of 5000 bytes exceeds the maximum yarn length of 4096 bytes",
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TooLong {
+  /// The length of the offending buffer, in bytes.
+  pub len: usize,
+  /// The longest buffer the yarn type can hold, in bytes.
+  pub max: usize,
+}
+
+impl TooLong {
+  /// Checks that `len` is at most `max`.
+  pub(crate) const fn check(len: usize, max: usize) -> Result<(), Self> {
+    match len <= max {
+      true => Ok(()),
+      false => Err(Self { len, max }),
+    }
+  }
+}
+
+impl fmt::Display for TooLong {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "buffer of {} bytes exceeds the maximum yarn length of {} bytes",
+      self.len, self.max
+    )
+  }
+}
+
+impl Error for TooLong {}
+
+/// An error returned by [`Yarn::try_from_fmt()`] when formatting fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FmtError {
+  /// A formatting trait implementation returned an error.
+  Fmt(fmt::Error),
+  /// The formatted output would have been too long to be represented by a
+  /// yarn.
+  ///
+  /// `len` is the length the output had reached when formatting was stopped,
+  /// not the length it would eventually have had.
+  TooLong(TooLong),
+}
+
+impl fmt::Display for FmtError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Fmt(e) => write!(f, "formatting yarn failed: {e}"),
+      Self::TooLong(e) => write!(f, "formatting yarn failed: {e}"),
+    }
+  }
+}
+
+impl Error for FmtError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      Self::Fmt(e) => Some(e),
+      Self::TooLong(e) => Some(e),
+    }
+  }
+}
+
+impl From<fmt::Error> for FmtError {
+  fn from(e: fmt::Error) -> Self {
+    Self::Fmt(e)
+  }
+}
+
+impl From<TooLong> for FmtError {
+  fn from(e: TooLong) -> Self {
+    Self::TooLong(e)
+  }
+}
+
+/// An error returned by [`YarnRef::from_wire()`] when its input does not
+/// start with a valid encoded yarn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WireError {
+  /// The input ended partway through the yarn; more input may complete it.
+  Truncated,
+  /// The length prefix was malformed, or too large for a yarn.
+  BadLength,
+  /// The yarn was expected to be UTF-8, but was not.
+  Utf8(Utf8Error),
+}
+
+impl fmt::Display for WireError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Truncated => f.write_str("encoded yarn is truncated"),
+      Self::BadLength => f.write_str("encoded yarn has an invalid length"),
+      Self::Utf8(e) => write!(f, "encoded yarn is not UTF-8: {e}"),
+    }
+  }
+}
+
+impl Error for WireError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      Self::Utf8(e) => Some(e),
+      _ => None,
+    }
+  }
+}
@@ -0,0 +1,582 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker::PhantomData;
+use std::mem;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::slice;
+
+use crate::raw::RawYarn;
+use crate::utf8;
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A three-word yarn, which inlines strings up to one byte shorter than
+/// itself, and can hold buffers of any length.
+///
+/// A [`YarnBox`] is two words wide, so it can only inline strings of up to 15
+/// bytes on 64-bit architectures. Many common strings, such as UUIDs in binary
+/// form, short hashes, and identifiers, are just slightly longer than that.
+/// `FatYarn` spends one extra word to push the small string optimization
+/// threshold to 23 bytes on 64-bit architectures (and 11 on 32-bit ones).
+///
+/// The extra word also means that a `FatYarn` does not need to steal any bits
+/// from its length, so it can hold any buffer Rust can, whereas a [`YarnBox`]
+/// is limited to a quarter of the address space. This matters on 32-bit
+/// architectures, where that limit is 1 GiB.
+///
+/// Converting between `FatYarn` and [`YarnBox`] does not copy, except for
+/// strings that only a `FatYarn` can inline.
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::mem::size_of;
+/// assert_eq!(size_of::<FatYarn>(), 3 * size_of::<usize>());
+///
+/// let hash = "6f1ed002ab5595859014ebf";
+/// assert!(Yarn::inlined(hash).is_none());
+/// # #[cfg(target_pointer_width = "64")]
+/// assert!(FatYarn::<str>::inlined(hash).is_some());
+///
+/// let yarn = FatYarn::<str>::copy(hash);
+/// assert_eq!(yarn, hash);
+/// ```
+pub struct FatYarn<'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  repr: Repr,
+  _ph: PhantomData<&'a Buf>,
+}
+
+/// The longest string a `FatYarn` can inline.
+const SSO_LEN: usize = 3 * mem::size_of::<usize>() - 1;
+
+/// The bit set in the last byte of a `FatYarn` that is not inlined; the low
+/// bits of that byte are the yarn's kind, one of the `RawYarn` constants.
+const BIG: u8 = 0x80;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+union Repr {
+  small: Small,
+  big: Big,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Small {
+  data: [u8; SSO_LEN],
+  len: u8,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Big {
+  ptr: *const u8,
+  len: usize,
+  pad: [u8; mem::size_of::<usize>() - 1],
+  tag: u8,
+}
+
+const _: () = {
+  assert!(mem::size_of::<Small>() == mem::size_of::<Big>());
+  assert!(mem::size_of::<Repr>() == 3 * mem::size_of::<usize>());
+  assert!(SSO_LEN < BIG as usize);
+};
+
+// FatYarn does not expose &mut through &self.
+unsafe impl<Buf: crate::Buf + ?Sized> Send for FatYarn<'_, Buf> {}
+unsafe impl<Buf: crate::Buf + ?Sized> Sync for FatYarn<'_, Buf> {}
+
+impl<'a, Buf> FatYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// The longest string, in bytes, that a `FatYarn` can inline.
+  ///
+  /// This is 23 on 64-bit architectures and 11 on 32-bit ones.
+  pub const SSO_LEN: usize = SSO_LEN;
+
+  /// Returns a yarn pointing to the given slice, without copying.
+  ///
+  /// Short slices are inlined, instead.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let foo = FatYarn::<str>::new("Byzantium");
+  /// assert_eq!(foo.len(), 9);
+  /// ```
+  pub const fn new(buf: &'a Buf) -> Self {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.len() <= SSO_LEN {
+      return Self::from_small(bytes);
+    }
+
+    unsafe {
+      // SAFETY: We copy the lifetime from buf into self.
+      Self::from_ptr_len_tag(bytes.as_ptr(), bytes.len(), RawYarn::ALIASED)
+    }
+  }
+
+  /// Returns a new yarn containing the contents of the given slice, or `None`
+  /// if it is longer than [`FatYarn::SSO_LEN`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// # #[cfg(target_pointer_width = "64")] {
+  /// let uuid = FatYarn::<str>::inlined("b8ad8f2b-b1e3-4a4c-8c3f9");
+  /// assert!(uuid.is_none());
+  ///
+  /// let uuid = FatYarn::<str>::inlined("b8ad8f2b-b1e3-4a4c-8c3f");
+  /// assert_eq!(uuid.unwrap(), "b8ad8f2b-b1e3-4a4c-8c3f");
+  /// # }
+  /// ```
+  pub const fn inlined(buf: &Buf) -> Option<Self> {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.len() > SSO_LEN {
+      return None;
+    }
+
+    Some(Self::from_small(bytes))
+  }
+
+  /// Converts a yarn into a `FatYarn`.
+  ///
+  /// If `yarn` is short enough for a `FatYarn` to inline, it is copied (and
+  /// any heap allocation it was holding is freed); otherwise, this does not
+  /// copy.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = Yarn::from_string("a string that is too long to inline".into());
+  /// let ptr = yarn.as_ptr();
+  ///
+  /// let fat = FatYarn::from_box(yarn);
+  /// assert_eq!(fat.as_ptr(), ptr);
+  /// ```
+  pub fn from_box(yarn: YarnBox<'a, Buf>) -> Self {
+    if yarn.len() <= SSO_LEN {
+      return Self::from_small(yarn.as_bytes());
+    }
+
+    let raw = yarn.into_raw();
+    unsafe {
+      // SAFETY: raw is too long to be SMALL, and carries the same obligations
+      // as yarn did, which we take over.
+      Self::from_ptr_len_tag(raw.as_ptr(), raw.len(), raw.kind())
+    }
+  }
+
+  /// Converts this yarn into an ordinary [`YarnBox`].
+  ///
+  /// This only allocates if this yarn is inlined, but too long for a
+  /// [`YarnBox`] to inline.
+  ///
+  /// # Panics
+  ///
+  /// Panics if this yarn is too long to fit in a [`YarnBox`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let fat = FatYarn::<str>::copy("tabby");
+  /// let yarn: Yarn = fat.into_box();
+  /// assert_eq!(yarn, "tabby");
+  /// ```
+  pub fn into_box(self) -> YarnBox<'a, Buf> {
+    let Some(big) = self.big() else {
+      return unsafe {
+        // SAFETY: copy_slice never aliases, and our bytes came out of a Buf.
+        YarnBox::from_raw(RawYarn::copy_slice(self.as_bytes()))
+      };
+    };
+
+    assert!(
+      big.len <= RawYarn::MAX_LEN,
+      "yarns cannot be larger than a quarter of the address space"
+    );
+    let raw = unsafe {
+      // SAFETY: We're transferring our obligations to a raw yarn.
+      RawYarn::from_ptr_len_tag(big.ptr, big.len, big.tag & !BIG)
+    };
+    mem::forget(self);
+
+    unsafe {
+      // SAFETY: raw is the yarn we were holding, which lives for 'a.
+      YarnBox::from_raw(raw)
+    }
+  }
+
+  /// Extends the lifetime of this yarn if this yarn is dynamically known to
+  /// point to immortal memory.
+  ///
+  /// If it doesn't, the contents are copied into a fresh heap allocation.
+  pub fn immortalize(self) -> FatYarn<'static, Buf> {
+    match self.big() {
+      Some(big) if big.tag == BIG | RawYarn::ALIASED => {
+        FatYarn::copy(self.as_slice())
+      }
+      _ => {
+        let this = ManuallyDrop::new(self);
+        FatYarn {
+          repr: this.repr,
+          _ph: PhantomData,
+        }
+      }
+    }
+  }
+
+  /// Checks whether this yarn is empty.
+  pub const fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Returns the length of this yarn, in bytes.
+  pub const fn len(&self) -> usize {
+    match self.big() {
+      Some(big) => big.len,
+      None => unsafe {
+        // SAFETY: big() returned None, so the small variant is active.
+        self.repr.small.len as usize
+      },
+    }
+  }
+
+  /// Converts this yarn into a slice.
+  pub const fn as_slice(&self) -> &Buf {
+    unsafe {
+      // SAFETY: Our bytes were either copied out of a Buf, or point into one.
+      YarnRef::<Buf>::raw2buf(self.as_bytes())
+    }
+  }
+
+  /// Converts this yarn into a byte slice.
+  pub const fn as_bytes(&self) -> &[u8] {
+    match self.big() {
+      Some(big) => unsafe {
+        // SAFETY: Big yarns point to big.len valid bytes.
+        slice::from_raw_parts(big.ptr, big.len)
+      },
+      None => unsafe {
+        // SAFETY: big() returned None, so the small variant is active.
+        let small = &self.repr.small;
+        let (bytes, _) = small.data.split_at(small.len as usize);
+        bytes
+      },
+    }
+  }
+
+  /// Returns a reference to this yarn's contents as a [`YarnRef`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if this yarn is too long to fit in a [`YarnRef`].
+  pub const fn as_ref(&self) -> YarnRef<Buf> {
+    match self.big() {
+      Some(big) if big.tag == BIG | RawYarn::STATIC => unsafe {
+        // SAFETY: Static yarns never dangle, so they stay static.
+        YarnRef::from_raw(RawYarn::from_ptr_len_tag(
+          big.ptr,
+          big.len,
+          RawYarn::STATIC,
+        ))
+      },
+      _ => YarnRef::new(self.as_slice()),
+    }
+  }
+
+  /// Inlines `bytes`, which must be at most `SSO_LEN` bytes long.
+  const fn from_small(bytes: &[u8]) -> Self {
+    let mut small = Small {
+      data: [0; SSO_LEN],
+      len: bytes.len() as u8,
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+      small.data[i] = bytes[i];
+      i += 1;
+    }
+
+    Self {
+      repr: Repr { small },
+      _ph: PhantomData,
+    }
+  }
+
+  /// Creates a new, non-inlined yarn with the given pointer, length, and tag.
+  ///
+  /// # Safety
+  ///
+  /// See [`RawYarn::from_ptr_len_tag()`]; however, `len` may be any length.
+  const unsafe fn from_ptr_len_tag(
+    ptr: *const u8,
+    len: usize,
+    tag: u8,
+  ) -> Self {
+    debug_assert!(tag != RawYarn::SMALL);
+    debug_assert!(len > SSO_LEN);
+
+    Self {
+      repr: Repr {
+        big: Big {
+          ptr,
+          len,
+          pad: [0; mem::size_of::<usize>() - 1],
+          tag: BIG | tag,
+        },
+      },
+      _ph: PhantomData,
+    }
+  }
+
+  /// Takes ownership of `bytes`, which must be longer than `SSO_LEN`.
+  fn from_boxed(bytes: Box<[u8]>) -> Self {
+    let len = bytes.len();
+    let ptr = Box::into_raw(bytes) as *mut u8;
+    unsafe {
+      // SAFETY: ptr is a heap allocation of the appropriate layout for HEAP,
+      // which we own uniquely because we dismantled it from a box.
+      Self::from_ptr_len_tag(ptr, len, RawYarn::HEAP)
+    }
+  }
+
+  /// Returns the big variant of this yarn, if it is not inlined.
+  const fn big(&self) -> Option<&Big> {
+    unsafe {
+      // SAFETY: The last byte is initialized in both variants, and has the
+      // BIG bit set exactly when the big variant is active.
+      if self.repr.small.len & BIG == 0 {
+        return None;
+      }
+      Some(&self.repr.big)
+    }
+  }
+}
+
+impl<Buf> FatYarn<'static, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns a yarn pointing to the given static slice, without copying.
+  ///
+  /// Short slices are inlined, instead.
+  pub const fn from_static(buf: &'static Buf) -> Self {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.len() <= SSO_LEN {
+      return Self::from_small(bytes);
+    }
+
+    unsafe {
+      // SAFETY: buf is static.
+      Self::from_ptr_len_tag(bytes.as_ptr(), bytes.len(), RawYarn::STATIC)
+    }
+  }
+
+  /// Copies `buf` into a new immortal yarn.
+  ///
+  /// This only allocates if `buf` is longer than [`FatYarn::SSO_LEN`].
+  pub fn copy(buf: &Buf) -> Self {
+    match Self::inlined(buf) {
+      Some(y) => y,
+      None => Self::from_boxed(YarnRef::buf2raw(buf).into()),
+    }
+  }
+}
+
+impl FatYarn<'static, [u8]> {
+  /// Returns a yarn by taking ownership of the given allocation, which may be
+  /// of any length.
+  ///
+  /// As with [`YarnBox::from_vec()`], any spare capacity in `bytes` is
+  /// released first.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let bytes = vec![0xfe; 1024];
+  /// let ptr = bytes.as_ptr();
+  ///
+  /// let fat = FatYarn::from_vec(bytes);
+  /// assert_eq!(fat.as_ptr(), ptr);
+  /// ```
+  pub fn from_vec(bytes: Vec<u8>) -> Self {
+    match Self::inlined(&bytes) {
+      Some(y) => y,
+      None => Self::from_boxed(bytes.into()),
+    }
+  }
+}
+
+impl FatYarn<'static, str> {
+  /// Returns a yarn by taking ownership of the given string, which may be of
+  /// any length.
+  ///
+  /// As with [`YarnBox::from_string()`], any spare capacity in `string` is
+  /// released first.
+  pub fn from_string(string: String) -> Self {
+    match Self::inlined(&string) {
+      Some(y) => y,
+      None => Self::from_boxed(string.into_bytes().into()),
+    }
+  }
+}
+
+impl FatYarn<'_, str> {
+  /// Converts this yarn into a string slice.
+  pub fn as_str(&self) -> &str {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Deref for FatYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Target = Buf;
+  fn deref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Drop for FatYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn drop(&mut self) {
+    match self.big() {
+      Some(&big) if big.tag == BIG | RawYarn::HEAP => unsafe {
+        // SAFETY: We own this allocation uniquely, and nothing will touch it
+        // after this.
+        let bytes = slice::from_raw_parts_mut(big.ptr as *mut u8, big.len);
+        #[cfg(feature = "zeroize")]
+        ::zeroize::Zeroize::zeroize(&mut *bytes);
+        drop(Box::from_raw(bytes))
+      },
+      #[cfg(feature = "zeroize")]
+      None => unsafe {
+        // SAFETY: big() returned None, so the small variant is active.
+        ::zeroize::Zeroize::zeroize(&mut self.repr.small.data)
+      },
+      _ => {}
+    }
+  }
+}
+
+impl<Buf> Clone for FatYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn clone(&self) -> Self {
+    match self.big() {
+      Some(big) if big.tag == BIG | RawYarn::HEAP => {
+        Self::from_boxed(self.as_bytes().into())
+      }
+      _ => Self {
+        repr: self.repr,
+        _ph: PhantomData,
+      },
+    }
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Debug for FatYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    utf8::debug(self.as_bytes(), f)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Display for FatYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    utf8::display(self.as_bytes(), f)
+  }
+}
+
+impl<Slice, Buf> PartialEq<Slice> for FatYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn eq(&self, that: &Slice) -> bool {
+    self.as_slice() == that.as_ref()
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Eq for FatYarn<'_, Buf> {}
+
+impl<Slice, Buf> PartialOrd<Slice> for FatYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn partial_cmp(&self, that: &Slice) -> Option<Ordering> {
+    self.as_slice().partial_cmp(that.as_ref())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Ord for FatYarn<'_, Buf> {
+  fn cmp(&self, that: &Self) -> Ordering {
+    self.as_slice().cmp(that.as_slice())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Hash for FatYarn<'_, Buf> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_slice().hash(state)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Default for FatYarn<'_, Buf> {
+  fn default() -> Self {
+    Self::from_small(&[])
+  }
+}
+
+impl<Buf> AsRef<Buf> for FatYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn as_ref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Borrow<Buf> for FatYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn borrow(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<'a, Buf> From<YarnBox<'a, Buf>> for FatYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnBox<'a, Buf>) -> Self {
+    Self::from_box(y)
+  }
+}
+
+impl<'a, Buf> From<YarnRef<'a, Buf>> for FatYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnRef<'a, Buf>) -> Self {
+    Self::from_box(y.to_box())
+  }
+}
+
+impl<'a, Buf> From<FatYarn<'a, Buf>> for YarnBox<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: FatYarn<'a, Buf>) -> Self {
+    y.into_box()
+  }
+}
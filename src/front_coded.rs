@@ -0,0 +1,320 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use crate::raw::RawYarn;
+use crate::wire::read_varint;
+use crate::wire::write_varint;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A sorted set of yarns, stored with their shared prefixes compressed away.
+///
+/// Sorted dictionaries, like wordlists and keyword tables, tend to have long
+/// runs of entries that share a prefix with the entry before them. This type
+/// stores each entry as the length of the prefix it shares with the previous
+/// one, followed by the rest of it, all in a single buffer. Every sixteenth
+/// entry is stored whole, so that lookups can binary search those entries and
+/// then only decode a handful of others.
+///
+/// Entries are materialized as yarns when they are accessed. Entries stored
+/// whole are borrowed from the table; all others are reassembled, which only
+/// allocates if they are too long to inline.
+///
+/// Compared to [`YarnSet`], this trades lookup speed for space, and cannot be
+/// modified once it has been built.
+///
+/// ```
+/// # use byteyarn::*;
+/// let words = ["interact", "interest", "interior", "internal", "interval"];
+/// let table = words.iter().collect::<FrontCodedYarns<str>>();
+///
+/// assert_eq!(table.len(), 5);
+/// assert_eq!(table.get(2).unwrap(), "interior");
+/// assert_eq!(table.position("internal"), Some(3));
+/// assert!(!table.contains("inter"));
+///
+/// let total = words.iter().map(|w| w.len()).sum::<usize>();
+/// assert!(table.encoded_len() < total);
+/// assert!(table.iter().eq(words));
+/// ```
+///
+/// [`YarnSet`]: crate::YarnSet
+pub struct FrontCodedYarns<Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  data: Box<[u8]>,
+  /// The offset into `data` of each bucket's first entry.
+  heads: Box<[usize]>,
+  len: usize,
+  _ph: PhantomData<Box<Buf>>,
+}
+
+impl<Buf> FrontCodedYarns<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  /// The number of entries in each bucket.
+  const BUCKET: usize = 16;
+
+  /// Returns the number of yarns in this table.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Checks whether this table is empty.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Returns the number of bytes used to store this table's entries.
+  pub fn encoded_len(&self) -> usize {
+    self.data.len()
+  }
+
+  /// Returns the yarn at index `idx` in sorted order.
+  pub fn get(&self, idx: usize) -> Option<YarnBox<Buf>> {
+    if idx >= self.len {
+      return None;
+    }
+
+    let mut iter = self.bucket(idx / Self::BUCKET);
+    iter.nth(idx % Self::BUCKET)
+  }
+
+  /// Returns the index of `key` in sorted order, if it is in this table.
+  pub fn position(&self, key: impl AsRef<Buf>) -> Option<usize> {
+    let key = YarnRef::buf2raw(key.as_ref());
+    let bucket = self
+      .heads
+      .partition_point(|&head| read_entry(&self.data[head..]).1 <= key)
+      .checked_sub(1)?;
+
+    let mut iter = self.bucket(bucket);
+    while let Some(entry) = iter.next_bytes() {
+      match entry.cmp(key) {
+        std::cmp::Ordering::Less => continue,
+        std::cmp::Ordering::Equal => return Some(iter.idx - 1),
+        std::cmp::Ordering::Greater => return None,
+      }
+    }
+    None
+  }
+
+  /// Checks whether this table contains `key`.
+  pub fn contains(&self, key: impl AsRef<Buf>) -> bool {
+    self.position(key).is_some()
+  }
+
+  /// Returns an iterator over the yarns in this table, in sorted order.
+  pub fn iter(&self) -> FrontCodedIter<Buf> {
+    FrontCodedIter {
+      data: &self.data,
+      idx: 0,
+      len: self.len,
+      buf: Vec::new(),
+      _ph: PhantomData,
+    }
+  }
+
+  /// Returns an iterator starting at the first entry of `bucket`.
+  fn bucket(&self, bucket: usize) -> FrontCodedIter<Buf> {
+    FrontCodedIter {
+      data: &self.data[self.heads[bucket]..],
+      idx: bucket * Self::BUCKET,
+      len: self.len,
+      buf: Vec::new(),
+      _ph: PhantomData,
+    }
+  }
+}
+
+/// An iterator over the yarns in a [`FrontCodedYarns`].
+pub struct FrontCodedIter<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  data: &'a [u8],
+  idx: usize,
+  len: usize,
+  /// The previous entry, if it had to be reassembled.
+  buf: Vec<u8>,
+  _ph: PhantomData<Box<Buf>>,
+}
+
+impl<'a, Buf> FrontCodedIter<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  /// Decodes the next entry, returning its bytes.
+  fn next_bytes(&mut self) -> Option<&[u8]> {
+    self.next_entry().map(|entry| match entry {
+      Some(head) => head,
+      None => &self.buf[..],
+    })
+  }
+
+  /// Decodes the next entry. If it is stored whole, it is returned; otherwise,
+  /// it is reassembled in `buf`.
+  fn next_entry(&mut self) -> Option<Option<&'a [u8]>> {
+    if self.idx >= self.len {
+      return None;
+    }
+
+    let head = self.idx % FrontCodedYarns::<Buf>::BUCKET == 0;
+    self.idx += 1;
+    if head {
+      let (rest, entry) = read_entry(self.data);
+      self.data = rest;
+      self.buf.clear();
+      self.buf.extend_from_slice(entry);
+      return Some(Some(entry));
+    }
+
+    let (shared, rest) = read_varint(self.data).expect(CORRUPT);
+    let (rest, suffix) = read_entry(rest);
+    self.data = rest;
+    self.buf.truncate(shared);
+    self.buf.extend_from_slice(suffix);
+    Some(None)
+  }
+}
+
+impl<'a, Buf> Iterator for FrontCodedIter<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = YarnBox<'a, Buf>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let yarn = match self.next_entry()? {
+      // SAFETY: Entries are stored whole only if they are a valid Buf.
+      Some(head) => YarnBox::new(unsafe { YarnRef::<Buf>::raw2buf(head) }),
+      // SAFETY: Every entry is a valid Buf, including reassembled ones.
+      None => unsafe { YarnBox::from_raw(RawYarn::copy_slice(&self.buf)) },
+    };
+    Some(yarn)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.len - self.idx;
+    (len, Some(len))
+  }
+}
+
+impl<Buf> ExactSizeIterator for FrontCodedIter<'_, Buf> where
+  Buf: crate::Buf + ?Sized + 'static
+{
+}
+impl<Buf> FusedIterator for FrontCodedIter<'_, Buf> where
+  Buf: crate::Buf + ?Sized + 'static
+{
+}
+
+impl<'a, Buf> IntoIterator for &'a FrontCodedYarns<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = YarnBox<'a, Buf>;
+  type IntoIter = FrontCodedIter<'a, Buf>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<K, Buf> FromIterator<K> for FrontCodedYarns<Buf>
+where
+  K: AsRef<Buf>,
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  /// Builds a table out of the given yarns, which need not be sorted. Any
+  /// duplicates are dropped.
+  fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+    let keys = iter.into_iter().collect::<Vec<_>>();
+    let mut keys = keys
+      .iter()
+      .map(|k| YarnRef::buf2raw(k.as_ref()))
+      .collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+
+    let mut data = Vec::new();
+    let mut heads = Vec::new();
+    let mut prev: &[u8] = &[];
+    for (i, &key) in keys.iter().enumerate() {
+      if i % Self::BUCKET == 0 {
+        heads.push(data.len());
+        write_entry(&mut data, key);
+      } else {
+        let shared = prev.iter().zip(key).take_while(|(a, b)| a == b).count();
+        write_varint(&mut data, shared);
+        write_entry(&mut data, &key[shared..]);
+      }
+      prev = key;
+    }
+
+    Self {
+      data: data.into(),
+      heads: heads.into(),
+      len: keys.len(),
+      _ph: PhantomData,
+    }
+  }
+}
+
+impl<Buf> Default for FrontCodedYarns<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn default() -> Self {
+    Self {
+      data: Box::default(),
+      heads: Box::default(),
+      len: 0,
+      _ph: PhantomData,
+    }
+  }
+}
+
+impl<Buf> Clone for FrontCodedYarns<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn clone(&self) -> Self {
+    Self {
+      data: self.data.clone(),
+      heads: self.heads.clone(),
+      len: self.len,
+      _ph: PhantomData,
+    }
+  }
+}
+
+impl<Buf> fmt::Debug for FrontCodedYarns<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+/// The message for a varint that cannot be read back, which would mean that
+/// the table's buffer is corrupt.
+const CORRUPT: &str = "corrupt varint in FrontCodedYarns";
+
+/// Writes `bytes`, prefixed with its length.
+fn write_entry(out: &mut Vec<u8>, bytes: &[u8]) {
+  write_varint(out, bytes.len());
+  out.extend_from_slice(bytes);
+}
+
+/// Reads bytes written by [`write_entry()`], returning the rest of `data` and
+/// the bytes.
+fn read_entry(data: &[u8]) -> (&[u8], &[u8]) {
+  let (len, rest) = read_varint(data).expect(CORRUPT);
+  let (entry, rest) = rest.split_at(len);
+  (rest, entry)
+}
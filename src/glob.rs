@@ -0,0 +1,293 @@
+//! Glob and wildcard matching.
+
+use std::str;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Checks whether this yarn matches the glob pattern `pat`.
+  ///
+  /// The pattern syntax is the one used for filtering paths:
+  ///
+  /// * `?` matches any one character other than `/`.
+  /// * `*` matches any sequence of characters not containing a `/`.
+  /// * `**` matches any sequence of characters, including `/`.
+  /// * `[abc]`, `[a-z]` match one character from a set; `[!abc]` or `[^abc]`
+  ///   match one character not in it. Sets never match `/`.
+  /// * `\` escapes the next character.
+  ///
+  /// Bytes that are not valid UTF-8 are treated as single characters that only
+  /// match themselves.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let path = YarnRef::new("src/glob.rs");
+  /// assert!(path.matches_glob("src/*.rs"));
+  /// assert!(path.matches_glob("**.rs"));
+  /// assert!(path.matches_glob("src/[a-h]???.rs"));
+  /// assert!(!path.matches_glob("*.rs"));
+  /// assert!(!path.matches_glob("src/[!g]*"));
+  /// ```
+  pub fn matches_glob(&self, pat: &Buf) -> bool {
+    let opts = Opts {
+      glob: true,
+      case_insensitive: false,
+    };
+    wildmatch(Self::buf2raw(pat), self.as_bytes(), opts) == Match::Yes
+  }
+
+  /// Checks whether this yarn matches the wildcard pattern `pat`.
+  ///
+  /// Unlike [`YarnRef::matches_glob()`], the only special characters are `?`,
+  /// which matches any one character, and `*`, which matches any sequence of
+  /// characters; neither treats `/` specially.
+  ///
+  /// If `case_insensitive` is set, characters are compared after lowercasing.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let name = YarnRef::<[u8]>::new(b"Cargo.TOML");
+  /// assert!(name.matches_wildcard(b"*.toml", true));
+  /// assert!(!name.matches_wildcard(b"*.toml", false));
+  /// assert!(name.matches_wildcard(b"C?rgo*", false));
+  /// ```
+  pub fn matches_wildcard(&self, pat: &Buf, case_insensitive: bool) -> bool {
+    let opts = Opts {
+      glob: false,
+      case_insensitive,
+    };
+    wildmatch(Self::buf2raw(pat), self.as_bytes(), opts) == Match::Yes
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Checks whether this yarn matches the glob pattern `pat`.
+  ///
+  /// See [`YarnRef::matches_glob()`] for the pattern syntax.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let path = yarn!("tests/{}/main.rs", "ui");
+  /// assert!(path.matches_glob("tests/**/*.rs"));
+  /// assert!(!path.matches_glob("tests/*.rs"));
+  /// ```
+  pub fn matches_glob(&self, pat: &Buf) -> bool {
+    self.as_ref().matches_glob(pat)
+  }
+
+  /// Checks whether this yarn matches the wildcard pattern `pat`.
+  ///
+  /// See [`YarnRef::matches_wildcard()`] for the pattern syntax.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = Yarn::from_static("README.md");
+  /// assert!(yarn.matches_wildcard("readme*", true));
+  /// assert!(!yarn.matches_wildcard("readme*", false));
+  /// ```
+  pub fn matches_wildcard(&self, pat: &Buf, case_insensitive: bool) -> bool {
+    self.as_ref().matches_wildcard(pat, case_insensitive)
+  }
+}
+
+#[derive(Copy, Clone)]
+struct Opts {
+  glob: bool,
+  case_insensitive: bool,
+}
+
+/// The result of a (partial) match.
+///
+/// The two abort results prune the search: once the text runs out, no later
+/// position for a `*` can succeed, and once a `*` runs into a `/`, only an
+/// enclosing `**` can make progress.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Match {
+  Yes,
+  No,
+  AbortAll,
+  AbortToStarStar,
+}
+
+/// A single unit of matching: either a character, or a byte that is not part
+/// of a valid UTF-8 sequence.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Unit {
+  Char(char),
+  Byte(u8),
+}
+
+impl Unit {
+  /// Splits the first unit off of `buf`.
+  fn split(buf: &[u8]) -> Option<(Unit, &[u8])> {
+    let &lead = buf.first()?;
+    let width = match lead {
+      0x00..=0x7f => 1,
+      0xc0..=0xdf => 2,
+      0xe0..=0xef => 3,
+      0xf0..=0xf7 => 4,
+      _ => return Some((Unit::Byte(lead), &buf[1..])),
+    };
+
+    if let Some(Ok(s)) = buf.get(..width).map(str::from_utf8) {
+      let c = s.chars().next().unwrap();
+      return Some((Unit::Char(c), &buf[width..]));
+    }
+
+    Some((Unit::Byte(lead), &buf[1..]))
+  }
+
+  fn eq(self, that: Unit, opts: Opts) -> bool {
+    match (self, that) {
+      (Unit::Char(a), Unit::Char(b)) if opts.case_insensitive => {
+        a == b || a.to_lowercase().eq(b.to_lowercase())
+      }
+      _ => self == that,
+    }
+  }
+}
+
+/// A matcher modeled after git's `wildmatch()`.
+fn wildmatch(mut pat: &[u8], mut text: &[u8], opts: Opts) -> Match {
+  while let Some((p, rest)) = Unit::split(pat) {
+    pat = rest;
+
+    if text.is_empty() && p != Unit::Char('*') {
+      return Match::AbortAll;
+    }
+
+    match p {
+      Unit::Char('?') => {
+        let (t, rest) = Unit::split(text).unwrap();
+        if opts.glob && t == Unit::Char('/') {
+          return Match::No;
+        }
+        text = rest;
+      }
+      Unit::Char('*') => {
+        let mut star_star = !opts.glob;
+        while let Some(rest) = pat.strip_prefix(b"*") {
+          star_star = true;
+          pat = rest;
+        }
+
+        if pat.is_empty() {
+          if !star_star && text.contains(&b'/') {
+            return Match::AbortToStarStar;
+          }
+          return Match::Yes;
+        }
+
+        loop {
+          match wildmatch(pat, text, opts) {
+            Match::No => {}
+            Match::AbortToStarStar if star_star => {}
+            m => return m,
+          }
+
+          let Some((t, rest)) = Unit::split(text) else {
+            return Match::AbortAll;
+          };
+          if !star_star && t == Unit::Char('/') {
+            return Match::AbortToStarStar;
+          }
+          text = rest;
+        }
+      }
+      Unit::Char('[') if opts.glob => {
+        let (t, rest) = Unit::split(text).unwrap();
+        if t == Unit::Char('/') {
+          return Match::No;
+        }
+        text = rest;
+
+        let Some((matched, rest)) = match_class(pat, t) else {
+          // An unterminated set is matched as a literal `[`.
+          if t != Unit::Char('[') {
+            return Match::No;
+          }
+          continue;
+        };
+        if !matched {
+          return Match::No;
+        }
+        pat = rest;
+      }
+      _ => {
+        let p = match p {
+          Unit::Char('\\') if opts.glob => match Unit::split(pat) {
+            Some((p, rest)) => {
+              pat = rest;
+              p
+            }
+            None => p,
+          },
+          _ => p,
+        };
+
+        let (t, rest) = Unit::split(text).unwrap();
+        if !p.eq(t, opts) {
+          return Match::No;
+        }
+        text = rest;
+      }
+    }
+  }
+
+  match text.is_empty() {
+    true => Match::Yes,
+    false => Match::No,
+  }
+}
+
+/// Matches `t` against the set at the start of `pat`, which has already had
+/// its opening `[` removed.
+///
+/// Returns whether `t` is in the set, and the rest of the pattern after the
+/// closing `]`, or `None` if the set is unterminated.
+fn match_class(mut pat: &[u8], t: Unit) -> Option<(bool, &[u8])> {
+  let mut negated = false;
+  if let Some(rest) = pat.strip_prefix(b"!").or(pat.strip_prefix(b"^")) {
+    negated = true;
+    pat = rest;
+  }
+
+  let mut matched = false;
+  let mut first = true;
+  loop {
+    let (mut lo, rest) = Unit::split(pat)?;
+    pat = rest;
+
+    // A `]` right after the opening bracket is part of the set.
+    if lo == Unit::Char(']') && !first {
+      return Some((matched != negated, pat));
+    }
+    first = false;
+
+    if lo == Unit::Char('\\') {
+      (lo, pat) = Unit::split(pat)?;
+    }
+
+    let mut hi = lo;
+    if let Some(rest) = pat.strip_prefix(b"-") {
+      match Unit::split(rest)? {
+        (Unit::Char(']'), _) => {}
+        (Unit::Char('\\'), rest) => (hi, pat) = Unit::split(rest)?,
+        (unit, rest) => (hi, pat) = (unit, rest),
+      }
+    }
+
+    matched |= match (lo, t, hi) {
+      (Unit::Char(lo), Unit::Char(t), Unit::Char(hi)) => (lo..=hi).contains(&t),
+      (Unit::Byte(lo), Unit::Byte(t), Unit::Byte(hi)) => (lo..=hi).contains(&t),
+      _ => lo == t,
+    };
+  }
+}
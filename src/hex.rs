@@ -0,0 +1,114 @@
+//! Hex dumps of byte buffers.
+
+use std::fmt;
+use std::fmt::Write;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A formatting adaptor that renders a byte buffer as a hex dump, in the style
+/// of `hexdump -C`.
+///
+/// Each row shows the offset of its first byte, sixteen bytes in hex, and
+/// those same bytes as ASCII, with unprintable bytes shown as `.`. Rows are
+/// rendered as they are formatted, without building an intermediate string.
+///
+/// `Debug` renders the same as `Display`, so that comparing two dumps with
+/// `assert_eq!()` prints both dumps row by row on failure.
+///
+/// See [`Yarn::hex_dump()`].
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct HexDump<'a> {
+  buf: &'a [u8],
+}
+
+impl<'a> HexDump<'a> {
+  /// The number of bytes in each row.
+  const ROW: usize = 16;
+
+  pub(crate) fn new(buf: &'a [u8]) -> Self {
+    Self { buf }
+  }
+}
+
+impl fmt::Display for HexDump<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, row) in self.buf.chunks(Self::ROW).enumerate() {
+      if i > 0 {
+        f.write_str("\n")?;
+      }
+      write!(f, "{:08x} ", i * Self::ROW)?;
+
+      for j in 0..Self::ROW {
+        if j % 8 == 0 {
+          f.write_str(" ")?;
+        }
+        match row.get(j) {
+          Some(b) => write!(f, "{b:02x} ")?,
+          None => f.write_str("   ")?,
+        }
+      }
+
+      f.write_str(" |")?;
+      for &b in row {
+        let c = match b.is_ascii_graphic() || b == b' ' {
+          true => b as char,
+          false => '.',
+        };
+        write!(f, "{c}")?;
+      }
+      f.write_str("|")?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Debug for HexDump<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(self, f)
+  }
+}
+
+// Hex and binary formatting of byte yarns, as one contiguous big-endian
+// number: each byte becomes exactly two hex digits or eight bits, including
+// leading zeros. Width, fill, and the `#` and `0` flags behave as they do for
+// integers.
+
+macro_rules! impl_radix {
+  ($($trait:ident => $prefix:literal, $digits:literal;)*) => {$(
+    impl fmt::$trait for YarnBox<'_, [u8]> {
+      fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::$trait::fmt(&self.as_ref(), f)
+      }
+    }
+
+    impl fmt::$trait for YarnRef<'_, [u8]> {
+      fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.width().is_none() {
+          if f.alternate() {
+            f.write_str($prefix)?;
+          }
+          for b in self.iter() {
+            write!(f, $digits, b)?;
+          }
+          return Ok(());
+        }
+
+        let mut digits = String::new();
+        for b in self.iter() {
+          write!(digits, $digits, b)?;
+        }
+        f.pad_integral(true, $prefix, &digits)
+      }
+    }
+  )*};
+}
+
+impl_radix! {
+  LowerHex => "0x", "{:02x}";
+  UpperHex => "0x", "{:02X}";
+  Binary => "0b", "{:08b}";
+}
@@ -0,0 +1,177 @@
+//! Integration with the `http` crate.
+
+use bytes::Bytes;
+use http::header::AsHeaderName;
+use http::header::InvalidHeaderName;
+use http::header::InvalidHeaderValue;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> TryFrom<YarnBox<'_, Buf>> for HeaderValue
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = InvalidHeaderValue;
+
+  /// Converts a yarn into a `HeaderValue`, validating it.
+  ///
+  /// Static and heap-allocated yarns are handed over without copying; yarns
+  /// that borrow their contents are copied.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use http::HeaderValue;
+  ///
+  /// let value = HeaderValue::try_from(yarn!("max-age={}", 3600)).unwrap();
+  /// assert_eq!(value, "max-age=3600");
+  ///
+  /// assert!(HeaderValue::try_from(Yarn::from_static("a\nb")).is_err());
+  /// ```
+  fn try_from(y: YarnBox<'_, Buf>) -> Result<Self, InvalidHeaderValue> {
+    HeaderValue::from_maybe_shared(Bytes::from(y.into_bytes().immortalize()))
+  }
+}
+
+impl<Buf> TryFrom<YarnRef<'_, Buf>> for HeaderValue
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = InvalidHeaderValue;
+
+  /// Converts a yarn into a `HeaderValue`, validating it.
+  ///
+  /// This copies the yarn unless it is static.
+  fn try_from(y: YarnRef<'_, Buf>) -> Result<Self, InvalidHeaderValue> {
+    y.to_box().try_into()
+  }
+}
+
+impl From<HeaderValue> for YarnBox<'_, [u8]> {
+  /// Converts a `HeaderValue` into a yarn.
+  ///
+  /// `HeaderValue`s share their buffers, so long values must be copied; short
+  /// ones are inlined. To avoid copying, borrow the value as a [`YarnRef`]
+  /// instead.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use http::HeaderValue;
+  ///
+  /// let yarn = ByteYarn::from(HeaderValue::from_static("gzip"));
+  /// assert_eq!(yarn, b"gzip");
+  /// ```
+  fn from(v: HeaderValue) -> Self {
+    YarnBox::new(v.as_bytes()).immortalize()
+  }
+}
+
+impl<'a> From<&'a HeaderValue> for YarnRef<'a, [u8]> {
+  /// Borrows a `HeaderValue` as a yarn, without copying.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use http::HeaderValue;
+  ///
+  /// let value = HeaderValue::from_static("text/html; charset=utf-8");
+  /// let yarn = YarnRef::from(&value);
+  /// assert_eq!(yarn, b"text/html; charset=utf-8");
+  /// ```
+  fn from(v: &'a HeaderValue) -> Self {
+    YarnRef::new(v.as_bytes())
+  }
+}
+
+impl<Buf> TryFrom<YarnBox<'_, Buf>> for HeaderName
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = InvalidHeaderName;
+
+  /// Converts a yarn into a `HeaderName`, validating it.
+  ///
+  /// Standard header names do not allocate; other names are copied and
+  /// lowercased.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use http::header;
+  /// use http::HeaderName;
+  ///
+  /// let name = HeaderName::try_from(yarn!("Content-Type")).unwrap();
+  /// assert_eq!(name, header::CONTENT_TYPE);
+  /// ```
+  fn try_from(y: YarnBox<'_, Buf>) -> Result<Self, InvalidHeaderName> {
+    HeaderName::from_bytes(y.as_bytes())
+  }
+}
+
+impl<Buf> TryFrom<YarnRef<'_, Buf>> for HeaderName
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = InvalidHeaderName;
+
+  /// Converts a yarn into a `HeaderName`, validating it.
+  fn try_from(y: YarnRef<'_, Buf>) -> Result<Self, InvalidHeaderName> {
+    HeaderName::from_bytes(y.as_bytes())
+  }
+}
+
+impl<Buf> From<HeaderName> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts a `HeaderName` into a yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use http::header;
+  ///
+  /// let yarn = Yarn::from(header::ACCEPT_ENCODING);
+  /// assert_eq!(yarn, "accept-encoding");
+  /// ```
+  fn from(n: HeaderName) -> Self {
+    unsafe {
+      // SAFETY: both [u8] and str can be safely constructed from a str, and
+      // copy_slice() returns a uniquely-owned raw yarn.
+      YarnBox::from_raw(RawYarn::copy_slice(n.as_str().as_bytes()))
+    }
+  }
+}
+
+impl<'a> From<&'a HeaderName> for YarnRef<'a, str> {
+  /// Borrows a `HeaderName` as a yarn, without copying.
+  fn from(n: &'a HeaderName) -> Self {
+    YarnRef::new(n.as_str())
+  }
+}
+
+impl<'a> YarnRef<'a, [u8]> {
+  /// Returns the values of the header `key` in `map` as yarns, without
+  /// copying.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use http::header;
+  /// use http::HeaderMap;
+  /// use http::HeaderValue;
+  ///
+  /// let mut map = HeaderMap::new();
+  /// map.append(header::ACCEPT, HeaderValue::from_static("text/html"));
+  /// map.append(header::ACCEPT, HeaderValue::from_static("*/*"));
+  ///
+  /// let accept = YarnRef::header_values(&map, header::ACCEPT).collect::<Vec<_>>();
+  /// assert_eq!(accept, [&b"text/html"[..], b"*/*"]);
+  /// ```
+  pub fn header_values(
+    map: &'a HeaderMap,
+    key: impl AsHeaderName,
+  ) -> impl Iterator<Item = YarnRef<'a, [u8]>> {
+    map.get_all(key).into_iter().map(YarnRef::from)
+  }
+}
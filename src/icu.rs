@@ -0,0 +1,170 @@
+//! Integration with the `icu_collator` crate.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use icu_collator::Collator;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Compares this yarn to `that` according to the rules of `collator`.
+  ///
+  /// Byte yarns are compared as if they were UTF-8, with invalid sequences
+  /// treated as U+FFFD.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use std::cmp::Ordering;
+  /// use icu_collator::Collator;
+  /// use icu_collator::CollatorOptions;
+  /// use icu_locid::locale;
+  ///
+  /// let options = CollatorOptions::new();
+  /// let swedish = Collator::try_new(&locale!("sv").into(), options).unwrap();
+  /// let english = Collator::try_new(&locale!("en").into(), options).unwrap();
+  ///
+  /// let beer = YarnRef::<str>::new("öl");
+  /// assert_eq!(beer.collate_cmp("zebra", &swedish), Ordering::Greater);
+  /// assert_eq!(beer.collate_cmp("zebra", &english), Ordering::Less);
+  /// ```
+  pub fn collate_cmp(
+    &self,
+    that: impl AsRef<Buf>,
+    collator: &Collator,
+  ) -> Ordering {
+    collator.compare_utf8(self.as_bytes(), YarnRef::buf2raw(that.as_ref()))
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Compares this yarn to `that` according to the rules of `collator`.
+  ///
+  /// See [`YarnRef::collate_cmp()`].
+  pub fn collate_cmp(
+    &self,
+    that: impl AsRef<Buf>,
+    collator: &Collator,
+  ) -> Ordering {
+    self.as_ref().collate_cmp(that, collator)
+  }
+}
+
+/// A yarn paired with the [`Collator`] it is ordered by.
+///
+/// Wrapping yarns in a `Collated` allows sorting them, or using them as
+/// `BTreeMap` keys, in a locale-aware order.
+///
+/// Yarns that collate equal, such as yarns that differ only in case under a
+/// collator that ignores case, are ordered by their original bytes, so that
+/// the ordering agrees with `Eq`, which compares the original yarns.
+/// Comparisons use the collator of the left-hand side, so all yarns being
+/// compared should share the same collator.
+///
+/// ```
+/// # use byteyarn::*;
+/// use icu_collator::Collator;
+/// use icu_collator::CollatorOptions;
+/// use icu_locid::locale;
+///
+/// let options = CollatorOptions::new();
+/// let swedish = Collator::try_new(&locale!("sv").into(), options).unwrap();
+///
+/// let mut words = ["öl", "zebra", "Åland", "apple"]
+///   .map(|w| Collated::new(Yarn::from(w), &swedish));
+/// words.sort();
+///
+/// let sorted = words.iter().map(Collated::yarn).collect::<Vec<_>>();
+/// assert_eq!(sorted, ["apple", "zebra", "Åland", "öl"]);
+/// ```
+pub struct Collated<'c, 'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  yarn: YarnBox<'a, Buf>,
+  collator: &'c Collator,
+}
+
+impl<'c, 'a, Buf> Collated<'c, 'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Wraps `yarn` so that it is ordered by `collator`.
+  pub fn new(yarn: YarnBox<'a, Buf>, collator: &'c Collator) -> Self {
+    Self { yarn, collator }
+  }
+
+  /// Returns the wrapped yarn.
+  pub fn yarn(&self) -> YarnRef<Buf> {
+    self.yarn.as_ref()
+  }
+
+  /// Returns the collator this yarn is ordered by.
+  pub fn collator(&self) -> &'c Collator {
+    self.collator
+  }
+
+  /// Unwraps the original yarn.
+  pub fn into_inner(self) -> YarnBox<'a, Buf> {
+    self.yarn
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Clone for Collated<'_, '_, Buf> {
+  fn clone(&self) -> Self {
+    Self {
+      yarn: self.yarn.clone(),
+      collator: self.collator,
+    }
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> PartialEq for Collated<'_, '_, Buf> {
+  fn eq(&self, that: &Self) -> bool {
+    self.yarn == that.yarn
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Eq for Collated<'_, '_, Buf> {}
+
+impl<Buf: crate::Buf + ?Sized> PartialOrd for Collated<'_, '_, Buf> {
+  fn partial_cmp(&self, that: &Self) -> Option<Ordering> {
+    Some(self.cmp(that))
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Ord for Collated<'_, '_, Buf> {
+  fn cmp(&self, that: &Self) -> Ordering {
+    self
+      .yarn
+      .collate_cmp(&that.yarn, self.collator)
+      .then_with(|| self.yarn.as_bytes().cmp(that.yarn.as_bytes()))
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Hash for Collated<'_, '_, Buf> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.yarn.hash(state)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Debug for Collated<'_, '_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(&self.yarn, f)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Display for Collated<'_, '_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.yarn, f)
+  }
+}
@@ -0,0 +1,265 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A fixed-capacity yarn that stores up to `N` bytes inline, and never
+/// allocates.
+///
+/// Unlike [`YarnBox`], which only inlines strings that fit in two words, an
+/// `InlineYarn` always keeps its contents inside itself; this makes it suitable
+/// for environments without an allocator, and for hot structs that want a
+/// guaranteed-inline small string. `N` may be at most 255.
+///
+/// ```
+/// # use byteyarn::*;
+/// let uuid = InlineYarn::<36, str>::new("67e55044-10b1-426f-9247-bb680e5fe0c8");
+/// let uuid = uuid.unwrap();
+/// assert_eq!(uuid.len(), 36);
+///
+/// // Too big!
+/// assert!(InlineYarn::<4, str>::new("tabby").is_none());
+///
+/// // Converting to other yarns is cheap.
+/// let yarn: Yarn = uuid.to_box();
+/// assert_eq!(yarn, uuid);
+/// ```
+pub struct InlineYarn<const N: usize, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  data: [u8; N],
+  len: u8,
+  _ph: PhantomData<Buf>,
+}
+
+impl<const N: usize, Buf> InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  const CAPACITY_CHECK: () = assert!(
+    N <= u8::MAX as usize,
+    "InlineYarn capacity must be at most 255"
+  );
+
+  /// Returns an empty yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let empty = InlineYarn::<8, str>::empty();
+  /// assert_eq!(empty, "");
+  /// ```
+  pub const fn empty() -> Self {
+    #[allow(clippy::let_unit_value)]
+    let () = Self::CAPACITY_CHECK;
+    Self {
+      data: [0; N],
+      len: 0,
+      _ph: PhantomData,
+    }
+  }
+
+  /// Returns a new yarn containing a copy of the given slice, or `None` if it
+  /// is longer than `N` bytes.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let smol = InlineYarn::<8, [u8]>::new(b"smol").unwrap();
+  /// assert_eq!(smol, b"smol");
+  ///
+  /// assert!(InlineYarn::<8, [u8]>::new(b"not so smol").is_none());
+  /// ```
+  pub const fn new(buf: &Buf) -> Option<Self> {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.len() > N {
+      return None;
+    }
+
+    let mut yarn = Self::empty();
+    let mut i = 0;
+    while i < bytes.len() {
+      yarn.data[i] = bytes[i];
+      i += 1;
+    }
+    yarn.len = bytes.len() as u8;
+    Some(yarn)
+  }
+
+  /// Returns the maximum number of bytes this yarn can hold.
+  pub const fn capacity(&self) -> usize {
+    N
+  }
+
+  /// Checks whether this yarn is empty.
+  pub const fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Returns the length of this yarn, in bytes.
+  pub const fn len(&self) -> usize {
+    self.len as usize
+  }
+
+  /// Converts this yarn into a slice.
+  pub const fn as_slice(&self) -> &Buf {
+    unsafe {
+      // SAFETY: The first self.len bytes were copied out of a Buf.
+      YarnRef::<Buf>::raw2buf(self.as_bytes())
+    }
+  }
+
+  /// Converts this yarn into a byte slice.
+  pub const fn as_bytes(&self) -> &[u8] {
+    let (bytes, _) = self.data.split_at(self.len as usize);
+    bytes
+  }
+
+  /// Returns a reference to this yarn's contents as a [`YarnRef`].
+  ///
+  /// This will inline the contents into the returned yarn if they are short
+  /// enough, and alias them otherwise.
+  pub const fn as_ref(&self) -> YarnRef<Buf> {
+    YarnRef::new(self.as_slice())
+  }
+
+  /// Copies this yarn into an immortal [`YarnBox`].
+  ///
+  /// This does not allocate if the contents are short enough for a
+  /// [`YarnBox`] to inline.
+  pub fn to_box(&self) -> YarnBox<'static, Buf> {
+    YarnBox::new(self.as_slice()).immortalize()
+  }
+}
+
+impl<const N: usize, Buf: crate::Buf + ?Sized> Copy for InlineYarn<N, Buf> {}
+
+impl<const N: usize, Buf: crate::Buf + ?Sized> Clone for InlineYarn<N, Buf> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<const N: usize> InlineYarn<N, str> {
+  /// Converts this yarn into a string slice.
+  pub fn as_str(&self) -> &str {
+    self.as_slice()
+  }
+}
+
+impl<const N: usize, Buf> Deref for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Target = Buf;
+  fn deref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<const N: usize, Buf> AsRef<Buf> for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn as_ref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<const N: usize, Buf> Borrow<Buf> for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn borrow(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<'a, const N: usize, Buf> TryFrom<YarnRef<'a, Buf>> for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = YarnRef<'a, Buf>;
+
+  /// Copies a yarn into an `InlineYarn`, or returns it unchanged if it is
+  /// longer than `N` bytes.
+  fn try_from(y: YarnRef<'a, Buf>) -> Result<Self, YarnRef<'a, Buf>> {
+    Self::new(y.as_slice()).ok_or(y)
+  }
+}
+
+impl<const N: usize, Buf> From<InlineYarn<N, Buf>> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: InlineYarn<N, Buf>) -> Self {
+    unsafe {
+      // SAFETY: copy_slice never aliases, and y's bytes came out of a Buf.
+      YarnBox::from_raw(RawYarn::copy_slice(y.as_bytes()))
+    }
+  }
+}
+
+impl<const N: usize, Buf> fmt::Debug for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<const N: usize, Buf> fmt::Display for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<const N: usize, Slice, Buf> PartialEq<Slice> for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn eq(&self, that: &Slice) -> bool {
+    self.as_slice() == that.as_ref()
+  }
+}
+
+impl<const N: usize, Buf: crate::Buf + ?Sized> Eq for InlineYarn<N, Buf> {}
+
+impl<const N: usize, Slice, Buf> PartialOrd<Slice> for InlineYarn<N, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn partial_cmp(&self, that: &Slice) -> Option<Ordering> {
+    self.as_slice().partial_cmp(that.as_ref())
+  }
+}
+
+impl<const N: usize, Buf: crate::Buf + ?Sized> Ord for InlineYarn<N, Buf> {
+  fn cmp(&self, that: &Self) -> Ordering {
+    self.as_slice().cmp(that.as_slice())
+  }
+}
+
+impl<const N: usize, Buf: crate::Buf + ?Sized> Hash for InlineYarn<N, Buf> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_slice().hash(state)
+  }
+}
+
+impl<const N: usize, Buf: crate::Buf + ?Sized> Default for InlineYarn<N, Buf> {
+  fn default() -> Self {
+    Self::empty()
+  }
+}
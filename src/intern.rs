@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::str;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// An interned yarn, as returned by [`Interner::intern()`].
+///
+/// A symbol is a small integer that can be compared and hashed much more
+/// cheaply than the yarn it stands for. It is only meaningful to the interner
+/// that produced it.
+///
+/// Symbols are assigned deterministically: the first distinct yarn interned
+/// gets index 0, the second gets index 1, and so on. Thus, interning the same
+/// yarns in the same order always produces the same symbols, even across runs
+/// of a program, so symbols may be stored in caches and build artifacts along
+/// with the interner's table.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(pub(crate) u32);
+
+impl Symbol {
+  /// Returns the index of this symbol in its interner's table.
+  pub const fn index(self) -> usize {
+    self.0 as usize
+  }
+}
+
+/// A table of interned yarns.
+///
+/// Interning a yarn returns a [`Symbol`]; interning an equal yarn again
+/// returns the same symbol. Yarns are stored as they are given, so static and
+/// borrowed yarns are interned without copying, and owned yarns are not copied
+/// again.
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut interner = Interner::<str>::new();
+/// let a = interner.intern("foo");
+/// let b = interner.intern(String::from("bar"));
+/// assert_eq!(interner.intern(yarn!("fo{}", 'o')), a);
+///
+/// assert_ne!(a, b);
+/// assert_eq!(interner.resolve(b), "bar");
+/// assert_eq!(interner.get("foo"), Some(a));
+/// assert_eq!(interner.get("baz"), None);
+///
+/// let long = String::from("a yarn too long to be inlined");
+/// let c = interner.intern(long.clone());
+/// assert_eq!(interner.get(&long), Some(c));
+/// assert_eq!(interner.resolve(c), long);
+/// ```
+///
+/// # Persistence
+///
+/// An interner's table can be written out with [`Interner::save()`] and read
+/// back with [`Interner::load()`], which does not copy: the loaded yarns
+/// borrow from the buffer they were loaded from. The buffer can be a
+/// memory-mapped file, so that a large table is paged in lazily.
+///
+/// The format is a header, consisting of the magic bytes `yarntab1` and the
+/// number of yarns, followed by the offset at which each yarn ends, and then
+/// the concatenated yarns. All integers are little-endian `u32`s.
+pub struct Interner<'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  yarns: Vec<YarnBox<'a, Buf>>,
+  // The keys of this map alias the yarns in `yarns` rather than copying them
+  // (see `intern()`), so this type must not derive Clone.
+  symbols: HashMap<YarnRef<'a, Buf>, Symbol>,
+}
+
+const MAGIC: &[u8; 8] = b"yarntab1";
+
+impl<'a, Buf> Interner<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns a new, empty interner.
+  pub fn new() -> Self {
+    Self {
+      yarns: Vec::new(),
+      symbols: HashMap::new(),
+    }
+  }
+
+  /// Returns the number of distinct yarns in this interner.
+  pub fn len(&self) -> usize {
+    self.yarns.len()
+  }
+
+  /// Checks whether this interner is empty.
+  pub fn is_empty(&self) -> bool {
+    self.yarns.is_empty()
+  }
+
+  /// Interns `yarn`, returning its symbol.
+  ///
+  /// # Panics
+  ///
+  /// Panics if this interner already contains `u32::MAX` yarns.
+  pub fn intern(&mut self, yarn: impl Into<YarnBox<'a, Buf>>) -> Symbol {
+    let yarn = yarn.into();
+    if let Some(&sym) = self.symbols.get(yarn.as_slice()) {
+      return sym;
+    }
+
+    let sym = Symbol(
+      self
+        .yarns
+        .len()
+        .try_into()
+        .expect("interned more than u32::MAX yarns"),
+    );
+    let key = unsafe {
+      // SAFETY: aliased() copies inlined yarns, so the key can only point to
+      // static memory, memory borrowed for 'a, or yarn's heap allocation.
+      // Moving a yarn does not move its heap allocation, and yarns are never
+      // removed from this interner, so the key lives as long as the yarn.
+      YarnRef::from_raw(yarn.aliased().into_raw())
+    };
+    self.yarns.push(yarn);
+    self.symbols.insert(key, sym);
+    sym
+  }
+
+  /// Looks up the symbol for `key`, if it has been interned.
+  pub fn get(&self, key: impl AsRef<Buf>) -> Option<Symbol> {
+    self.symbols.get(key.as_ref()).copied()
+  }
+
+  /// Returns the yarn that `sym` stands for.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `sym` was not produced by this interner.
+  pub fn resolve(&self, sym: Symbol) -> YarnRef<Buf> {
+    self.yarns[sym.index()].as_ref()
+  }
+
+  /// Returns an iterator over the symbols in this interner and the yarns they
+  /// stand for.
+  pub fn iter(&self) -> impl Iterator<Item = (Symbol, YarnRef<Buf>)> + '_ {
+    self
+      .yarns
+      .iter()
+      .enumerate()
+      .map(|(i, y)| (Symbol(i as u32), y.as_ref()))
+  }
+
+  /// Writes this interner's table to `out`.
+  ///
+  /// Loading the result with [`Interner::load()`] produces an interner that
+  /// assigns the same symbols to the same yarns.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut interner = Interner::<str>::new();
+  /// let foo = interner.intern("foo");
+  /// let bar = interner.intern("bar");
+  ///
+  /// let mut table = Vec::new();
+  /// interner.save(&mut table).unwrap();
+  ///
+  /// let mut loaded = Interner::<str>::load(&table).unwrap();
+  /// assert_eq!(loaded.resolve(foo), "foo");
+  /// assert_eq!(loaded.intern("bar"), bar);
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if writing to `out` fails, or if the table is larger
+  /// than 4 GiB.
+  pub fn save(&self, mut out: impl io::Write) -> io::Result<()> {
+    let too_big = || {
+      io::Error::new(io::ErrorKind::InvalidInput, "interner table too large")
+    };
+
+    out.write_all(MAGIC)?;
+    let count = u32::try_from(self.len()).map_err(|_| too_big())?;
+    out.write_all(&count.to_le_bytes())?;
+
+    let mut end = 0u32;
+    for yarn in &self.yarns {
+      let len = u32::try_from(yarn.len()).map_err(|_| too_big())?;
+      end = end.checked_add(len).ok_or_else(too_big)?;
+      out.write_all(&end.to_le_bytes())?;
+    }
+    for yarn in &self.yarns {
+      out.write_all(yarn.as_bytes())?;
+    }
+    Ok(())
+  }
+
+  /// Loads a table written by [`Interner::save()`].
+  ///
+  /// The yarns in the returned interner borrow from `table`, rather than
+  /// copying out of it.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error of kind [`io::ErrorKind::InvalidData`] if `table` is
+  /// not a valid table, including if `Buf` is `str` and the table contains
+  /// invalid UTF-8.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let table = b"yarntab1\x01\0\0\0\x01\0\0\0\xFF";
+  /// assert_eq!(Interner::<[u8]>::load(table).unwrap().len(), 1);
+  /// assert!(Interner::<str>::load(table).is_err());
+  /// assert!(Interner::<[u8]>::load(&table[..12]).is_err());
+  /// ```
+  pub fn load(table: &'a [u8]) -> io::Result<Self> {
+    let bad = |msg| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+    let rest = table
+      .strip_prefix(MAGIC)
+      .ok_or_else(|| bad("not an interner table"))?;
+    let (count, rest) =
+      read_u32(rest).ok_or_else(|| bad("truncated header"))?;
+    let ends_len = (count as usize)
+      .checked_mul(4)
+      .filter(|&n| n <= rest.len())
+      .ok_or_else(|| bad("truncated offsets"))?;
+    let (ends, data) = rest.split_at(ends_len);
+
+    let mut interner = Self::new();
+    let mut start = 0;
+    for end in ends.chunks_exact(4) {
+      let end = read_u32(end).unwrap().0 as usize;
+      let bytes = data
+        .get(start..end)
+        .ok_or_else(|| bad("offset out of bounds"))?;
+      start = end;
+
+      if <Buf as crate::z::Sealed>::UTF8 && str::from_utf8(bytes).is_err() {
+        return Err(bad("invalid UTF-8"));
+      }
+      let yarn = unsafe {
+        // SAFETY: We just checked that bytes is valid UTF-8 if Buf needs it.
+        YarnRef::<Buf>::raw2buf(bytes)
+      };
+
+      let len = interner.len();
+      if interner.intern(YarnBox::new(yarn)).index() != len {
+        return Err(bad("duplicate yarn"));
+      }
+    }
+
+    if start != data.len() {
+      return Err(bad("trailing data"));
+    }
+    Ok(interner)
+  }
+}
+
+fn read_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+  let (int, rest) = (bytes.get(..4)?, &bytes[4..]);
+  Some((u32::from_le_bytes(int.try_into().unwrap()), rest))
+}
+
+impl<Buf> Default for Interner<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Buf> fmt::Debug for Interner<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_list().entries(&self.yarns).finish()
+  }
+}
@@ -0,0 +1,28 @@
+//! Integration with the `itoa` crate.
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+
+impl YarnBox<'_, str> {
+  /// Returns a yarn containing the decimal representation of an integer.
+  ///
+  /// This produces the same string as `yarn!("{i}")`, but is much faster,
+  /// since it bypasses `core::fmt`. It does not allocate unless the result,
+  /// counting the minus sign, is longer than `RawYarn::SSO_LEN` bytes, which
+  /// is 15 on 64-bit targets and 7 on 32-bit ones.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// assert_eq!(Yarn::from_int(42), "42");
+  /// assert_eq!(Yarn::from_int(-128i8), "-128");
+  /// assert_eq!(Yarn::from_int(u128::MAX), u128::MAX.to_string());
+  /// ```
+  pub fn from_int(i: impl itoa::Integer) -> Self {
+    let mut buf = itoa::Buffer::new();
+    unsafe {
+      // SAFETY: copy_slice() returns a uniquely-owned raw yarn, and the bytes
+      // came out of a str.
+      YarnBox::from_raw(RawYarn::copy_slice(buf.format(i).as_bytes()))
+    }
+  }
+}
@@ -0,0 +1,73 @@
+//! Formatting sequences of yarns without concatenating them.
+
+use std::fmt;
+
+/// Returns an adaptor that formats the items of `iter`, separated by `sep`.
+///
+/// The items are written straight into the formatter, so listing many yarns
+/// in a message does not require building an intermediate string. Formatting
+/// options, like width and precision, are applied to each item, but not to
+/// the separator.
+///
+/// ```
+/// # use byteyarn::*;
+/// let missing = [yarn!("--input"), yarn!("--output"), yarn!("--level")];
+/// let msg = format!("missing flags: {}", display_joined(&missing, ", "));
+/// assert_eq!(msg, "missing flags: --input, --output, --level");
+///
+/// let cols = display_joined([1.5, 2.25], " | ");
+/// assert_eq!(format!("{cols:.2}"), "1.50 | 2.25");
+/// ```
+pub fn display_joined<I, S>(iter: I, sep: S) -> DisplayJoined<I::IntoIter, S>
+where
+  I: IntoIterator,
+  I::IntoIter: Clone,
+  I::Item: fmt::Display,
+  S: fmt::Display,
+{
+  DisplayJoined {
+    iter: iter.into_iter(),
+    sep,
+  }
+}
+
+/// A formatting adaptor that writes a sequence of items with a separator
+/// between them.
+///
+/// `Debug` renders the same as `Display`.
+///
+/// See [`display_joined()`].
+#[derive(Copy, Clone)]
+pub struct DisplayJoined<I, S> {
+  iter: I,
+  sep: S,
+}
+
+impl<I, S> fmt::Display for DisplayJoined<I, S>
+where
+  I: Iterator + Clone,
+  I::Item: fmt::Display,
+  S: fmt::Display,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, item) in self.iter.clone().enumerate() {
+      if i > 0 {
+        // The formatting options are meant for the items, not the separator.
+        write!(f, "{}", self.sep)?;
+      }
+      fmt::Display::fmt(&item, f)?;
+    }
+    Ok(())
+  }
+}
+
+impl<I, S> fmt::Debug for DisplayJoined<I, S>
+where
+  I: Iterator + Clone,
+  I::Item: fmt::Display,
+  S: fmt::Display,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(self, f)
+  }
+}
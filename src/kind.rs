@@ -0,0 +1,48 @@
+use crate::raw::RawYarn;
+
+#[cfg(doc)]
+use crate::*;
+
+/// The storage class of a yarn, as returned by [`YarnBox::kind()`] and
+/// [`YarnRef::kind()`].
+///
+/// Which class a yarn ends up in is an implementation detail that can change
+/// between versions; this is intended for profiling and for tests that want to
+/// check that a hot path avoids allocating.
+///
+/// ```
+/// # use byteyarn::*;
+/// assert_eq!(yarn!("smol").kind(), YarnKind::Small);
+/// assert_eq!(
+///   Yarn::from_static("a very long string literal").kind(),
+///   YarnKind::Static,
+/// );
+///
+/// let string = String::from("a very long heap-allocated string");
+/// assert_eq!(YarnBox::<str>::new(&string).kind(), YarnKind::Borrowed);
+/// assert_eq!(Yarn::from_string(string).kind(), YarnKind::Heap);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum YarnKind {
+  /// The contents are stored inline, inside the yarn itself.
+  Small,
+  /// The yarn points to a buffer that lives forever, such as a string literal
+  /// or a leaked allocation.
+  Static,
+  /// The yarn points to a buffer that it borrows for its lifetime.
+  Borrowed,
+  /// The yarn owns a heap allocation, which it frees when dropped.
+  Heap,
+}
+
+impl YarnKind {
+  /// Returns the kind of a raw yarn.
+  pub(crate) const fn of(raw: RawYarn) -> Self {
+    match raw.kind() {
+      RawYarn::SMALL => Self::Small,
+      RawYarn::STATIC => Self::Static,
+      RawYarn::HEAP => Self::Heap,
+      _ => Self::Borrowed,
+    }
+  }
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A yarn that is computed the first time it is used, for use in `static`s.
+///
+/// A `LazyYarn` holds a function that produces a yarn, which is called at
+/// most once, the first time the yarn is needed. Because the `LazyYarn` itself
+/// lives in a `static`, the yarn it produces does too, so it can be handed
+/// out as a `YarnRef<'static, _>`, even if it is on the heap.
+///
+/// The easiest way to create one is with [`lazy_yarn!()`].
+///
+/// ```
+/// # use byteyarn::*;
+/// static BANNER: LazyYarn<str> = LazyYarn::new(|| {
+///   yarn!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+/// });
+///
+/// let banner: YarnRef<'static, str> = BANNER.get();
+/// assert!(banner.starts_with("byteyarn v"));
+/// ```
+pub struct LazyYarn<Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  cell: OnceLock<YarnBox<'static, Buf>>,
+  init: fn() -> YarnBox<'static, Buf>,
+}
+
+impl<Buf> LazyYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns a new lazy yarn that will be computed by calling `init`.
+  pub const fn new(init: fn() -> YarnBox<'static, Buf>) -> Self {
+    Self {
+      cell: OnceLock::new(),
+      init,
+    }
+  }
+
+  /// Returns this yarn, computing it if this is the first use.
+  ///
+  /// If several threads race to compute the yarn, only one of them calls the
+  /// initialization function and the others wait for it.
+  pub fn get(&'static self) -> YarnRef<'static, Buf> {
+    self.force().as_ref()
+  }
+
+  /// Returns this yarn if it has already been computed.
+  pub fn try_get(&'static self) -> Option<YarnRef<'static, Buf>> {
+    self.cell.get().map(YarnBox::as_ref)
+  }
+
+  fn force(&self) -> &YarnBox<'static, Buf> {
+    self.cell.get_or_init(self.init)
+  }
+}
+
+impl<Buf> Deref for LazyYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Target = Buf;
+
+  fn deref(&self) -> &Buf {
+    self.force()
+  }
+}
+
+impl<Buf> fmt::Debug for LazyYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(self.force(), f)
+  }
+}
+
+impl<Buf> fmt::Display for LazyYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(self.force(), f)
+  }
+}
@@ -65,30 +65,237 @@ use std::hash::Hash;
 use std::borrow::Cow;
 
 mod boxed;
+mod buf;
 mod convert;
+mod counter;
+mod csv;
+mod cursor;
+mod elements;
+mod error;
+mod fat;
+mod front_coded;
+mod glob;
+mod hex;
+mod inline;
+mod intern;
+mod join;
+mod kind;
+mod lazy;
+mod map;
 mod raw;
 mod reffed;
+mod rope;
+mod secret;
+mod set;
+mod small;
+mod sort;
+mod sort_key;
+mod stable;
+mod static_map;
+mod symbol_map;
+mod text;
+mod thin;
 mod utf8;
+mod wire;
 
+#[cfg(feature = "actix-web")]
+mod actix_web;
+#[cfg(feature = "arcstr")]
+mod arcstr;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "ascii")]
+mod ascii;
+#[cfg(feature = "axum")]
+mod axum;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "camino")]
+mod camino;
+#[cfg(feature = "clap")]
+mod clap;
+#[cfg(feature = "compact_str")]
+mod compact_str;
+#[cfg(feature = "copy_limit")]
+mod copy_limit;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "ecow")]
+mod ecow;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "icu")]
+mod icu;
+#[cfg(feature = "itoa")]
+mod itoa;
+#[cfg(feature = "logos")]
+mod logos;
+#[cfg(feature = "nom")]
+mod nom;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "redis")]
+mod redis;
+#[cfg(feature = "regex")]
+mod regex;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "ryu")]
+mod ryu;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "serde_json")]
+mod serde_json;
+#[cfg(feature = "smol_str")]
+mod smol_str;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "string_cache")]
+mod string_cache;
+#[cfg(feature = "strsim")]
+mod strsim;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "tokio")]
+mod tokio;
+#[cfg(feature = "tracing")]
+mod tracing;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "valuable")]
+mod valuable;
+#[cfg(feature = "winnow")]
+mod winnow;
+#[cfg(feature = "yoke")]
+mod yoke;
+#[cfg(feature = "zeroize")]
+mod zeroize;
+
+#[cfg(feature = "serde_json")]
+pub use self::serde_json::RawJsonYarn;
+#[cfg(feature = "tokio")]
+pub use self::tokio::YarnWriter;
+#[cfg(feature = "arrow")]
+pub use arrow::ArrowIter;
 pub use boxed::YarnBox;
+pub use buf::YarnBuf;
+#[cfg(feature = "copy_limit")]
+pub use copy_limit::set_copy_limit;
+pub use counter::YarnCounter;
+pub use csv::CsvFields;
+pub use cursor::YarnCursor;
+pub use elements::Chunks;
+#[cfg(any(feature = "nom", feature = "winnow"))]
+pub use elements::ElementIndices;
+#[cfg(any(feature = "nom", feature = "winnow"))]
+pub use elements::Elements;
+pub use elements::Pattern;
+pub use elements::Windows;
+pub use error::FmtError;
+pub use error::TooLong;
+pub use error::WireError;
+pub use fat::FatYarn;
+pub use front_coded::FrontCodedIter;
+pub use front_coded::FrontCodedYarns;
+pub use hex::HexDump;
+#[cfg(feature = "icu")]
+pub use icu::Collated;
+pub use inline::InlineYarn;
+pub use intern::Interner;
+pub use intern::Symbol;
+pub use join::display_joined;
+pub use join::DisplayJoined;
+pub use kind::YarnKind;
+pub use lazy::LazyYarn;
+pub use map::YarnMap;
+pub use map::YarnMapIter;
 pub use reffed::YarnRef;
+pub use rope::YarnRope;
+pub use secret::SecretYarn;
+pub use set::YarnSet;
+pub use set::YarnSetIter;
+pub use small::SmallYarn;
+pub use sort::sort_yarns;
+pub use sort_key::SortKey;
+pub use stable::StableYarn;
+pub use static_map::StaticYarnMap;
+#[cfg(feature = "stats")]
+pub use stats::stats;
+#[cfg(feature = "stats")]
+pub use stats::Stats;
+pub use symbol_map::SymbolMap;
+pub use symbol_map::SymbolSet;
+pub use text::Wrap;
+pub use thin::ThinYarn;
+pub use utf8::CharIndex;
+pub use utf8::CharIndicesLossy;
+pub use utf8::CharsLossy;
 pub use utf8::Utf8Chunks;
 
 mod z {
-  pub trait Sealed {}
+  pub trait Sealed {
+    /// Whether this buffer type must contain valid UTF-8.
+    const UTF8: bool;
+  }
 }
 
 // Macro stuff.
 #[doc(hidden)]
 pub mod m {
   pub extern crate std;
+
+  pub use crate::static_map::sort_order;
+  pub use crate::static_map::Entries;
 }
 
 /// A trait for abstracting over `str` and `[u8]`.
+///
+/// This trait is sealed, and these are the only two buffer types: yarns pack
+/// their length and storage class into a single word and inline short
+/// buffers byte-for-byte, so they are inherently byte strings, and there is no
+/// `YarnBox<[T]>` for other element types. Element access on byte yarns goes
+/// through their [`Deref`][std::ops::Deref] implementation, which provides
+/// `iter()`, `get()`, `first()`, `last()`, and so on; subyarns are available
+/// through [`YarnRef::chunk_yarns()`] and friends.
+///
+/// ```
+/// # use byteyarn::*;
+/// let yarn = ByteYarn::from([1, 2, 3]);
+/// assert_eq!(yarn.first(), Some(&1));
+/// assert_eq!(yarn.iter().sum::<u8>(), 6);
+/// ```
+///
+/// In particular, [`CStr`][std::ffi::CStr] is not a buffer type. A `&CStr`
+/// is not guaranteed to share a `&[u8]`'s layout, and its length excludes a
+/// terminator that generic operations such as [`YarnBox::from_char()`] and
+/// [`YarnBuf`] appends would not know to maintain. Instead, keep the
+/// nul-terminated bytes in a byte yarn and borrow them as a `CStr` at the FFI
+/// boundary:
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::ffi::CStr;
+///
+/// let name = ByteYarn::new(b"libbyteyarn.so\0");
+/// assert!(name.is_inlined());
+///
+/// let c_name = CStr::from_bytes_with_nul(&name).unwrap();
+/// assert_eq!(c_name.to_bytes(), b"libbyteyarn.so");
+/// ```
 pub trait Buf: z::Sealed + Eq + Ord + Hash {}
 
-impl z::Sealed for [u8] {}
-impl z::Sealed for str {}
+impl z::Sealed for [u8] {
+  const UTF8: bool = false;
+}
+impl z::Sealed for str {
+  const UTF8: bool = true;
+}
 
 impl Buf for [u8] {}
 impl Buf for str {}
@@ -100,6 +307,22 @@ pub type Yarn = YarnBox<'static, str>;
 
 /// An optimized raw byte string.
 ///
+/// Byte yarns can be formatted as one contiguous number with `{:x}`, `{:X}`,
+/// and `{:b}`, which is handy for digests and other binary identifiers. For
+/// a more readable layout, see [`YarnBox::hex_dump()`].
+///
+/// ```
+/// # use byteyarn::*;
+/// let id = ByteYarn::from([0x00, 0x1b, 0x63, 0xff]);
+/// assert_eq!(format!("{id:x}"), "001b63ff");
+/// assert_eq!(format!("{id:#X}"), "0x001B63FF");
+/// assert_eq!(format!("{:b}", YarnRef::new(&id[..2])), "0000000000011011");
+///
+/// // Width and padding behave as they do for integers.
+/// assert_eq!(format!("{id:>10x}"), "  001b63ff");
+/// assert_eq!(format!("{id:#012x}"), "0x00001b63ff");
+/// ```
+///
 /// See [`YarnBox`] for full type documentation.
 pub type ByteYarn = YarnBox<'static, [u8]>;
 
@@ -112,3 +335,163 @@ macro_rules! yarn {
     $crate::Yarn::from_fmt($crate::m::std::format_args!($($args)*))
   };
 }
+
+/// Similar to [`format!()`], but returns a [`ByteYarn`], instead.
+///
+/// This macro calls out to [`ByteYarn::from_fmt()`] internally, which formats
+/// directly into the byte yarn.
+///
+/// Formatting only ever produces UTF-8, so byte arguments must be rendered
+/// as text, such as with `{:x?}` or [`ByteYarn::hex_dump()`]. To splice raw,
+/// possibly non-UTF-8 bytes into formatted output, build it in a [`YarnBuf`]
+/// with [`yarn_write!()`] and [`YarnBuf::push()`].
+///
+/// ```
+/// # use byteyarn::*;
+/// let digest = [0xde, 0xad, 0xbe, 0xef];
+/// let line = byarn!("sha={:02x?} len={}", digest, digest.len());
+/// assert_eq!(line, b"sha=[de, ad, be, ef] len=4");
+///
+/// let mut frame = YarnBuf::<[u8]>::new();
+/// yarn_write!(frame, "LEN {}\n", digest.len());
+/// frame.push(&digest);
+/// assert_eq!(frame.freeze(), b"LEN 4\n\xde\xad\xbe\xef");
+/// ```
+#[macro_export]
+macro_rules! byarn {
+  ($($args:tt)*) => {
+    $crate::ByteYarn::from_fmt($crate::m::std::format_args!($($args)*))
+  };
+}
+
+/// Similar to [`write!()`], but appends to a [`YarnBuf`], instead.
+///
+/// Writing to a `YarnBuf` cannot fail, so unlike `write!()`, this does not
+/// return a `Result`. It works for both string and byte buffers.
+///
+/// This macro calls out to [`YarnBuf::push_fmt()`] internally.
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut buf = YarnBuf::<str>::new();
+/// for (i, name) in ["alpha", "beta"].iter().enumerate() {
+///   yarn_write!(buf, "{i}: {name}; ");
+/// }
+/// yarn_write!(buf, "total: {}", 2);
+/// assert_eq!(buf.freeze(), "0: alpha; 1: beta; total: 2");
+///
+/// let mut bytes = YarnBuf::<[u8]>::new();
+/// bytes.push(b"\x00");
+/// yarn_write!(&mut bytes, "{:x}", 255);
+/// assert_eq!(bytes.freeze(), b"\x00ff");
+/// ```
+#[macro_export]
+macro_rules! yarn_write {
+  ($buf:expr, $($args:tt)*) => {
+    ($buf).push_fmt($crate::m::std::format_args!($($args)*))
+  };
+}
+
+/// Like [`yarn!()`], but produces a [`LazyYarn`] that does the formatting the
+/// first time it is used.
+///
+/// This is intended for global strings that can only be computed at runtime.
+///
+/// ```
+/// # use byteyarn::*;
+/// fn user() -> String {
+///   "ferris".into()
+/// }
+///
+/// static GREETING: LazyYarn<str> = lazy_yarn!("hello, {}!", user());
+/// assert_eq!(GREETING.get(), "hello, ferris!");
+/// assert_eq!(&*GREETING, "hello, ferris!");
+/// ```
+#[macro_export]
+macro_rules! lazy_yarn {
+  ($($args:tt)*) => {
+    $crate::LazyYarn::new(|| $crate::yarn!($($args)*))
+  };
+}
+
+/// Similar to [`include_str!()`], but returns a [`Yarn`], instead.
+///
+/// The yarn points into the embedded file's contents, which are static, so
+/// this macro can be used in `const` and `static` initializers and never
+/// copies.
+///
+/// ```
+/// # use byteyarn::*;
+/// const MANIFEST: Yarn = include_yarn!("../Cargo.toml");
+/// assert!(MANIFEST.starts_with("[package]"));
+/// assert_eq!(MANIFEST.kind(), YarnKind::Static);
+/// ```
+#[macro_export]
+macro_rules! include_yarn {
+  ($path:expr $(,)?) => {
+    $crate::Yarn::from_static($crate::m::std::include_str!($path))
+  };
+}
+
+/// Similar to [`include_bytes!()`], but returns a [`ByteYarn`], instead.
+///
+/// Like [`include_yarn!()`], this can be used in `const` and `static`
+/// initializers and never copies.
+///
+/// ```
+/// # use byteyarn::*;
+/// static MANIFEST: ByteYarn = include_byarn!("../Cargo.toml");
+/// assert_eq!(MANIFEST, include_bytes!("../Cargo.toml"));
+/// ```
+#[macro_export]
+macro_rules! include_byarn {
+  ($path:expr $(,)?) => {
+    $crate::ByteYarn::from_static($crate::m::std::include_bytes!($path))
+  };
+}
+
+/// Declares a [`StaticYarnMap`], a lookup table from static yarns to values
+/// that is built at compile time.
+///
+/// The keys must be byte string literals, or string literals if the table's
+/// second type parameter is `str`, and the values must be constant
+/// expressions. Repeating a key is a compile-time error.
+///
+/// ```
+/// # use byteyarn::*;
+/// static_yarn_map! {
+///   /// HTTP methods that are safe to retry.
+///   pub static IDEMPOTENT: StaticYarnMap<bool> = {
+///     b"GET" => true,
+///     b"PUT" => true,
+///     b"POST" => false,
+///   };
+/// }
+///
+/// assert_eq!(IDEMPOTENT.get(b"PUT"), Some(&true));
+/// assert_eq!(IDEMPOTENT.len(), 3);
+/// ```
+///
+/// ```compile_fail,E0080
+/// # use byteyarn::*;
+/// static_yarn_map! {
+///   static DUPES: StaticYarnMap<i32, str> = { "a" => 1, "a" => 2 };
+/// }
+/// ```
+#[macro_export]
+macro_rules! static_yarn_map {
+  ($(
+    $(#[$attr:meta])*
+    $vis:vis static $name:ident: StaticYarnMap<$V:ty $(, $Buf:ty)?> = {
+      $($key:expr => $value:expr),* $(,)?
+    };
+  )*) => {$(
+    $(#[$attr])*
+    $vis static $name: $crate::StaticYarnMap<$V $(, $Buf)?> = {
+      const ENTRIES: &$crate::m::Entries<$V $(, $Buf)?> =
+        &[$(($crate::YarnRef::from_static($key), $value)),*];
+      const ORDER: [usize; ENTRIES.len()] = $crate::m::sort_order(ENTRIES);
+      $crate::StaticYarnMap::__new(ENTRIES, &ORDER)
+    };
+  )*};
+}
@@ -0,0 +1,67 @@
+//! Integration with the `logos` crate.
+//!
+//! Yarn references deref to their contents, so `logos` can already lex them,
+//! with `#[logos(source = (YarnRef<'s, str>))]` (or `[u8]`). However, the
+//! slices `logos` hands out are ordinary slices borrowed from the yarn; this
+//! module provides a way to get subyarns, instead.
+
+use logos::Lexer;
+use logos::Logos;
+use logos::Source;
+
+use crate::YarnRef;
+
+impl<'s, Buf> YarnRef<'s, Buf>
+where
+  Buf: crate::Buf + Source + ?Sized,
+{
+  /// Returns the token `lex` just lexed as a subyarn of the yarn it is lexing,
+  /// without copying.
+  ///
+  /// Unlike [`Lexer::slice()`], this preserves static-ness: tokens lexed from
+  /// a static yarn are static too.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use logos::Logos;
+  ///
+  /// #[derive(Logos, Debug, PartialEq)]
+  /// #[logos(source = (YarnRef<'s, str>))]
+  /// #[logos(skip r"\s+")]
+  /// enum Token<'s> {
+  ///   #[regex("[a-z]+", YarnRef::from_lexer)]
+  ///   Word(YarnRef<'s, str>),
+  ///   #[token("=")]
+  ///   Eq,
+  /// }
+  ///
+  /// let source = YarnRef::from_static("colour = tabby");
+  /// let tokens = Token::lexer(&source).collect::<Result<Vec<_>, _>>().unwrap();
+  /// assert_eq!(tokens, [
+  ///   Token::Word(YarnRef::new("colour")),
+  ///   Token::Eq,
+  ///   Token::Word(YarnRef::new("tabby")),
+  /// ]);
+  ///
+  /// let Token::Word(tabby) = &tokens[2] else { unreachable!() };
+  /// assert!(tabby.immortalize().is_some());
+  /// ```
+  pub fn from_lexer<T>(lex: &Lexer<'s, T>) -> Self
+  where
+    T: Logos<'s, Source = Self>,
+  {
+    let source = *lex.source();
+    let span = lex.span();
+    assert!(
+      source.as_slice().slice(span.clone()).is_some(),
+      "lexer span {span:?} is not valid for a yarn of length {}",
+      source.len(),
+    );
+
+    unsafe {
+      // SAFETY: We just checked that span is in bounds and, for str, on
+      // character boundaries.
+      source.slice_unchecked(span)
+    }
+  }
+}
@@ -0,0 +1,272 @@
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::Index;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A hash map whose keys are yarns.
+///
+/// This is a thin wrapper over a [`HashMap`] with [`YarnBox`] keys, which
+/// makes for a cheap symbol table: keys short enough to inline live directly
+/// in the table, without a separate allocation, and static keys are never
+/// copied. Lookups accept anything that can be viewed as a `Buf`, including
+/// string slices, byte slices, and yarns, without allocating.
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut map = YarnMap::<u32, str>::new();
+/// map.insert("fn", 0);
+/// map.insert(String::from("let"), 1);
+/// map.insert(yarn!("{}{}", "stat", "ic"), 2);
+///
+/// assert_eq!(map.get("fn"), Some(&0));
+/// assert_eq!(map.get(&yarn!("let")), Some(&1));
+/// assert_eq!(map.get(YarnRef::new("static")), Some(&2));
+/// assert_eq!(map.get("mut"), None);
+/// ```
+pub struct YarnMap<V, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  map: HashMap<YarnBox<'static, Buf>, V>,
+}
+
+impl<V, Buf> YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  /// Returns a new, empty map.
+  pub fn new() -> Self {
+    Self {
+      map: HashMap::new(),
+    }
+  }
+
+  /// Returns a new, empty map with room for at least `capacity` entries.
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      map: HashMap::with_capacity(capacity),
+    }
+  }
+
+  /// Returns the number of entries in this map.
+  pub fn len(&self) -> usize {
+    self.map.len()
+  }
+
+  /// Checks whether this map is empty.
+  pub fn is_empty(&self) -> bool {
+    self.map.is_empty()
+  }
+
+  /// Removes every entry from this map.
+  pub fn clear(&mut self) {
+    self.map.clear()
+  }
+
+  /// Inserts a key-value pair, returning the value previously associated with
+  /// `key`, if any.
+  ///
+  /// If the key was already present, the original key yarn is kept.
+  pub fn insert(
+    &mut self,
+    key: impl Into<YarnBox<'static, Buf>>,
+    value: V,
+  ) -> Option<V> {
+    self.map.insert(key.into(), value)
+  }
+
+  /// Looks up the value associated with `key`.
+  pub fn get(&self, key: impl AsRef<Buf>) -> Option<&V> {
+    self.map.get(key.as_ref())
+  }
+
+  /// Looks up the value associated with `key`, mutably.
+  pub fn get_mut(&mut self, key: impl AsRef<Buf>) -> Option<&mut V> {
+    self.map.get_mut(key.as_ref())
+  }
+
+  /// Looks up the entry for `key`, returning the key as stored in this map.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut map = YarnMap::<(), str>::new();
+  /// map.insert(Yarn::from_static("a long static key, not copied"), ());
+  ///
+  /// let key = String::from("a long static key, not copied");
+  /// let (interned, _) = map.get_key_value(&key).unwrap();
+  /// let interned: YarnRef<'static, str> = interned.immortalize().unwrap();
+  /// assert_eq!(interned, key);
+  /// ```
+  pub fn get_key_value(
+    &self,
+    key: impl AsRef<Buf>,
+  ) -> Option<(YarnRef<Buf>, &V)> {
+    self
+      .map
+      .get_key_value(key.as_ref())
+      .map(|(k, v)| (k.as_ref(), v))
+  }
+
+  /// Checks whether this map contains `key`.
+  pub fn contains_key(&self, key: impl AsRef<Buf>) -> bool {
+    self.map.contains_key(key.as_ref())
+  }
+
+  /// Removes `key` from this map, returning its value, if it was present.
+  pub fn remove(&mut self, key: impl AsRef<Buf>) -> Option<V> {
+    self.map.remove(key.as_ref())
+  }
+
+  /// Returns an iterator over the entries of this map, in arbitrary order.
+  pub fn iter(&self) -> YarnMapIter<V, Buf> {
+    YarnMapIter {
+      iter: self.map.iter(),
+    }
+  }
+
+  /// Returns an iterator over the keys of this map, in arbitrary order.
+  pub fn keys(&self) -> impl Iterator<Item = YarnRef<Buf>> + '_ {
+    self.iter().map(|(k, _)| k)
+  }
+
+  /// Returns an iterator over the values of this map, in arbitrary order.
+  pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+    self.map.values()
+  }
+}
+
+/// An iterator over the entries of a [`YarnMap`].
+pub struct YarnMapIter<'a, V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  iter: hash_map::Iter<'a, YarnBox<'static, Buf>, V>,
+}
+
+impl<'a, V, Buf> Iterator for YarnMapIter<'a, V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = (YarnRef<'a, Buf>, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|(k, v)| (k.as_ref(), v))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<V, Buf> ExactSizeIterator for YarnMapIter<'_, V, Buf> where
+  Buf: crate::Buf + ?Sized + 'static
+{
+}
+impl<V, Buf> FusedIterator for YarnMapIter<'_, V, Buf> where
+  Buf: crate::Buf + ?Sized + 'static
+{
+}
+
+impl<'a, V, Buf> IntoIterator for &'a YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = (YarnRef<'a, Buf>, &'a V);
+  type IntoIter = YarnMapIter<'a, V, Buf>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<V, Buf> IntoIterator for YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = (YarnBox<'static, Buf>, V);
+  type IntoIter = hash_map::IntoIter<YarnBox<'static, Buf>, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.map.into_iter()
+  }
+}
+
+impl<K, V, Buf> FromIterator<(K, V)> for YarnMap<V, Buf>
+where
+  K: Into<YarnBox<'static, Buf>>,
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    let mut map = Self::new();
+    map.extend(iter);
+    map
+  }
+}
+
+impl<K, V, Buf> Extend<(K, V)> for YarnMap<V, Buf>
+where
+  K: Into<YarnBox<'static, Buf>>,
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    self
+      .map
+      .extend(iter.into_iter().map(|(k, v)| (k.into(), v)))
+  }
+}
+
+impl<V, Buf, Key> Index<&Key> for YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+  Key: AsRef<Buf> + ?Sized,
+{
+  type Output = V;
+
+  fn index(&self, key: &Key) -> &V {
+    self.get(key).expect("key not present in YarnMap")
+  }
+}
+
+impl<V, Buf> Default for YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<V: Clone, Buf> Clone for YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn clone(&self) -> Self {
+    Self {
+      map: self.map.clone(),
+    }
+  }
+}
+
+impl<V: PartialEq, Buf> PartialEq for YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn eq(&self, that: &Self) -> bool {
+    self.map == that.map
+  }
+}
+
+impl<V: Eq, Buf> Eq for YarnMap<V, Buf> where Buf: crate::Buf + ?Sized + 'static {}
+
+impl<V: fmt::Debug, Buf> fmt::Debug for YarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
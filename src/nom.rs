@@ -0,0 +1,168 @@
+//! Integration with the `nom` crate.
+//!
+//! Yarn references can be used directly as parser input; every piece of input
+//! a parser hands back is a subyarn that borrows from the original buffer, or
+//! is static if the original yarn was.
+
+use nom::Compare;
+use nom::CompareResult;
+use nom::FindSubstring;
+use nom::Input;
+use nom::Needed;
+use nom::Offset;
+
+use crate::elements::Decode;
+use crate::ElementIndices;
+use crate::Elements;
+use crate::YarnRef;
+
+/// Yarns can be consumed directly by `nom` parsers.
+///
+/// ```
+/// # use byteyarn::*;
+/// use nom::bytes::complete::tag;
+/// use nom::character::complete::alpha1;
+/// use nom::IResult;
+/// use nom::Parser;
+///
+/// fn key(input: YarnRef<str>) -> IResult<YarnRef<str>, YarnRef<str>> {
+///   let (rest, (key, _)) = (alpha1, tag(" = ")).parse(input)?;
+///   Ok((rest, key))
+/// }
+///
+/// let line = YarnRef::from_static("colour = tabby");
+/// let (rest, key) = key(line).unwrap();
+/// assert_eq!(key, "colour");
+/// assert_eq!(rest, "tabby");
+///
+/// // Subyarns of static yarns are static too.
+/// assert!(rest.immortalize().is_some());
+/// ```
+impl<'a, Buf> Input for YarnRef<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+{
+  type Item = Buf::Item;
+  type Iter = Elements<'a, Buf>;
+  type IterIndices = ElementIndices<'a, Buf>;
+
+  fn input_len(&self) -> usize {
+    self.len()
+  }
+
+  fn take(&self, index: usize) -> Self {
+    self.split_at(index).0
+  }
+
+  fn take_from(&self, index: usize) -> Self {
+    self.split_at(index).1
+  }
+
+  fn take_split(&self, index: usize) -> (Self, Self) {
+    let (prefix, suffix) = self.split_at(index);
+    (suffix, prefix)
+  }
+
+  fn position<P>(&self, predicate: P) -> Option<usize>
+  where
+    P: Fn(Self::Item) -> bool,
+  {
+    self
+      .iter_indices()
+      .find(|&(_, item)| predicate(item))
+      .map(|(idx, _)| idx)
+  }
+
+  fn iter_elements(&self) -> Self::Iter {
+    Elements::new(*self)
+  }
+
+  fn iter_indices(&self) -> Self::IterIndices {
+    ElementIndices::new(*self)
+  }
+
+  fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+    let mut have = 0;
+    for (idx, _) in self.iter_indices() {
+      if have == count {
+        return Ok(idx);
+      }
+      have += 1;
+    }
+
+    if have == count {
+      return Ok(self.len());
+    }
+    match Buf::BYTEWISE {
+      true => Err(Needed::new(count - have)),
+      false => Err(Needed::Unknown),
+    }
+  }
+}
+
+impl<Buf, T> Compare<T> for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  for<'c> &'c Buf: Compare<T>,
+{
+  fn compare(&self, t: T) -> CompareResult {
+    self.as_slice().compare(t)
+  }
+
+  fn compare_no_case(&self, t: T) -> CompareResult {
+    self.as_slice().compare_no_case(t)
+  }
+}
+
+/// ```
+/// # use byteyarn::*;
+/// use nom::bytes::complete::take_until;
+/// use nom::IResult;
+///
+/// fn line(input: YarnRef<[u8]>) -> IResult<YarnRef<[u8]>, YarnRef<[u8]>> {
+///   take_until("\r\n")(input)
+/// }
+///
+/// let (rest, first) = line(YarnRef::new(b"GET / HTTP/1.1\r\nHost: x")).unwrap();
+/// assert_eq!(first, b"GET / HTTP/1.1");
+/// assert_eq!(rest, b"\r\nHost: x");
+/// ```
+impl<Buf, T> FindSubstring<T> for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  for<'c> &'c Buf: FindSubstring<T>,
+{
+  fn find_substring(&self, substr: T) -> Option<usize> {
+    self.as_slice().find_substring(substr)
+  }
+}
+
+/// `nom` only ever asks for the offset of a suffix of a yarn, which is what
+/// this implementation assumes; it does not compare pointers, since subyarns
+/// of inlined yarns are copies.
+///
+/// ```
+/// # use byteyarn::*;
+/// use nom::character::complete::alpha1;
+/// use nom::character::complete::digit1;
+/// use nom::combinator::recognize;
+/// use nom::IResult;
+/// use nom::Parser;
+///
+/// fn ident(input: YarnRef<str>) -> IResult<YarnRef<str>, YarnRef<str>> {
+///   recognize((alpha1, digit1)).parse(input)
+/// }
+///
+/// let (rest, word) = ident(YarnRef::new("cat9 lives")).unwrap();
+/// assert_eq!(word, "cat9");
+/// assert_eq!(rest, " lives");
+/// ```
+impl<Buf> Offset for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn offset(&self, second: &Self) -> usize {
+    debug_assert!(second.len() <= self.len());
+    self.len() - second.len()
+  }
+}
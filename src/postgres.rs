@@ -0,0 +1,117 @@
+//! Integration with the `postgres` family of crates, through `postgres-types`.
+//!
+//! String yarns map to `TEXT` (and the other types `&str` maps to), and byte
+//! yarns map to `BYTEA`.
+//!
+//! [`YarnRef`]s borrow their contents from the row they are read out of,
+//! while [`YarnBox`]es copy them, unless they are short enough to inline:
+//!
+//! ```
+//! # use byteyarn::*;
+//! use postgres_types::FromSql;
+//! use postgres_types::Type;
+//!
+//! let raw = b"cached";
+//! let borrowed = YarnRef::<str>::from_sql(&Type::TEXT, raw).unwrap();
+//! assert_eq!(borrowed, "cached");
+//!
+//! let owned = Yarn::from_sql(&Type::TEXT, raw).unwrap();
+//! assert!(owned.is_inlined());
+//!
+//! assert!(<Yarn as FromSql>::accepts(&Type::VARCHAR));
+//! assert!(!<Yarn as FromSql>::accepts(&Type::BYTEA));
+//! assert!(<ByteYarn as FromSql>::accepts(&Type::BYTEA));
+//! ```
+
+use std::error::Error;
+use std::str;
+
+use bytes::BytesMut;
+use postgres_types::to_sql_checked;
+use postgres_types::FromSql;
+use postgres_types::IsNull;
+use postgres_types::ToSql;
+use postgres_types::Type;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+impl<Buf> ToSql for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+    match <Buf as crate::z::Sealed>::UTF8 {
+      true => unsafe {
+        // SAFETY: If Buf is UTF-8, so is the yarn.
+        str::from_utf8_unchecked(self.as_bytes()).to_sql(ty, out)
+      },
+      false => self.as_bytes().to_sql(ty, out),
+    }
+  }
+
+  fn accepts(ty: &Type) -> bool {
+    match <Buf as crate::z::Sealed>::UTF8 {
+      true => <&str as ToSql>::accepts(ty),
+      false => <&[u8] as ToSql>::accepts(ty),
+    }
+  }
+
+  to_sql_checked!();
+}
+
+impl<Buf> ToSql for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+    self.as_ref().to_sql(ty, out)
+  }
+
+  fn accepts(ty: &Type) -> bool {
+    <YarnRef<Buf> as ToSql>::accepts(ty)
+  }
+
+  to_sql_checked!();
+}
+
+impl<'a, Buf> FromSql<'a> for YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+    let bytes = match <Buf as crate::z::Sealed>::UTF8 {
+      true => <&str>::from_sql(ty, raw)?.as_bytes(),
+      false => <&[u8]>::from_sql(ty, raw)?,
+    };
+
+    Ok(YarnRef::new(unsafe {
+      // SAFETY: from_sql() validates UTF-8 if Buf requires it.
+      YarnRef::<Buf>::raw2buf(bytes)
+    }))
+  }
+
+  fn accepts(ty: &Type) -> bool {
+    <YarnRef<Buf> as ToSql>::accepts(ty)
+  }
+}
+
+impl<'a, Buf> FromSql<'a> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+    let yarn = YarnRef::<Buf>::from_sql(ty, raw)?;
+    Ok(unsafe {
+      // SAFETY: yarn is a valid Buf.
+      YarnBox::from_raw(RawYarn::copy_slice(yarn.as_bytes()))
+    })
+  }
+
+  fn accepts(ty: &Type) -> bool {
+    <YarnRef<Buf> as ToSql>::accepts(ty)
+  }
+}
@@ -1,4 +1,5 @@
 use std::alloc;
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Write;
 use std::mem;
@@ -6,10 +7,25 @@ use std::mem::MaybeUninit;
 use std::num::NonZeroUsize;
 use std::slice;
 
+use crate::FmtError;
+use crate::TooLong;
+
 /// The core implementation of yarns.
 ///
 /// This type encapsulates the various size optimizations that yarns make; this
 /// wrapper is shared between both owning and non-owning yarns.
+///
+/// The tag lives in the most significant byte of the length word, which must
+/// coincide with `Small::len`. On little-endian targets that is the last byte
+/// of the yarn, so the length goes after the pointer; on big-endian targets
+/// it is the first byte of the length word, so the length goes first.
+///
+/// Keeping the tag in the length rather than the pointer means the pointer is
+/// never round-tripped through an integer: it is only ever stored and loaded
+/// as a pointer, so its provenance survives, and the code is clean under
+/// Miri's `-Zmiri-strict-provenance`. Small yarns overwrite the pointer with
+/// data, but a small yarn's pointer is never dereferenced.
+#[cfg(target_endian = "little")]
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct RawYarn {
@@ -17,6 +33,15 @@ pub struct RawYarn {
   len: NonZeroUsize,
 }
 
+#[cfg(target_endian = "big")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct RawYarn {
+  len: NonZeroUsize,
+  ptr: *const u8,
+}
+
+#[cfg(target_endian = "little")]
 #[repr(C)]
 #[derive(Copy, Clone)]
 struct Small {
@@ -24,6 +49,15 @@ struct Small {
   len: u8,
 }
 
+#[cfg(target_endian = "big")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Small {
+  len: u8,
+  data: [u8; mem::size_of::<RawYarn>() - 1],
+}
+
+#[cfg(target_endian = "little")]
 #[repr(C)]
 #[derive(Copy, Clone)]
 struct Slice {
@@ -31,6 +65,14 @@ struct Slice {
   len: usize,
 }
 
+#[cfg(target_endian = "big")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Slice {
+  len: usize,
+  ptr: *const u8,
+}
+
 enum Layout<'a> {
   Small(&'a Small),
   Slice(&'a Slice),
@@ -50,6 +92,47 @@ fn has_niche() {
   assert_eq!(mem::size_of::<RawYarn>(), mem::size_of::<Option<RawYarn>>());
 }
 
+#[test]
+fn small_cmp_matches_slice_cmp() {
+  let data: &[&[u8]] = &[b"", b"\0", b"a", b"a\0", b"ab", b"b", b"abcdefg"];
+  for &a in data {
+    for &b in data {
+      let ya = RawYarn::from_slice_inlined(a).unwrap();
+      let yb = RawYarn::from_slice_inlined(b).unwrap();
+      assert_eq!(ya.cmp_bytes(yb), a.cmp(b), "{a:?} vs {b:?}");
+      assert_eq!(ya.cmp_slice(b), a.cmp(b), "{a:?} vs {b:?}");
+      assert_eq!(ya.eq_bytes(yb), a == b, "{a:?} vs {b:?}");
+      assert_eq!(ya.eq_slice(b), a == b, "{a:?} vs {b:?}");
+    }
+  }
+}
+
+#[test]
+fn tag_overlaps_small_len() {
+  let yarn = RawYarn::from_slice_inlined(b"abc").unwrap();
+  let small = unsafe { mem::transmute::<RawYarn, Small>(yarn) };
+  assert_eq!(small.len, 3 | RawYarn::SMALL << RawYarn::SHIFT8);
+  assert_eq!(&small.data[..3], b"abc");
+
+  let word = unsafe { mem::transmute::<RawYarn, Slice>(yarn) }.len;
+  assert_eq!((word >> RawYarn::SHIFT) as u8, RawYarn::SMALL);
+  assert_eq!(word.to_be_bytes()[0], small.len);
+
+  for len in 0..=RawYarn::SSO_LEN {
+    let data = &b"0123456789abcdefghijklmnopqrstuv"[..len];
+    let yarn = RawYarn::from_slice_inlined(data).unwrap();
+    assert!(yarn.is_small());
+    assert_eq!(yarn.len(), len);
+    assert_eq!(yarn.as_slice(), data);
+  }
+
+  let data = b"a string much too long to inline";
+  let yarn = unsafe { RawYarn::alias_slice(data) };
+  assert_eq!(yarn.kind(), RawYarn::ALIASED);
+  assert_eq!(yarn.as_ptr(), data.as_ptr());
+  assert_eq!(yarn.len(), data.len());
+}
+
 impl RawYarn {
   /// The number of bytes beyond the length byte that are usable for data.
   /// This is 7 on 32-bit and 15 on 64-bit.
@@ -71,6 +154,10 @@ impl RawYarn {
     sso_len
   };
 
+  /// The longest buffer, in bytes, that a yarn can hold, since the top two
+  /// bits of the length are used for the tag.
+  pub const MAX_LEN: usize = usize::MAX / 4 - 1;
+
   /// The tag for an SSO yarn.
   pub const SMALL: u8 = 0b11;
   /// The tag for a yarn that came from an immortal string slice.
@@ -127,7 +214,7 @@ impl RawYarn {
     tag: u8,
   ) -> Self {
     assert!(
-      len < usize::MAX / 4,
+      len <= Self::MAX_LEN,
       "yarns cannot be larger than a quarter of the address space"
     );
     debug_assert!(
@@ -226,6 +313,121 @@ impl RawYarn {
     self.kind() != Self::ALIASED
   }
 
+  /// Checks whether two yarns have the same contents.
+  ///
+  /// When both yarns are `SMALL`, this takes two word comparisons rather than
+  /// a call to bcmp.
+  #[inline]
+  pub fn eq_bytes(self, that: Self) -> bool {
+    if self.is_small() && that.is_small() {
+      // SAFETY: SMALL yarns consist entirely of initialized bytes, and
+      // RawYarn is two words wide.
+      let a = unsafe { mem::transmute::<Self, [usize; 2]>(self) };
+      let b = unsafe { mem::transmute::<Self, [usize; 2]>(that) };
+
+      // Every byte of `data` past the contents is zero, and the length byte
+      // is part of the words, so the words are equal exactly when the
+      // contents are.
+      return a == b;
+    }
+
+    self.as_slice() == that.as_slice()
+  }
+
+  /// Like [`RawYarn::eq_bytes()`], but compares against a slice, which is
+  /// inlined first if this yarn is `SMALL`.
+  #[inline]
+  pub fn eq_slice(self, that: &[u8]) -> bool {
+    if self.is_small() {
+      if let Some(that) = Self::from_slice_inlined(that) {
+        return self.eq_bytes(that);
+      }
+    }
+
+    self.as_slice() == that
+  }
+
+  // NOTE: There is no word-wise counterpart for Hash. Borrow<Buf> requires a
+  // yarn to hash exactly like its slice, which feeds the hasher its length
+  // and then its bytes; hashing a SMALL yarn's two words instead would give
+  // `HashMap<Yarn, _>::get("key")` a different hash than the key it looks for.
+
+  /// Compares the contents of two yarns.
+  ///
+  /// When both yarns are `SMALL`, this takes two word comparisons rather than
+  /// a call to memcmp.
+  #[inline]
+  pub fn cmp_bytes(self, that: Self) -> Ordering {
+    if cfg!(target_endian = "little") && self.is_small() && that.is_small() {
+      // SAFETY: SMALL yarns consist entirely of initialized bytes, and
+      // RawYarn is two words wide.
+      let [a0, a1] = unsafe { mem::transmute::<Self, [usize; 2]>(self) };
+      let [b0, b1] = unsafe { mem::transmute::<Self, [usize; 2]>(that) };
+
+      // Every byte of `data` past the contents is zero, and the length byte
+      // comes last, so comparing the words most significant byte first
+      // compares the contents lexicographically and then breaks ties by
+      // length. This is only true on little-endian targets; on big-endian
+      // ones, the length byte comes first.
+      return (usize::from_be(a0), usize::from_be(a1))
+        .cmp(&(usize::from_be(b0), usize::from_be(b1)));
+    }
+
+    self.as_slice().cmp(that.as_slice())
+  }
+
+  /// Like [`RawYarn::cmp_bytes()`], but compares against a slice, which is
+  /// inlined first if this yarn is `SMALL`.
+  #[inline]
+  pub fn cmp_slice(self, that: &[u8]) -> Ordering {
+    if self.is_small() {
+      if let Some(that) = Self::from_slice_inlined(that) {
+        return self.cmp_bytes(that);
+      }
+    }
+
+    self.as_slice().cmp(that)
+  }
+
+  /// Panics if this yarn's representation is inconsistent.
+  ///
+  /// If `utf8` is set, this also checks that the contents are valid UTF-8.
+  #[track_caller]
+  pub fn assert_invariants(self, utf8: bool) {
+    let len = self.len();
+    match self.kind() {
+      Self::SMALL => {
+        assert!(
+          len <= Self::SSO_LEN,
+          "SMALL yarn has length {len}, but SSO_LEN is {}",
+          Self::SSO_LEN
+        );
+        let Layout::Small(small) = self.layout() else {
+          unreachable!()
+        };
+        assert!(
+          small.data[len..].iter().all(|&b| b == 0),
+          "SMALL yarn has nonzero bytes past its contents"
+        );
+      }
+      kind => {
+        assert!(!self.ptr.is_null(), "non-SMALL yarn has a null pointer");
+        assert!(
+          len <= Self::MAX_LEN,
+          "yarn has length {len}, but MAX_LEN is {}",
+          Self::MAX_LEN
+        );
+        assert!(kind != Self::HEAP || len > 0, "HEAP yarn is empty");
+      }
+    }
+
+    if utf8 {
+      if let Err(e) = std::str::from_utf8(self.as_slice()) {
+        panic!("yarn contains invalid UTF-8: {e}");
+      }
+    }
+  }
+
   /// Frees heap memory owned by this raw yarn.
   ///
   /// # Safety
@@ -243,6 +445,24 @@ impl RawYarn {
     alloc::dealloc(self.ptr as *mut u8, layout)
   }
 
+  /// Overwrites the bytes owned by this raw yarn with zeros, without changing
+  /// its length or kind.
+  ///
+  /// Yarns that do not own their bytes (i.e., `STATIC` or `ALIASED` yarns) are
+  /// left untouched; this function returns whether any bytes were scrubbed.
+  #[cfg(feature = "zeroize")]
+  pub fn zeroize(&mut self) -> bool {
+    if !self.is_small() && !self.on_heap() {
+      return false;
+    }
+
+    unsafe {
+      // SAFETY: We just checked that this is a SMALL or HEAP yarn.
+      zeroize::Zeroize::zeroize(self.as_mut_slice());
+    }
+    true
+  }
+
   /// Returns a pointer into the data for this raw yarn.
   #[inline(always)]
   pub const fn as_ptr(&self) -> *const u8 {
@@ -284,11 +504,25 @@ impl RawYarn {
     }
   }
 
+  /// Counts this yarn as newly constructed, if statistics are enabled.
+  ///
+  /// This is called by every constructor that is not a `const fn`, on the
+  /// yarn it returns; `const fn`s cannot update the counters.
+  #[inline(always)]
+  fn counted(self) -> Self {
+    #[cfg(feature = "stats")]
+    crate::stats::record_new(self.kind());
+    self
+  }
+
   /// Returns a `RawYarn` by making a copy of the given slice.
   #[inline(always)]
   pub fn copy_slice(s: &[u8]) -> Self {
+    #[cfg(feature = "stats")]
+    crate::stats::record_copy(s.len());
+
     match Self::from_slice_inlined(s) {
-      Some(inl) => inl,
+      Some(inl) => inl.counted(),
       None => Self::from_heap(s.into()),
     }
   }
@@ -327,8 +561,8 @@ impl RawYarn {
 
     let tagged_len = (len as u8) | Self::SMALL << Self::SHIFT8;
 
-    // Specialization for 64-bit architectures.
-    if mem::size_of::<Self>() == 16 {
+    // Specialization for 64-bit little-endian architectures.
+    if mem::size_of::<Self>() == 16 && cfg!(target_endian = "little") {
       // Do binary search on the length of the buffer to construct the shortest
       // instruction sequence for reading `len` little-endian bytes into
       // `register`, with all higher bytes zeroed.
@@ -342,7 +576,7 @@ impl RawYarn {
         // This reads between 9 and 15 distinct bytes, total.
         let x0 = ptr.cast::<u64>().read_unaligned() as u128;
         let x1 = ptr.add(len - 8).cast::<u64>().read_unaligned() as u128;
-        x0 | (x1 << (len - 8) * 8)
+        x0 | (x1 << ((len - 8) * 8))
       } else if len > 3 {
         // SAFETY: This reads the low four bytes of the buffer and the high
         // four bytes, which possibly overlap, and then ors them together.
@@ -350,7 +584,7 @@ impl RawYarn {
         // This reads between 4 and 8 distinct bytes, total.
         let x0 = ptr.cast::<u32>().read_unaligned() as u128;
         let x1 = ptr.add(len - 4).cast::<u32>().read_unaligned() as u128;
-        x0 | (x1 << (len - 4) * 8)
+        x0 | (x1 << ((len - 4) * 8))
       } else if len > 0 {
         // SAFETY: This code runs when len is 1, 2, or 3, in which case these
         // three points are, respectively:
@@ -458,63 +692,169 @@ impl RawYarn {
       cursor += b.len();
     }
 
-    Self::from_slice_inlined(&data[..cursor]).unwrap_unchecked()
+    Self::from_slice_inlined(&data[..cursor])
+      .unwrap_unchecked()
+      .counted()
   }
 
   /// Returns a `RawYarn` by taking ownership of the given allocation.
   #[inline]
   pub fn from_heap(s: Box<[u8]>) -> Self {
     if let Some(inline) = Self::from_slice_inlined(&s) {
-      return inline;
+      return inline.counted();
     }
 
     let len = s.len();
+    #[cfg(feature = "stats")]
+    crate::stats::record_heap(len);
+
     let ptr = Box::into_raw(s) as *mut u8;
     unsafe {
       // SAFETY: s is a heap allocation of the appropriate layout for HEAP,
       // which we own uniquely because we dismantled it from a box.
-      Self::from_ptr_len_tag(ptr, len, Self::HEAP)
+      Self::from_ptr_len_tag(ptr, len, Self::HEAP).counted()
+    }
+  }
+
+  /// Returns a `RawYarn` with the same contents as this one, which is never
+  /// `SMALL`, so that its data pointer does not change when it is moved.
+  ///
+  /// `SMALL` yarns are copied to the heap (empty ones become `STATIC`); all
+  /// other yarns are returned as-is.
+  pub fn out_of_line(self) -> Self {
+    if !self.is_small() {
+      return self;
+    }
+
+    if self.len() == 0 {
+      unsafe {
+        // SAFETY: The empty string literal is static.
+        return Self::from_ptr_len_tag(b"".as_ptr(), 0, Self::STATIC).counted();
+      }
+    }
+
+    let len = self.len();
+    #[cfg(feature = "stats")]
+    {
+      crate::stats::record_copy(len);
+      crate::stats::record_heap(len);
+    }
+
+    let ptr = Box::into_raw(Box::<[u8]>::from(self.as_slice())) as *mut u8;
+    unsafe {
+      // SAFETY: This is a non-empty heap allocation which we own uniquely, as in
+      // from_heap(). It is fine for a HEAP yarn to be short enough to inline.
+      Self::from_ptr_len_tag(ptr, len, Self::HEAP).counted()
     }
   }
 
   /// Builds a new yarn from the given formatting arguments, without allocating
   /// in the trival and small cases.
+  ///
+  /// If formatting fails, returns whatever was written before it failed.
   pub fn from_fmt_args(args: fmt::Arguments) -> Self {
+    Self::write_fmt_args(args, usize::MAX).0
+  }
+
+  /// Like `from_fmt_args()`, but returns an error if formatting fails, or
+  /// would produce a yarn longer than `MAX_LEN`.
+  ///
+  /// Formatting stops as soon as the output becomes too long, so this does not
+  /// allocate more than `MAX_LEN` bytes.
+  pub fn try_from_fmt_args(args: fmt::Arguments) -> Result<Self, FmtError> {
+    let (yarn, result) = Self::write_fmt_args(args, Self::MAX_LEN);
+    result.map(|()| yarn)
+  }
+
+  fn write_fmt_args(
+    args: fmt::Arguments,
+    max: usize,
+  ) -> (Self, Result<(), FmtError>) {
     if let Some(constant) = args.as_str() {
-      return Self::new(constant.as_bytes());
+      return (Self::new(constant.as_bytes()).counted(), Ok(()));
     }
 
     enum Buf {
       Sso(usize, [u8; RawYarn::SSO_LEN]),
       Vec(Vec<u8>),
     }
-    impl fmt::Write for Buf {
+    struct Writer {
+      buf: Buf,
+      max: usize,
+      too_long: Option<TooLong>,
+    }
+    impl fmt::Write for Writer {
       fn write_str(&mut self, s: &str) -> fmt::Result {
-        match self {
-          Self::Sso(len, bytes) => {
-            let new_len = *len + s.len();
+        let len = match &self.buf {
+          Buf::Sso(len, _) => *len,
+          Buf::Vec(vec) => vec.len(),
+        };
+        let new_len = len.saturating_add(s.len());
+        if let Err(e) = TooLong::check(new_len, self.max) {
+          self.too_long = Some(e);
+          return Err(fmt::Error);
+        }
+
+        match &mut self.buf {
+          Buf::Sso(len, bytes) => {
             if new_len > RawYarn::SSO_LEN {
               let mut vec = Vec::from(&bytes[..*len]);
               vec.extend_from_slice(s.as_bytes());
 
-              *self = Self::Vec(vec);
+              self.buf = Buf::Vec(vec);
             } else {
               let _ = &bytes[*len..new_len].copy_from_slice(s.as_bytes());
               *len = new_len;
             }
           }
-          Self::Vec(vec) => vec.extend_from_slice(s.as_bytes()),
+          Buf::Vec(vec) => vec.extend_from_slice(s.as_bytes()),
         }
 
         Ok(())
       }
     }
 
-    let mut w = Buf::Sso(0, [0; RawYarn::SSO_LEN]);
-    let _ = w.write_fmt(args);
-    match w {
-      Buf::Sso(len, bytes) => Self::from_slice_inlined(&bytes[..len]).unwrap(),
+    let mut w = Writer {
+      buf: Buf::Sso(0, [0; RawYarn::SSO_LEN]),
+      max,
+      too_long: None,
+    };
+    let result = w.write_fmt(args).map_err(|e| match w.too_long {
+      Some(too_long) => FmtError::TooLong(too_long),
+      None => FmtError::Fmt(e),
+    });
+    let yarn = match w.buf {
+      Buf::Sso(len, bytes) => {
+        Self::from_slice_inlined(&bytes[..len]).unwrap().counted()
+      }
       Buf::Vec(vec) => Self::from_heap(vec.into()),
+    };
+    (yarn, result)
+  }
+}
+
+impl fmt::Debug for RawYarn {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let kind = match self.kind() {
+      Self::SMALL => "SMALL",
+      Self::STATIC => "STATIC",
+      Self::HEAP => "HEAP",
+      _ => "ALIASED",
+    };
+
+    let mut d = f.debug_struct("RawYarn");
+    d.field("kind", &format_args!("{kind}"));
+    d.field("len", &self.len());
+    match self.layout() {
+      Layout::Small(s) => {
+        d.field("data", &format_args!("{:02x?}", s.data));
+        d.field("len_byte", &format_args!("{:#04x}", s.len));
+      }
+      Layout::Slice(s) => {
+        d.field("ptr", &s.ptr);
+        d.field("len_word", &format_args!("{:#x}", s.len));
+      }
     }
+    d.finish()
   }
 }
@@ -0,0 +1,173 @@
+//! Integration with the `rayon` crate.
+
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
+use rayon::slice::ParallelSlice;
+
+use crate::Utf8Chunks;
+use crate::YarnRef;
+
+/// The smallest piece of a buffer that `par_utf8_chunks()` hands to a thread.
+const MIN_PIECE: usize = 64 * 1024;
+
+/// A chunk yielded by `par_utf8_chunks()`: valid UTF-8, or a run of invalid
+/// bytes.
+type Utf8Chunk<'a> = Result<YarnRef<'a, str>, YarnRef<'a, [u8]>>;
+
+impl<'a, Buf> YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + Sync + ?Sized,
+{
+  /// Returns a parallel iterator over the lines of this yarn.
+  ///
+  /// Lines are split as by [`str::lines()`]: they end in `\n` or `\r\n`, and
+  /// the final line break is optional. The lines are subyarns of this one, so
+  /// they do not copy unless this yarn is inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use rayon::prelude::*;
+  ///
+  /// let yarn = YarnRef::<str>::new("one\ntwo\r\n\nthree\n");
+  /// let lines = yarn.par_lines().collect::<Vec<_>>();
+  /// assert_eq!(lines, ["one", "two", "", "three"]);
+  /// ```
+  pub fn par_lines(
+    &self,
+  ) -> impl ParallelIterator<Item = YarnRef<'a, Buf>> + '_ {
+    let bytes = self.as_bytes();
+    let body = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    self.par_split_slice(body, b'\n').filter_map(move |line| {
+      if bytes.is_empty() {
+        return None;
+      }
+
+      let end = match line.as_bytes() {
+        [.., b'\r'] => line.len() - 1,
+        _ => line.len(),
+      };
+      unsafe {
+        // SAFETY: Removing a trailing ASCII byte keeps us on a character
+        // boundary.
+        Some(line.slice_unchecked(0..end))
+      }
+    })
+  }
+
+  /// Returns a parallel iterator over the pieces of this yarn separated by
+  /// `sep`.
+  ///
+  /// The pieces are subyarns of this one, so they do not copy unless this
+  /// yarn is inlined.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `Buf` is `str` and `sep` is not ASCII, since splitting on it
+  /// could produce invalid UTF-8.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use rayon::prelude::*;
+  ///
+  /// let yarn = YarnRef::<[u8]>::new(b"a,b,,c");
+  /// let pieces = yarn.par_split(b',').collect::<Vec<_>>();
+  /// assert_eq!(pieces, [&b"a"[..], b"b", b"", b"c"]);
+  /// ```
+  pub fn par_split(
+    &self,
+    sep: u8,
+  ) -> impl ParallelIterator<Item = YarnRef<'a, Buf>> + '_ {
+    assert!(
+      !<Buf as crate::z::Sealed>::UTF8 || sep.is_ascii(),
+      "cannot split a str yarn on the non-ASCII byte {sep:#04x}"
+    );
+    self.par_split_slice(self.as_bytes(), sep)
+  }
+
+  /// Returns a parallel iterator over the UTF-8 (or otherwise) chunks in this
+  /// yarn.
+  ///
+  /// This yields the same bytes as [`YarnRef::utf8_chunks()`], but a valid
+  /// run of UTF-8 may be broken up into several chunks, where the yarn was
+  /// divided between threads. The chunks are subyarns of this one, so they do
+  /// not copy unless this yarn is inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use rayon::prelude::*;
+  ///
+  /// let yarn = YarnRef::<[u8]>::new(b"abc\xFF\xF0\x9F\x90\x88!");
+  /// let chunks = yarn.par_utf8_chunks().collect::<Vec<_>>();
+  /// assert_eq!(chunks, [
+  ///   Ok("abc".into()),
+  ///   Err(YarnRef::from_byte(0xff)),
+  ///   Ok("🐈!".into()),
+  /// ]);
+  ///
+  /// let text = "text that is long enough to not be inlined";
+  /// let yarn = YarnRef::<[u8]>::from_static(text.as_bytes());
+  /// let chunk = yarn.par_utf8_chunks().find_any(|_| true).unwrap().unwrap();
+  /// assert_eq!(chunk.kind(), YarnKind::Static);
+  /// ```
+  pub fn par_utf8_chunks(
+    &self,
+  ) -> impl ParallelIterator<Item = Utf8Chunk<'a>> + '_ {
+    let piece_len = (self.len() / rayon::current_num_threads()).max(MIN_PIECE);
+
+    let mut pieces = Vec::new();
+    let mut rest = self.as_bytes();
+    while !rest.is_empty() {
+      // Never split just before a continuation byte, so that no valid UTF-8
+      // sequence is cut in two.
+      let mut mid = piece_len.min(rest.len());
+      while rest.get(mid).is_some_and(|&b| b & 0xc0 == 0x80) {
+        mid += 1;
+      }
+
+      let (piece, next) = rest.split_at(mid);
+      pieces.push(piece);
+      rest = next;
+    }
+
+    pieces.into_par_iter().flat_map_iter(move |piece| {
+      Utf8Chunks::new(piece).map(move |chunk| {
+        let bytes = match chunk {
+          Ok(s) => s.as_bytes(),
+          Err(b) => b,
+        };
+
+        unsafe {
+          // SAFETY: Every chunk is a subslice of this yarn's bytes. Ok chunks
+          // are valid UTF-8.
+          let start =
+            bytes.as_ptr().offset_from(self.as_bytes().as_ptr()) as usize;
+          let yarn = self
+            .into_bytes()
+            .slice_unchecked(start..start + bytes.len());
+          match chunk {
+            Ok(_) => Ok(yarn.to_utf8_unchecked()),
+            Err(_) => Err(yarn),
+          }
+        }
+      })
+    })
+  }
+
+  /// Splits `bytes`, which must be a subslice of this yarn's bytes, on `sep`,
+  /// and converts each piece into a subyarn.
+  fn par_split_slice<'b>(
+    &'b self,
+    bytes: &'b [u8],
+    sep: u8,
+  ) -> impl ParallelIterator<Item = YarnRef<'a, Buf>> + 'b {
+    bytes
+      .par_split(move |&b| b == sep)
+      .map(move |piece| unsafe {
+        // SAFETY: piece is a subslice of this yarn's bytes, and splitting on an
+        // ASCII byte keeps us on character boundaries.
+        let start =
+          piece.as_ptr().offset_from(self.as_bytes().as_ptr()) as usize;
+        self.slice_unchecked(start..start + piece.len())
+      })
+  }
+}
@@ -0,0 +1,92 @@
+//! Integration with the `redis` crate.
+//!
+//! Yarns can be passed as command arguments, and parsed out of replies.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use redis::Value;
+//!
+//! let key = yarn!("session:{}", 42);
+//! let cmd = redis::cmd("GET").arg(&key).get_packed_command();
+//! assert_eq!(cmd, b"*2\r\n$3\r\nGET\r\n$10\r\nsession:42\r\n");
+//!
+//! let reply = Value::Data(b"logged in".to_vec());
+//! let value: Yarn = redis::from_redis_value(&reply).unwrap();
+//! assert!(value.is_inlined());
+//! assert_eq!(value, "logged in");
+//!
+//! let reply = Value::Data(b"\xff\xfe".to_vec());
+//! assert!(redis::from_redis_value::<Yarn>(&reply).is_err());
+//! let value: ByteYarn = redis::from_redis_value(&reply).unwrap();
+//! assert_eq!(value, [0xff, 0xfe]);
+//! ```
+//!
+//! Replies are only ever borrowed by [`FromRedisValue`], so yarns parsed out
+//! of them are copies, unless they are short enough to inline.
+
+use redis::ErrorKind;
+use redis::FromRedisValue;
+use redis::RedisError;
+use redis::RedisResult;
+use redis::RedisWrite;
+use redis::ToRedisArgs;
+use redis::Value;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> ToRedisArgs for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn write_redis_args<W>(&self, out: &mut W)
+  where
+    W: ?Sized + RedisWrite,
+  {
+    out.write_arg(self.as_bytes())
+  }
+}
+
+impl<Buf> ToRedisArgs for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn write_redis_args<W>(&self, out: &mut W)
+  where
+    W: ?Sized + RedisWrite,
+  {
+    out.write_arg(self.as_bytes())
+  }
+}
+
+impl<Buf> FromRedisValue for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Parses a bulk string or status reply, in the same way as `String` or
+  /// `Vec<u8>` would be.
+  fn from_redis_value(v: &Value) -> RedisResult<Self> {
+    let bytes = match v {
+      Value::Data(bytes) => bytes.as_slice(),
+      Value::Status(status) => status.as_bytes(),
+      Value::Okay => b"OK",
+      _ => {
+        return Err(RedisError::from((
+          ErrorKind::TypeError,
+          "Response was of incompatible type",
+          format!("{v:?} is not a string"),
+        )))
+      }
+    };
+
+    if <Buf as crate::z::Sealed>::UTF8 {
+      std::str::from_utf8(bytes)?;
+    }
+
+    Ok(unsafe {
+      // SAFETY: bytes was validated as UTF-8 above, if Buf requires it.
+      YarnBox::from_raw(RawYarn::copy_slice(bytes))
+    })
+  }
+}
@@ -1,17 +1,27 @@
 use std::cmp::Ordering;
 use std::fmt;
-use std::fmt::Write;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
+use std::ops::Range;
+use std::rc::Rc;
 use std::str;
 use std::str::Utf8Error;
+use std::sync::Arc;
 
+use crate::elements::Decode;
 use crate::raw::RawYarn;
+use crate::utf8;
+use crate::CharIndex;
+use crate::CharIndicesLossy;
+use crate::CharsLossy;
+use crate::HexDump;
+use crate::TooLong;
 use crate::Utf8Chunks;
 use crate::YarnBox;
+use crate::YarnKind;
 
 #[cfg(doc)]
 use crate::*;
@@ -24,6 +34,9 @@ use crate::*;
 /// to be [`Copy`]. [`YarnRef`] is very similar to [`Yarn`], although it can't
 /// provide full functionality because it can't own a heap allocation.
 ///
+/// Like a [`Yarn`], a `YarnRef` hashes exactly like its contents as a `&Buf`;
+/// see [`YarnBox`'s docs](YarnBox#hashing).
+///
 /// See the [crate documentation](crate) for general information.
 #[repr(transparent)]
 pub struct YarnRef<'a, Buf>
@@ -59,6 +72,74 @@ where
     }
   }
 
+  /// Returns a yarn over `range` of this yarn's bytes.
+  ///
+  /// This does not copy, unless this yarn is inlined (in which case the result
+  /// is also inlined). Static yarns produce static subyarns.
+  ///
+  /// # Safety
+  ///
+  /// `range` must be in bounds and, if `Buf` is `str`, must fall on character
+  /// boundaries.
+  pub(crate) unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+    debug_assert!(range.start <= range.end && range.end <= self.len());
+    let bytes = self.as_bytes().get_unchecked(range);
+    let raw = match self.raw.kind() {
+      // SAFETY: A subslice of an inlined yarn is short enough to inline.
+      RawYarn::SMALL => {
+        RawYarn::from_slice_inlined_unchecked(bytes.as_ptr(), bytes.len())
+      }
+      // SAFETY: A STATIC yarn points into immortal memory, so the subslice does
+      // too.
+      RawYarn::STATIC => RawYarn::new(&*(bytes as *const [u8])),
+      // SAFETY: An ALIASED yarn points into memory that lives for 'a.
+      _ => RawYarn::alias_slice(bytes),
+    };
+
+    Self::from_raw(raw)
+  }
+
+  /// Returns this yarn's contents as a static slice, if it points into static
+  /// memory.
+  ///
+  /// Inlined yarns are immortal, but their contents live inside the yarn, so
+  /// they do not count.
+  #[cfg_attr(
+    not(any(
+      feature = "bytes",
+      feature = "compact_str",
+      feature = "smol_str"
+    )),
+    allow(dead_code)
+  )]
+  pub(crate) fn as_static_slice(self) -> Option<&'static Buf> {
+    if self.raw.kind() != RawYarn::STATIC {
+      return None;
+    }
+
+    unsafe {
+      // SAFETY: A STATIC yarn's data pointer is never deallocated.
+      let bytes = &*(self.as_bytes() as *const [u8]);
+      Some(Self::raw2buf(bytes))
+    }
+  }
+
+  /// Returns this yarn's contents as a slice that lives as long as the yarn
+  /// does, if it is not inlined.
+  #[cfg_attr(not(feature = "sqlx"), allow(dead_code))]
+  pub(crate) fn as_borrowed_slice(self) -> Option<&'a Buf> {
+    if self.is_inlined() {
+      return None;
+    }
+
+    unsafe {
+      // SAFETY: A yarn that is not inlined points to memory that lives for at
+      // least 'a.
+      let bytes = &*(self.as_bytes() as *const [u8]);
+      Some(Self::raw2buf(bytes))
+    }
+  }
+
   /// Returns a reference to an empty yarn of any lifetime.
   ///
   /// ```
@@ -95,6 +176,21 @@ where
     }
   }
 
+  /// Like [`YarnRef::new()`], but returns an error instead of panicking if
+  /// `buf` is too long to be represented by a yarn.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let foo = YarnRef::try_new("Byzantium").unwrap();
+  /// assert_eq!(foo.len(), 9);
+  /// ```
+  pub const fn try_new(buf: &'a Buf) -> Result<Self, TooLong> {
+    if let Err(e) = TooLong::check(Self::buf2raw(buf).len(), RawYarn::MAX_LEN) {
+      return Err(e);
+    }
+    Ok(Self::new(buf))
+  }
+
   /// Returns a new yarn containing the contents of the given slice.
   ///
   /// This function will always return an inlined string, or `None` if the
@@ -150,6 +246,51 @@ where
     self.raw.len()
   }
 
+  /// Returns the storage class this yarn is currently using.
+  ///
+  /// A `YarnRef` never owns a heap allocation, so this is never
+  /// [`YarnKind::Heap`].
+  pub const fn kind(self) -> YarnKind {
+    YarnKind::of(self.raw)
+  }
+
+  /// Checks whether this yarn's contents are stored inline, without pointing
+  /// to a separate buffer.
+  pub const fn is_inlined(self) -> bool {
+    self.raw.is_small()
+  }
+
+  /// Returns the alignment of this yarn's contents.
+  ///
+  /// See [`YarnBox::align()`].
+  pub fn align(self) -> usize {
+    // This uses align_offset() rather than casting the pointer to an integer,
+    // so that it does not expose the pointer's provenance.
+    let ptr = self.as_bytes().as_ptr();
+    let mut align = 1;
+    while align < 1 << (usize::BITS - 1) && ptr.align_offset(align * 2) == 0 {
+      align *= 2;
+    }
+    align
+  }
+
+  /// Checks that this yarn's internal representation is consistent, panicking
+  /// if it is not.
+  ///
+  /// See [`YarnBox::assert_invariants()`].
+  #[track_caller]
+  pub fn assert_invariants(self) {
+    assert!(!self.raw.on_heap(), "YarnRef owns a HEAP allocation");
+    self.raw.assert_invariants(<Buf as crate::z::Sealed>::UTF8)
+  }
+
+  /// Returns a value whose `Debug` impl dumps this yarn's raw representation.
+  ///
+  /// See [`YarnBox::debug_raw()`].
+  pub fn debug_raw(self) -> impl fmt::Debug {
+    self.raw
+  }
+
   /// Converts this yarn into a slice.
   pub const fn as_slice(&self) -> &Buf {
     unsafe { Self::raw2buf(self.as_bytes()) }
@@ -182,6 +323,27 @@ where
     self.to_box().into_vec()
   }
 
+  /// Converts this yarn into an [`Arc`] by copying it.
+  pub fn to_arc(self) -> Arc<Buf> {
+    let arc = Arc::<[u8]>::from(self.as_bytes());
+    unsafe {
+      // SAFETY: The safety rules of `Buf` make the pointer cast valid, and our
+      // bytes came out of a Buf.
+      let ptr = Arc::into_raw(arc);
+      Arc::from_raw(*(&ptr as *const *const [u8] as *const *const Buf))
+    }
+  }
+
+  /// Converts this yarn into an [`Rc`] by copying it.
+  pub fn to_rc(self) -> Rc<Buf> {
+    let rc = Rc::<[u8]>::from(self.as_bytes());
+    unsafe {
+      // SAFETY: Ditto.
+      let ptr = Rc::into_raw(rc);
+      Rc::from_raw(*(&ptr as *const *const [u8] as *const *const Buf))
+    }
+  }
+
   /// Converts this yarn into a byte yarn.
   pub const fn into_bytes(self) -> YarnRef<'a, [u8]> {
     unsafe {
@@ -253,6 +415,33 @@ where
   pub fn utf8_chunks(&self) -> Utf8Chunks {
     Utf8Chunks::new(self.as_bytes())
   }
+
+  /// Returns an adaptor for formatting this yarn as a hex dump.
+  ///
+  /// See [`YarnBox::hex_dump()`].
+  pub fn hex_dump(&self) -> HexDump {
+    HexDump::new(self.as_bytes())
+  }
+
+  /// Checks whether this yarn starts with `prefix`, ignoring ASCII case.
+  ///
+  /// See [`YarnBox::starts_with_ignore_ascii_case()`].
+  pub fn starts_with_ignore_ascii_case(self, prefix: impl AsRef<Buf>) -> bool {
+    let prefix = Self::buf2raw(prefix.as_ref());
+    let bytes = self.as_bytes();
+    bytes.len() >= prefix.len()
+      && bytes[..prefix.len()].eq_ignore_ascii_case(prefix)
+  }
+
+  /// Checks whether this yarn ends with `suffix`, ignoring ASCII case.
+  ///
+  /// See [`YarnBox::ends_with_ignore_ascii_case()`].
+  pub fn ends_with_ignore_ascii_case(self, suffix: impl AsRef<Buf>) -> bool {
+    let suffix = Self::buf2raw(suffix.as_ref());
+    let bytes = self.as_bytes();
+    bytes.len() >= suffix.len()
+      && bytes[bytes.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+  }
 }
 
 impl<Buf> YarnRef<'static, Buf>
@@ -269,6 +458,15 @@ where
     let raw = RawYarn::new(Self::buf2raw(buf));
     unsafe { Self::from_raw(raw) }
   }
+
+  /// Like [`YarnRef::from_static()`], but returns an error instead of
+  /// panicking if `buf` is too long to be represented by a yarn.
+  pub const fn try_from_static(buf: &'static Buf) -> Result<Self, TooLong> {
+    if let Err(e) = TooLong::check(Self::buf2raw(buf).len(), RawYarn::MAX_LEN) {
+      return Err(e);
+    }
+    Ok(Self::from_static(buf))
+  }
 }
 
 impl<'a> YarnRef<'a, [u8]> {
@@ -291,7 +489,33 @@ impl<'a> YarnRef<'a, [u8]> {
   /// ```
   pub fn to_utf8(self) -> Result<YarnRef<'a, str>, Utf8Error> {
     str::from_utf8(self.as_bytes())?;
-    unsafe { Ok(YarnRef::from_raw(self.raw)) }
+    unsafe { Ok(self.to_utf8_unchecked()) }
+  }
+
+  /// Converts this yarn into a UTF-8 yarn without checking that it is valid.
+  ///
+  /// # Safety
+  ///
+  /// This yarn must contain valid UTF-8.
+  pub(crate) unsafe fn to_utf8_unchecked(self) -> YarnRef<'a, str> {
+    YarnRef::from_raw(self.raw)
+  }
+
+  /// Returns an iterator over the `char`s in this yarn, with each invalid
+  /// UTF-8 sequence replaced by U+FFFD REPLACEMENT CHARACTER.
+  ///
+  /// See [`YarnBox::chars_lossy()`].
+  pub fn chars_lossy(&self) -> CharsLossy {
+    CharsLossy::new(self.as_bytes())
+  }
+
+  /// Returns an iterator over the `char`s in this yarn and their byte offsets
+  /// and lengths, with each invalid UTF-8 sequence replaced by U+FFFD
+  /// REPLACEMENT CHARACTER.
+  ///
+  /// See [`YarnBox::char_indices_lossy()`].
+  pub fn char_indices_lossy(&self) -> CharIndicesLossy {
+    CharIndicesLossy::new(self.as_bytes())
   }
 }
 
@@ -301,6 +525,36 @@ impl<'a> YarnRef<'a, str> {
     self.as_slice()
   }
 
+  /// Returns the number of `char`s in this yarn.
+  ///
+  /// See [`YarnBox::char_count()`].
+  pub fn char_count(self) -> usize {
+    utf8::count_chars(self.as_bytes())
+  }
+
+  /// Returns the byte offset of the `n`th character of this yarn.
+  ///
+  /// See [`YarnBox::char_to_byte_index()`].
+  pub fn char_to_byte_index(self, n: usize) -> Option<usize> {
+    <str as Decode>::advance(self.as_str(), 0, n)
+  }
+
+  /// Returns the number of characters in this yarn before byte offset `idx`.
+  ///
+  /// See [`YarnBox::byte_to_char_index()`].
+  pub fn byte_to_char_index(self, idx: usize) -> Option<usize> {
+    let prefix = self.as_str().get(..idx)?;
+    Some(utf8::count_chars(prefix.as_bytes()))
+  }
+
+  /// Builds an index for quickly translating between byte offsets and
+  /// character offsets in this yarn.
+  ///
+  /// See [`CharIndex`].
+  pub fn char_index(self) -> CharIndex<'a> {
+    CharIndex::new(self)
+  }
+
   /// Converts this yarn into a boxed slice by copying it.
   pub fn to_boxed_str(self) -> Box<str> {
     self.to_box().into_boxed_str()
@@ -338,31 +592,13 @@ where
 
 impl<Buf: crate::Buf + ?Sized> fmt::Debug for YarnRef<'_, Buf> {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f, "\"")?;
-    for chunk in self.utf8_chunks() {
-      match chunk {
-        Ok(utf8) => write!(f, "{}", utf8.escape_debug())?,
-        Err(bytes) => {
-          for b in bytes {
-            write!(f, "\\x{:02X}", b)?;
-          }
-        }
-      }
-    }
-    write!(f, "\"")
+    utf8::debug(self.as_bytes(), f)
   }
 }
 
 impl<Buf: crate::Buf + ?Sized> fmt::Display for YarnRef<'_, Buf> {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    for chunk in self.utf8_chunks() {
-      match chunk {
-        Ok(utf8) => f.write_str(utf8)?,
-        Err(..) => f.write_char(char::REPLACEMENT_CHARACTER)?,
-      }
-    }
-
-    Ok(())
+    utf8::display(self.as_bytes(), f)
   }
 }
 
@@ -372,7 +608,7 @@ where
   Slice: AsRef<Buf> + ?Sized,
 {
   fn eq(&self, that: &Slice) -> bool {
-    self.as_slice() == that.as_ref()
+    self.raw.eq_slice(Self::buf2raw(that.as_ref()))
   }
 }
 
@@ -384,17 +620,18 @@ where
   Slice: AsRef<Buf> + ?Sized,
 {
   fn partial_cmp(&self, that: &Slice) -> Option<Ordering> {
-    self.as_slice().partial_cmp(that.as_ref())
+    Some(self.raw.cmp_slice(Self::buf2raw(that.as_ref())))
   }
 }
 
 impl<Buf: crate::Buf + ?Sized> Ord for YarnRef<'_, Buf> {
   fn cmp(&self, that: &Self) -> Ordering {
-    self.as_slice().cmp(that.as_slice())
+    self.raw.cmp_bytes(that.raw)
   }
 }
 
 impl<Buf: crate::Buf + ?Sized> Hash for YarnRef<'_, Buf> {
+  // NOTE: This must hash exactly like Buf; see "Hashing" in YarnBox's docs.
   fn hash<H: Hasher>(&self, state: &mut H) {
     self.as_slice().hash(state)
   }
@@ -0,0 +1,193 @@
+//! Integration with the `regex` crate.
+
+use std::ops::Range;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+mod z {
+  use std::ops::Range;
+
+  /// A buffer type that some flavor of `regex` can search.
+  pub trait Haystack: crate::Buf {
+    /// The regex type that searches this buffer type.
+    type Regex;
+
+    fn captures(
+      re: &Self::Regex,
+      hay: &Self,
+    ) -> Option<Vec<Option<Range<usize>>>>;
+    fn split(re: &Self::Regex, hay: &Self) -> Vec<Range<usize>>;
+  }
+
+  impl Haystack for str {
+    type Regex = regex::Regex;
+
+    fn captures(
+      re: &Self::Regex,
+      hay: &Self,
+    ) -> Option<Vec<Option<Range<usize>>>> {
+      let caps = re.captures(hay)?;
+      Some(caps.iter().map(|m| m.map(|m| m.range())).collect())
+    }
+
+    fn split(re: &Self::Regex, hay: &Self) -> Vec<Range<usize>> {
+      re.split(hay)
+        .map(|s| {
+          // SAFETY: Every piece of the split is a subslice of hay.
+          let start = unsafe { s.as_ptr().offset_from(hay.as_ptr()) } as usize;
+          start..start + s.len()
+        })
+        .collect()
+    }
+  }
+
+  impl Haystack for [u8] {
+    type Regex = regex::bytes::Regex;
+
+    fn captures(
+      re: &Self::Regex,
+      hay: &Self,
+    ) -> Option<Vec<Option<Range<usize>>>> {
+      let caps = re.captures(hay)?;
+      Some(caps.iter().map(|m| m.map(|m| m.range())).collect())
+    }
+
+    fn split(re: &Self::Regex, hay: &Self) -> Vec<Range<usize>> {
+      re.split(hay)
+        .map(|s| {
+          // SAFETY: Every piece of the split is a subslice of hay.
+          let start = unsafe { s.as_ptr().offset_from(hay.as_ptr()) } as usize;
+          start..start + s.len()
+        })
+        .collect()
+    }
+  }
+}
+
+use z::Haystack;
+
+impl<'a, Buf> YarnRef<'a, Buf>
+where
+  Buf: Haystack + ?Sized,
+{
+  /// Runs `re` over this yarn and returns its leftmost-first capture groups as
+  /// subyarns, without copying.
+  ///
+  /// The first element is the whole match; groups that did not participate in
+  /// the match are `None`. Returns `None` if `re` does not match at all.
+  ///
+  /// String yarns are searched with a [`regex::Regex`]; byte yarns are searched
+  /// with a [`regex::bytes::Regex`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use regex::Regex;
+  ///
+  /// let re = Regex::new(r"(\w+)@(\w+)(\.com)?").unwrap();
+  /// let yarn = YarnRef::new("mail: cat@yarnball oh no");
+  /// let caps = yarn.captures_as_yarns(&re).unwrap();
+  /// assert_eq!(caps, [
+  ///   Some(YarnRef::new("cat@yarnball")),
+  ///   Some(YarnRef::new("cat")),
+  ///   Some(YarnRef::new("yarnball")),
+  ///   None,
+  /// ]);
+  ///
+  /// assert!(YarnRef::new("no mail").captures_as_yarns(&re).is_none());
+  /// ```
+  pub fn captures_as_yarns(
+    self,
+    re: &Buf::Regex,
+  ) -> Option<Vec<Option<YarnRef<'a, Buf>>>> {
+    let caps = Buf::captures(re, self.as_slice())?;
+    Some(
+      caps
+        .into_iter()
+        .map(|range| range.map(|r| self.subyarn(r)))
+        .collect(),
+    )
+  }
+
+  /// Splits this yarn on matches of `re`, returning the pieces between matches
+  /// as subyarns, without copying.
+  ///
+  /// This has the same semantics as [`regex::Regex::split()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use regex::bytes::Regex;
+  ///
+  /// let re = Regex::new(r"[ \t]+").unwrap();
+  /// let yarn = ByteYarn::from_static(b"a  b \t c\td");
+  /// let pieces = yarn.as_ref().split_regex(&re).collect::<Vec<_>>();
+  /// assert_eq!(pieces, [b"a", b"b", b"c", b"d"]);
+  ///
+  /// // Pieces of a static yarn are static too.
+  /// assert!(pieces.iter().all(|y| y.immortalize().is_some()));
+  /// ```
+  pub fn split_regex(
+    self,
+    re: &Buf::Regex,
+  ) -> impl Iterator<Item = YarnRef<'a, Buf>> {
+    Buf::split(re, self.as_slice())
+      .into_iter()
+      .map(move |r| self.subyarn(r))
+  }
+
+  fn subyarn(self, range: Range<usize>) -> Self {
+    unsafe {
+      // SAFETY: The regex engine only produces in-bounds ranges, which are on
+      // character boundaries when searching a `str`.
+      self.slice_unchecked(range)
+    }
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: Haystack + ?Sized,
+{
+  /// Runs `re` over this yarn and returns its leftmost-first capture groups as
+  /// subyarns, without copying.
+  ///
+  /// See [`YarnRef::captures_as_yarns()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use regex::Regex;
+  ///
+  /// let re = Regex::new(r"(\d+)-(\d+)").unwrap();
+  /// let yarn = Yarn::from_string(String::from("pages 10-20"));
+  /// let caps = yarn.captures_as_yarns(&re).unwrap();
+  /// assert_eq!(caps[1].unwrap(), "10");
+  /// assert_eq!(caps[2].unwrap(), "20");
+  /// ```
+  pub fn captures_as_yarns(
+    &self,
+    re: &Buf::Regex,
+  ) -> Option<Vec<Option<YarnRef<Buf>>>> {
+    self.as_ref().captures_as_yarns(re)
+  }
+
+  /// Splits this yarn on matches of `re`, returning the pieces between matches
+  /// as subyarns, without copying.
+  ///
+  /// See [`YarnRef::split_regex()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use regex::Regex;
+  ///
+  /// let re = Regex::new(r",\s*").unwrap();
+  /// let yarn = yarn!("{}, {}, {}", "apples", "oranges", "pears");
+  /// let pieces = yarn.split_regex(&re).collect::<Vec<_>>();
+  /// assert_eq!(pieces, ["apples", "oranges", "pears"]);
+  /// ```
+  pub fn split_regex(
+    &self,
+    re: &Buf::Regex,
+  ) -> impl Iterator<Item = YarnRef<Buf>> {
+    self.as_ref().split_regex(re)
+  }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A string assembled from a sequence of yarns.
+///
+/// A rope is for building a large yarn out of many smaller ones, possibly out
+/// of order. Each yarn pushed or inserted into the rope is kept as a separate
+/// segment, so static and borrowed segments are not copied until the rope is
+/// [flattened][YarnRope::flatten], which copies everything exactly once.
+///
+/// Splitting a segment to insert into the middle of it copies the segment if
+/// it is on the heap, since two yarns cannot share one heap allocation.
+/// Static, borrowed, and inlined segments are split without copying.
+///
+/// Positions in a rope are byte offsets, as for a string slice.
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut rope = YarnRope::<str>::new();
+/// rope.push("world");
+/// rope.insert(0, "hello ");
+/// rope.push(yarn!("{}", '!'));
+///
+/// assert_eq!(rope.to_string(), "hello world!");
+/// assert_eq!(rope.flatten(), "hello world!");
+/// ```
+pub struct YarnRope<'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  segments: Vec<YarnBox<'a, Buf>>,
+  // The byte offset at which each segment ends.
+  ends: Vec<usize>,
+}
+
+impl<'a, Buf> YarnRope<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns a new, empty rope.
+  pub const fn new() -> Self {
+    Self {
+      segments: Vec::new(),
+      ends: Vec::new(),
+    }
+  }
+
+  /// Returns the length of this rope, in bytes.
+  pub fn len(&self) -> usize {
+    self.ends.last().copied().unwrap_or(0)
+  }
+
+  /// Checks whether this rope is empty.
+  pub fn is_empty(&self) -> bool {
+    self.segments.is_empty()
+  }
+
+  /// Returns an iterator over the segments of this rope.
+  ///
+  /// Empty yarns are never stored as segments.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut rope = YarnRope::<str>::new();
+  /// rope.push("fox");
+  /// rope.push("");
+  /// rope.insert(1, "ire");
+  ///
+  /// let segments = rope.segments().collect::<Vec<_>>();
+  /// assert_eq!(segments, ["f", "ire", "ox"]);
+  /// ```
+  pub fn segments(&self) -> impl Iterator<Item = YarnRef<Buf>> + '_ {
+    self.segments.iter().map(YarnBox::as_ref)
+  }
+
+  /// Appends `yarn` to the end of this rope.
+  pub fn push(&mut self, yarn: impl Into<YarnBox<'a, Buf>>) {
+    let yarn = yarn.into();
+    if yarn.is_empty() {
+      return;
+    }
+
+    self.ends.push(self.len() + yarn.len());
+    self.segments.push(yarn);
+  }
+
+  /// Inserts `yarn` into this rope at byte offset `idx`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `idx` is greater than the length of this rope or, if `Buf` is
+  /// `str`, if it does not fall on a character boundary.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut rope = YarnRope::<str>::new();
+  /// rope.push(Yarn::from_string("a heap-allocated yarn".into()));
+  /// rope.push("!");
+  /// rope.insert(21, "?");
+  /// rope.insert(2, "big, ");
+  ///
+  /// let segments = rope.segments().collect::<Vec<_>>();
+  /// assert_eq!(segments, ["a ", "big, ", "heap-allocated yarn", "?", "!"]);
+  /// ```
+  pub fn insert(&mut self, idx: usize, yarn: impl Into<YarnBox<'a, Buf>>) {
+    let yarn = yarn.into();
+    let len = self.len();
+    assert!(
+      idx <= len,
+      "index {idx} out of bounds for rope of length {len}"
+    );
+    if yarn.is_empty() {
+      return;
+    }
+    if idx == len {
+      return self.push(yarn);
+    }
+
+    let (seg, offset) = self.locate(idx);
+    let seg = if offset == 0 {
+      seg
+    } else {
+      let bytes = self.segments[seg].as_bytes();
+      assert!(
+        !<Buf as crate::z::Sealed>::UTF8 || (bytes[offset] as i8) >= -0x40,
+        "index {idx} is not on a character boundary"
+      );
+
+      let (head, tail) = split(&self.segments[seg], offset);
+      self.segments[seg] = head;
+      self.segments.insert(seg + 1, tail);
+      self.ends.insert(seg, idx);
+      seg + 1
+    };
+
+    let yarn_len = yarn.len();
+    self.segments.insert(seg, yarn);
+    self.ends.insert(seg, idx);
+    for end in &mut self.ends[seg..] {
+      *end += yarn_len;
+    }
+  }
+
+  /// Returns the byte at offset `idx` in this rope, if it is in bounds.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let rope = ["ab", "cd"].into_iter().collect::<YarnRope<str>>();
+  /// assert_eq!(rope.byte(2), Some(b'c'));
+  /// assert_eq!(rope.byte(4), None);
+  /// ```
+  pub fn byte(&self, idx: usize) -> Option<u8> {
+    if idx >= self.len() {
+      return None;
+    }
+
+    let (seg, offset) = self.locate(idx);
+    Some(self.segments[seg].as_bytes()[offset])
+  }
+
+  /// Concatenates the segments of this rope into a single yarn.
+  ///
+  /// If this rope has only one segment, it is returned as-is, without
+  /// copying.
+  pub fn flatten(self) -> YarnBox<'a, Buf> {
+    if self.segments.len() == 1 {
+      return self.segments.into_iter().next().unwrap();
+    }
+    YarnBox::concat(&self.segments)
+  }
+
+  /// Finds the segment containing byte offset `idx`, which must be in bounds,
+  /// and the offset of `idx` within it.
+  fn locate(&self, idx: usize) -> (usize, usize) {
+    let seg = self.ends.partition_point(|&end| end <= idx);
+    let start = seg.checked_sub(1).map_or(0, |prev| self.ends[prev]);
+    (seg, idx - start)
+  }
+}
+
+impl<'a> YarnRope<'a, str> {
+  /// Returns the character starting at byte offset `idx` in this rope, if it
+  /// is in bounds and on a character boundary.
+  ///
+  /// Segments are always valid UTF-8, so no character straddles two of them.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let rope = ["a", "λb"].into_iter().collect::<YarnRope<str>>();
+  /// assert_eq!(rope.char_at(1), Some('λ'));
+  /// assert_eq!(rope.char_at(2), None);
+  /// assert_eq!(rope.char_at(3), Some('b'));
+  /// ```
+  pub fn char_at(&self, idx: usize) -> Option<char> {
+    if idx >= self.len() {
+      return None;
+    }
+
+    let (seg, offset) = self.locate(idx);
+    self.segments[seg].as_str().get(offset..)?.chars().next()
+  }
+}
+
+/// Splits `yarn` at `offset`, which must be strictly inside of it and on a
+/// character boundary, if applicable.
+fn split<'a, Buf>(
+  yarn: &YarnBox<'a, Buf>,
+  offset: usize,
+) -> (YarnBox<'a, Buf>, YarnBox<'a, Buf>)
+where
+  Buf: crate::Buf + ?Sized,
+{
+  match yarn.to_ref() {
+    Some(yarn) => unsafe {
+      // SAFETY: The caller guarantees that offset is in bounds and on a
+      // character boundary.
+      (
+        yarn.slice_unchecked(0..offset).to_box(),
+        yarn.slice_unchecked(offset..yarn.len()).to_box(),
+      )
+    },
+    None => {
+      let (head, tail) = yarn.as_bytes().split_at(offset);
+      unsafe {
+        // SAFETY: Ditto.
+        (
+          YarnBox::from_raw(RawYarn::copy_slice(head)),
+          YarnBox::from_raw(RawYarn::copy_slice(tail)),
+        )
+      }
+    }
+  }
+}
+
+impl<'a, Buf, Y> FromIterator<Y> for YarnRope<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Y: Into<YarnBox<'a, Buf>>,
+{
+  fn from_iter<I: IntoIterator<Item = Y>>(iter: I) -> Self {
+    let mut rope = Self::new();
+    rope.extend(iter);
+    rope
+  }
+}
+
+impl<'a, Buf, Y> Extend<Y> for YarnRope<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Y: Into<YarnBox<'a, Buf>>,
+{
+  fn extend<I: IntoIterator<Item = Y>>(&mut self, iter: I) {
+    for yarn in iter {
+      self.push(yarn);
+    }
+  }
+}
+
+impl<Buf> Default for YarnRope<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Buf> Clone for YarnRope<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn clone(&self) -> Self {
+    Self {
+      segments: self.segments.clone(),
+      ends: self.ends.clone(),
+    }
+  }
+}
+
+impl<Buf> fmt::Debug for YarnRope<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_list().entries(&self.segments).finish()
+  }
+}
+
+impl<Buf> fmt::Display for YarnRope<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for segment in &self.segments {
+      fmt::Display::fmt(segment, f)?;
+    }
+    Ok(())
+  }
+}
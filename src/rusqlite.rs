@@ -0,0 +1,116 @@
+//! Integration with the `rusqlite` crate.
+//!
+//! String yarns are stored as `TEXT`, and byte yarns as `BLOB`.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use rusqlite::Connection;
+//!
+//! let db = Connection::open_in_memory().unwrap();
+//! db.execute("CREATE TABLE kv (key TEXT, value BLOB)", ()).unwrap();
+//! db.execute(
+//!   "INSERT INTO kv VALUES (?1, ?2)",
+//!   (yarn!("user:{}", 42), ByteYarn::new(b"\x00\x01")),
+//! )
+//! .unwrap();
+//!
+//! let (key, value): (Yarn, ByteYarn) = db
+//!   .query_row("SELECT key, value FROM kv", (), |row| {
+//!     Ok((row.get(0)?, row.get(1)?))
+//!   })
+//!   .unwrap();
+//! assert_eq!(key, "user:42");
+//! assert_eq!(value, [0, 1]);
+//! ```
+//!
+//! [`FromSql`] cannot borrow from the row, so [`YarnBox`]es read out of a row
+//! are copies, unless they are short enough to inline. To avoid the copy, a
+//! [`ValueRef`] can be converted into a [`YarnRef`] that borrows it, for the
+//! duration of a row-mapping closure:
+//!
+//! ```
+//! # use byteyarn::*;
+//! # use rusqlite::Connection;
+//! # let db = Connection::open_in_memory().unwrap();
+//! let len = db
+//!   .query_row("SELECT 'a fairly long string, to be measured'", (), |row| {
+//!     let text: YarnRef<str> = row.get_ref(0)?.try_into()?;
+//!     Ok(text.len())
+//!   })
+//!   .unwrap();
+//! assert_eq!(len, 36);
+//! ```
+
+use rusqlite::types::FromSql;
+use rusqlite::types::FromSqlError;
+use rusqlite::types::FromSqlResult;
+use rusqlite::types::ToSql;
+use rusqlite::types::ToSqlOutput;
+use rusqlite::types::ValueRef;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> ToSql for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+    Ok(ToSqlOutput::Borrowed(value_ref::<Buf>(self.as_bytes())))
+  }
+}
+
+impl<Buf> ToSql for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+    Ok(ToSqlOutput::Borrowed(value_ref::<Buf>(self.as_bytes())))
+  }
+}
+
+/// Wraps `bytes` as a `TEXT` value if `Buf` is `str`, and a `BLOB` otherwise.
+fn value_ref<Buf>(bytes: &[u8]) -> ValueRef
+where
+  Buf: crate::Buf + ?Sized,
+{
+  match <Buf as crate::z::Sealed>::UTF8 {
+    true => ValueRef::Text(bytes),
+    false => ValueRef::Blob(bytes),
+  }
+}
+
+impl<'a, Buf> TryFrom<ValueRef<'a>> for YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Error = FromSqlError;
+
+  /// Borrows a `TEXT` value as a string yarn, or a `BLOB` value as a byte
+  /// yarn, without copying.
+  fn try_from(value: ValueRef<'a>) -> FromSqlResult<Self> {
+    let bytes = match <Buf as crate::z::Sealed>::UTF8 {
+      true => value.as_str()?.as_bytes(),
+      false => value.as_blob()?,
+    };
+
+    Ok(YarnRef::new(unsafe {
+      // SAFETY: as_str() validates UTF-8 if Buf requires it.
+      YarnRef::<Buf>::raw2buf(bytes)
+    }))
+  }
+}
+
+impl<Buf> FromSql for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn column_result(value: ValueRef) -> FromSqlResult<Self> {
+    let yarn = YarnRef::<Buf>::try_from(value)?;
+    Ok(unsafe {
+      // SAFETY: yarn is a valid Buf.
+      YarnBox::from_raw(RawYarn::copy_slice(yarn.as_bytes()))
+    })
+  }
+}
@@ -0,0 +1,30 @@
+//! Integration with the `ryu` crate.
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+
+impl YarnBox<'_, str> {
+  /// Returns a yarn containing the shortest representation of a float that
+  /// round-trips through parsing.
+  ///
+  /// This is much faster than `yarn!("{f}")`, since it bypasses `core::fmt`,
+  /// but the output is not always the same: very large and very small numbers
+  /// are written in exponential notation, and integral values always have a
+  /// trailing `.0`. Non-finite values are written as `NaN`, `inf`, and `-inf`.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// assert_eq!(Yarn::from_float(1.5), "1.5");
+  /// assert_eq!(Yarn::from_float(1f32), "1.0");
+  /// assert_eq!(Yarn::from_float(1e100), "1e100");
+  /// assert_eq!(Yarn::from_float(f64::NEG_INFINITY), "-inf");
+  /// ```
+  pub fn from_float(f: impl ryu::Float) -> Self {
+    let mut buf = ryu::Buffer::new();
+    unsafe {
+      // SAFETY: copy_slice() returns a uniquely-owned raw yarn, and the bytes
+      // came out of a str.
+      YarnBox::from_raw(RawYarn::copy_slice(buf.format(f).as_bytes()))
+    }
+  }
+}
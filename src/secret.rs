@@ -0,0 +1,171 @@
+use std::fmt;
+use std::hint;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A yarn holding a secret, such as a password or an API key.
+///
+/// This is a thin wrapper over [`YarnBox`] whose `Debug` and `Display`
+/// implementations only print the length of the secret, so that it can't leak
+/// into logs through `#[derive(Debug)]` chains or careless formatting. To get
+/// at the secret itself, call [`SecretYarn::expose()`].
+///
+/// ```
+/// # use byteyarn::*;
+/// #[derive(Debug)]
+/// struct Config {
+///   user: Yarn,
+///   token: SecretYarn<'static, str>,
+/// }
+///
+/// let config = Config {
+///   user: yarn!("mittens"),
+///   token: SecretYarn::new(yarn!("hunter2")),
+/// };
+///
+/// assert_eq!(
+///   format!("{config:?}"),
+///   r#"Config { user: "mittens", token: [REDACTED 7 bytes] }"#,
+/// );
+/// assert_eq!(config.token.expose(), "hunter2");
+/// ```
+#[repr(transparent)]
+pub struct SecretYarn<'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  yarn: YarnBox<'a, Buf>,
+}
+
+impl<'a, Buf> SecretYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Wraps a yarn holding a secret.
+  pub const fn new(yarn: YarnBox<'a, Buf>) -> Self {
+    Self { yarn }
+  }
+
+  /// Returns the length of the secret, in bytes.
+  ///
+  /// This is the only information that the formatting impls reveal.
+  pub const fn len(&self) -> usize {
+    self.yarn.len()
+  }
+
+  /// Checks whether the secret is empty.
+  pub const fn is_empty(&self) -> bool {
+    self.yarn.is_empty()
+  }
+
+  /// Returns a reference to the secret.
+  pub const fn expose(&self) -> YarnRef<Buf> {
+    self.yarn.as_ref()
+  }
+
+  /// Compares this secret with `that` in constant time.
+  ///
+  /// See [`YarnRef::ct_eq()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let token = SecretYarn::new(yarn!("hunter2"));
+  /// assert!(token.ct_eq("hunter2"));
+  /// assert!(!token.ct_eq("hunter3"));
+  /// ```
+  pub fn ct_eq(&self, that: &Buf) -> bool {
+    self.yarn.ct_eq(that)
+  }
+
+  /// Unwraps the secret.
+  pub fn into_inner(self) -> YarnBox<'a, Buf> {
+    self.yarn
+  }
+}
+
+impl<'a, Buf> From<YarnBox<'a, Buf>> for SecretYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(yarn: YarnBox<'a, Buf>) -> Self {
+    Self::new(yarn)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Clone for SecretYarn<'_, Buf> {
+  fn clone(&self) -> Self {
+    Self::new(self.yarn.clone())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Default for SecretYarn<'_, Buf> {
+  fn default() -> Self {
+    Self::new(YarnBox::default())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Debug for SecretYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "[REDACTED {} bytes]", self.len())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Display for SecretYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(self, f)
+  }
+}
+
+impl<Buf> YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Compares this yarn with `that` in constant time.
+  ///
+  /// The running time of this function depends only on the lengths of the
+  /// inputs, and not on their contents, so it is suitable for comparing tokens
+  /// or MACs without opening up a timing side channel. The lengths themselves
+  /// are not secret: comparing yarns of different lengths returns early.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mac = YarnRef::<[u8]>::new(&[0xde, 0xad, 0xbe, 0xef]);
+  /// assert!(mac.ct_eq(&[0xde, 0xad, 0xbe, 0xef]));
+  /// assert!(!mac.ct_eq(&[0xde, 0xad, 0xbe, 0xee]));
+  /// assert!(!mac.ct_eq(&[0xde, 0xad]));
+  /// ```
+  pub fn ct_eq(&self, that: &Buf) -> bool {
+    let (a, b) = (self.as_bytes(), Self::buf2raw(that));
+    if a.len() != b.len() {
+      return false;
+    }
+
+    // The black box keeps the optimizer from noticing that it could bail out
+    // of the loop early.
+    let mut acc = 0;
+    for (x, y) in a.iter().zip(b) {
+      acc = hint::black_box(acc | (x ^ y));
+    }
+    acc == 0
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Compares this yarn with `that` in constant time.
+  ///
+  /// See [`YarnRef::ct_eq()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let token = Yarn::copy("6f1ed002ab5595859014ebf0951522d9");
+  /// assert!(token.ct_eq("6f1ed002ab5595859014ebf0951522d9"));
+  /// assert!(!token.ct_eq("6f1ed002ab5595859014ebf0951522d8"));
+  /// ```
+  pub fn ct_eq(&self, that: &Buf) -> bool {
+    self.as_ref().ct_eq(that)
+  }
+}
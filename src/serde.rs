@@ -0,0 +1,198 @@
+//! Integration with the `serde` crate.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::str;
+
+use serde::de;
+use serde::de::Deserialize;
+use serde::de::Deserializer;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use serde::ser::Serialize;
+use serde::ser::Serializer;
+
+use crate::raw::RawYarn;
+use crate::Interner;
+use crate::Symbol;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// Symbols serialize as their index, a `u32`.
+impl Serialize for Symbol {
+  fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u32(self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+  fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    u32::deserialize(d).map(Self)
+  }
+}
+
+/// An interner serializes as the sequence of its yarns, in symbol order, as
+/// strings if `Buf` is `str` and as bytes otherwise.
+///
+/// Deserializing it restores the same symbols. Yarns are borrowed from the
+/// input where the format allows it.
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut interner = Interner::<str>::new();
+/// let sym = [interner.intern("foo"), interner.intern("bar")];
+///
+/// let json = serde_json::to_string(&(&interner, sym)).unwrap();
+/// assert_eq!(json, r#"[["foo","bar"],[0,1]]"#);
+///
+/// let (loaded, sym): (Interner<str>, [Symbol; 2]) =
+///   serde_json::from_str(&json).unwrap();
+/// assert_eq!(loaded.resolve(sym[1]), "bar");
+/// ```
+impl<Buf> Serialize for Interner<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    s.collect_seq(self.iter().map(|(_, yarn)| Element(yarn)))
+  }
+}
+
+impl<'de, Buf> Deserialize<'de> for Interner<'de, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    struct V<Buf: ?Sized>(PhantomData<Box<Buf>>);
+    impl<'de, Buf> Visitor<'de> for V<Buf>
+    where
+      Buf: crate::Buf + ?Sized + 'de,
+    {
+      type Value = Interner<'de, Buf>;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of distinct yarns")
+      }
+
+      fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+      ) -> Result<Self::Value, A::Error> {
+        let mut interner = Interner::new();
+        while let Some(Element(yarn)) = seq.next_element()? {
+          let len = interner.len();
+          if interner.intern(yarn).index() != len {
+            return Err(de::Error::custom("duplicate yarn in interner"));
+          }
+        }
+        Ok(interner)
+      }
+    }
+
+    d.deserialize_seq(V(PhantomData))
+  }
+}
+
+/// A yarn that serializes as a string or as bytes, depending on `Buf`.
+struct Element<Y>(Y);
+
+impl<Buf> Serialize for Element<YarnRef<'_, Buf>>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    let bytes = self.0.as_bytes();
+    match <Buf as crate::z::Sealed>::UTF8 {
+      true => s.serialize_str(unsafe {
+        // SAFETY: If Buf is UTF-8, so is the yarn.
+        str::from_utf8_unchecked(bytes)
+      }),
+      false => s.serialize_bytes(bytes),
+    }
+  }
+}
+
+impl<'de, Buf> Deserialize<'de> for Element<YarnBox<'de, Buf>>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    struct V<Buf: ?Sized>(PhantomData<Box<Buf>>);
+    impl<Buf> V<Buf>
+    where
+      Buf: crate::Buf + ?Sized,
+    {
+      fn check<E: de::Error>(&self, bytes: &[u8]) -> Result<(), E> {
+        if <Buf as crate::z::Sealed>::UTF8 && str::from_utf8(bytes).is_err() {
+          return Err(E::invalid_value(de::Unexpected::Bytes(bytes), self));
+        }
+        Ok(())
+      }
+    }
+
+    impl<'de, Buf> Visitor<'de> for V<Buf>
+    where
+      Buf: crate::Buf + ?Sized + 'de,
+    {
+      type Value = YarnBox<'de, Buf>;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match <Buf as crate::z::Sealed>::UTF8 {
+          true => f.write_str("a string"),
+          false => f.write_str("a byte string"),
+        }
+      }
+
+      fn visit_borrowed_str<E: de::Error>(
+        self,
+        s: &'de str,
+      ) -> Result<Self::Value, E> {
+        self.visit_borrowed_bytes(s.as_bytes())
+      }
+
+      fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        self.visit_bytes(s.as_bytes())
+      }
+
+      fn visit_borrowed_bytes<E: de::Error>(
+        self,
+        b: &'de [u8],
+      ) -> Result<Self::Value, E> {
+        self.check(b)?;
+        Ok(YarnBox::new(unsafe {
+          // SAFETY: check() validates UTF-8 if Buf requires it.
+          YarnRef::<Buf>::raw2buf(b)
+        }))
+      }
+
+      fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Self::Value, E> {
+        self.check(b)?;
+        Ok(unsafe {
+          // SAFETY: Ditto.
+          YarnBox::from_raw(RawYarn::copy_slice(b))
+        })
+      }
+
+      fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+      ) -> Result<Self::Value, A::Error> {
+        // The size hint comes from the input, so don't trust it with more
+        // than a modest preallocation, the same way serde itself does.
+        let hint = seq.size_hint().unwrap_or(0);
+        let mut bytes = Vec::with_capacity(hint.min(4096));
+        while let Some(b) = seq.next_element()? {
+          bytes.push(b);
+        }
+        self.visit_bytes(&bytes)
+      }
+    }
+
+    let v = V(PhantomData);
+    match <Buf as crate::z::Sealed>::UTF8 {
+      true => d.deserialize_str(v),
+      false => d.deserialize_bytes(v),
+    }
+    .map(Element)
+  }
+}
@@ -0,0 +1,121 @@
+//! Integration with the `serde_json` crate.
+
+use std::fmt;
+
+use serde::de::Deserialize;
+use serde::de::Deserializer;
+use serde::de::Error as _;
+use serde::de::Unexpected;
+use serde::ser::Error as _;
+use serde::ser::Serialize;
+use serde::ser::Serializer;
+use serde_json::value::RawValue;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A JSON string, exactly as it was written in the input.
+///
+/// Like [`RawValue`], deserializing a `RawJsonYarn` captures the input text
+/// of the value without interpreting it: escapes are left as-is, and the
+/// surrounding quotes are kept. The text is borrowed from the input, and
+/// serializing a `RawJsonYarn` writes it back out byte-for-byte. This is
+/// useful for programs, like proxies and linters, that need to pass strings
+/// through without normalizing them.
+///
+/// Only strings can be captured; deserializing any other kind of value is an
+/// error.
+///
+/// (This is not called `RawYarn`, because that is the name of the yarn
+/// representation inside this crate.)
+///
+/// ```
+/// # use byteyarn::*;
+/// let json = r#"["caf\u00e9", "say \"hi\""]"#;
+/// let strings: Vec<RawJsonYarn> = serde_json::from_str(json).unwrap();
+/// assert_eq!(strings[0].as_raw(), r#""caf\u00e9""#);
+/// assert_eq!(strings[0].unescape(), "café");
+/// assert_eq!(strings[1].unescape(), "say \"hi\"");
+///
+/// let out = serde_json::to_string(&strings).unwrap();
+/// assert_eq!(out, r#"["caf\u00e9","say \"hi\""]"#);
+///
+/// assert!(serde_json::from_str::<RawJsonYarn>("42").is_err());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RawJsonYarn<'a> {
+  raw: YarnRef<'a, str>,
+}
+
+impl<'a> RawJsonYarn<'a> {
+  /// Returns the text of this string as it appeared in the input, including
+  /// its quotes.
+  pub fn as_raw(&self) -> YarnRef<'a, str> {
+    self.raw
+  }
+
+  /// Decodes this string, replacing escapes with the characters they stand
+  /// for.
+  ///
+  /// If the string contains no escapes, this does not copy, and the result
+  /// borrows from the input.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let json = r#" "no escapes in here" "#;
+  /// let raw: RawJsonYarn = serde_json::from_str(json).unwrap();
+  /// let text = raw.unescape();
+  /// assert_eq!(text, "no escapes in here");
+  /// assert_eq!(text.kind(), YarnKind::Borrowed);
+  /// ```
+  pub fn unescape(&self) -> YarnBox<'a, str> {
+    let raw = self.raw;
+    if !raw.as_bytes().contains(&b'\\') {
+      return unsafe {
+        // SAFETY: raw is a JSON string, so it begins and ends with ASCII
+        // quotes.
+        raw.slice_unchecked(1..raw.len() - 1).to_box()
+      };
+    }
+
+    let text = serde_json::from_str::<String>(raw.as_str())
+      .expect("RawJsonYarn should contain a valid JSON string");
+    YarnBox::from_string(text)
+  }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawJsonYarn<'a> {
+  fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+    let value = <&'de RawValue>::deserialize(d)?;
+    let raw = value.get();
+    if !raw.starts_with('"') {
+      return Err(D::Error::invalid_type(Unexpected::Other(raw), &"a string"));
+    }
+
+    Ok(Self {
+      raw: YarnRef::new(raw),
+    })
+  }
+}
+
+impl Serialize for RawJsonYarn<'_> {
+  fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    // Re-parsing the string is the only way to get a RawValue that borrows
+    // it, which avoids having to copy it.
+    let value = serde_json::from_str::<&RawValue>(self.raw.as_str())
+      .map_err(S::Error::custom)?;
+    value.serialize(s)
+  }
+}
+
+impl fmt::Debug for RawJsonYarn<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("RawJsonYarn").field(&self.raw).finish()
+  }
+}
+
+impl fmt::Display for RawJsonYarn<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.raw, f)
+  }
+}
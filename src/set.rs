@@ -0,0 +1,350 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use std::slice;
+use std::vec;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A set of yarns, kept in sorted order.
+///
+/// The yarns are stored in a single sorted array, so keys short enough to
+/// inline take up no space beyond the array itself, and lookups are a binary
+/// search. Because the yarns are sorted, all members that share a prefix are
+/// next to each other, which makes prefix queries cheap; this is handy for
+/// keyword tables and for matching paths against a set of routes.
+///
+/// Inserting into or removing from the middle of the set is linear in its
+/// size, so a set is best built all at once, such as with [`collect()`].
+///
+/// Yarns are ordered by their bytes, which for `str` is the same as ordering
+/// by `char`s.
+///
+/// ```
+/// # use byteyarn::*;
+/// let keywords = ["fn", "for", "if", "impl", "in", "let"]
+///   .into_iter()
+///   .collect::<YarnSet<str>>();
+///
+/// assert!(keywords.contains("impl"));
+/// assert!(!keywords.contains("im"));
+///
+/// let i = keywords.prefixed("i").collect::<Vec<_>>();
+/// assert_eq!(i, ["if", "impl", "in"]);
+/// ```
+///
+/// [`collect()`]: Iterator::collect
+pub struct YarnSet<Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  keys: Vec<YarnBox<'static, Buf>>,
+}
+
+impl<Buf> YarnSet<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  /// Returns a new, empty set.
+  pub const fn new() -> Self {
+    Self { keys: Vec::new() }
+  }
+
+  /// Returns the number of yarns in this set.
+  pub fn len(&self) -> usize {
+    self.keys.len()
+  }
+
+  /// Checks whether this set is empty.
+  pub fn is_empty(&self) -> bool {
+    self.keys.is_empty()
+  }
+
+  /// Removes every yarn from this set.
+  pub fn clear(&mut self) {
+    self.keys.clear()
+  }
+
+  /// Adds `key` to this set, returning whether it was not already present.
+  ///
+  /// If the key was already present, the original key yarn is kept.
+  pub fn insert(&mut self, key: impl Into<YarnBox<'static, Buf>>) -> bool {
+    let key = key.into();
+    match self.search(key.as_bytes()) {
+      Ok(_) => false,
+      Err(idx) => {
+        self.keys.insert(idx, key);
+        true
+      }
+    }
+  }
+
+  /// Removes `key` from this set, returning whether it was present.
+  pub fn remove(&mut self, key: impl AsRef<Buf>) -> bool {
+    match self.search(YarnRef::buf2raw(key.as_ref())) {
+      Ok(idx) => {
+        self.keys.remove(idx);
+        true
+      }
+      Err(_) => false,
+    }
+  }
+
+  /// Checks whether this set contains `key`.
+  pub fn contains(&self, key: impl AsRef<Buf>) -> bool {
+    self.get(key).is_some()
+  }
+
+  /// Looks up `key`, returning the yarn stored in this set.
+  pub fn get(&self, key: impl AsRef<Buf>) -> Option<YarnRef<Buf>> {
+    let idx = self.search(YarnRef::buf2raw(key.as_ref())).ok()?;
+    Some(self.keys[idx].as_ref())
+  }
+
+  /// Returns an iterator over the yarns in this set, in sorted order.
+  pub fn iter(&self) -> YarnSetIter<Buf> {
+    YarnSetIter {
+      iter: self.keys.iter(),
+    }
+  }
+
+  /// Returns an iterator over the yarns in this set that fall within `range`,
+  /// in sorted order.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let set = ["a", "b", "c", "d"].into_iter().collect::<YarnSet<str>>();
+  ///
+  /// assert_eq!(set.range("b".."d").collect::<Vec<_>>(), ["b", "c"]);
+  /// assert_eq!(set.range("bb"..).collect::<Vec<_>>(), ["c", "d"]);
+  /// assert_eq!(set.range(..="b").collect::<Vec<_>>(), ["a", "b"]);
+  /// ```
+  pub fn range<Key>(&self, range: impl RangeBounds<Key>) -> YarnSetIter<Buf>
+  where
+    Key: AsRef<Buf>,
+  {
+    let start = match range.start_bound() {
+      Bound::Included(k) => {
+        self.partition(|x| x < YarnRef::buf2raw(k.as_ref()))
+      }
+      Bound::Excluded(k) => {
+        self.partition(|x| x <= YarnRef::buf2raw(k.as_ref()))
+      }
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(k) => {
+        self.partition(|x| x <= YarnRef::buf2raw(k.as_ref()))
+      }
+      Bound::Excluded(k) => {
+        self.partition(|x| x < YarnRef::buf2raw(k.as_ref()))
+      }
+      Bound::Unbounded => self.len(),
+    };
+
+    YarnSetIter {
+      iter: self.keys[start..end.max(start)].iter(),
+    }
+  }
+
+  /// Returns an iterator over the yarns in this set that start with `prefix`,
+  /// in sorted order.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let routes = ["/", "/api/users", "/api/posts", "/apiary"]
+  ///   .into_iter()
+  ///   .collect::<YarnSet<str>>();
+  ///
+  /// let api = routes.prefixed("/api/").collect::<Vec<_>>();
+  /// assert_eq!(api, ["/api/posts", "/api/users"]);
+  /// ```
+  pub fn prefixed(&self, prefix: impl AsRef<Buf>) -> YarnSetIter<Buf> {
+    let prefix = YarnRef::buf2raw(prefix.as_ref());
+    let start = self.partition(|x| x < prefix);
+    let len =
+      self.keys[start..].partition_point(|x| x.as_bytes().starts_with(prefix));
+
+    YarnSetIter {
+      iter: self.keys[start..start + len].iter(),
+    }
+  }
+
+  /// Finds the longest yarn in this set that is a prefix of `key`.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let routes = ["/", "/api/", "/api/users/"]
+  ///   .into_iter()
+  ///   .collect::<YarnSet<str>>();
+  ///
+  /// assert_eq!(routes.longest_prefix("/api/users/42").unwrap(), "/api/users/");
+  /// assert_eq!(routes.longest_prefix("/api/posts/42").unwrap(), "/api/");
+  /// assert_eq!(routes.longest_prefix("/index.html").unwrap(), "/");
+  /// assert!(routes.longest_prefix("api").is_none());
+  /// ```
+  pub fn longest_prefix(&self, key: impl AsRef<Buf>) -> Option<YarnRef<Buf>> {
+    let mut key = YarnRef::buf2raw(key.as_ref());
+    loop {
+      // Any member that is a prefix of key sorts before it, and it is also a
+      // prefix of every yarn that sorts between it and key, including the
+      // greatest member that is not after key. Thus, the answer is a prefix
+      // of what that member and key have in common.
+      let idx = self.partition(|x| x <= key).checked_sub(1)?;
+      let candidate = self.keys[idx].as_bytes();
+      if key.starts_with(candidate) {
+        return Some(self.keys[idx].as_ref());
+      }
+
+      let common = candidate
+        .iter()
+        .zip(key)
+        .take_while(|(a, b)| a == b)
+        .count();
+      key = &key[..common];
+    }
+  }
+
+  /// Binary searches for `key` among this set's yarns.
+  fn search(&self, key: &[u8]) -> Result<usize, usize> {
+    self.keys.binary_search_by(|x| x.as_bytes().cmp(key))
+  }
+
+  /// Returns the index of the first yarn for which `pred` returns false.
+  fn partition(&self, pred: impl Fn(&[u8]) -> bool) -> usize {
+    self.keys.partition_point(|x| pred(x.as_bytes()))
+  }
+}
+
+/// An iterator over the yarns in a [`YarnSet`].
+pub struct YarnSetIter<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  iter: slice::Iter<'a, YarnBox<'static, Buf>>,
+}
+
+impl<'a, Buf> Iterator for YarnSetIter<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = YarnRef<'a, Buf>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(YarnBox::as_ref)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<Buf> DoubleEndedIterator for YarnSetIter<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(YarnBox::as_ref)
+  }
+}
+
+impl<Buf> ExactSizeIterator for YarnSetIter<'_, Buf> where
+  Buf: crate::Buf + ?Sized + 'static
+{
+}
+impl<Buf> FusedIterator for YarnSetIter<'_, Buf> where
+  Buf: crate::Buf + ?Sized + 'static
+{
+}
+
+impl<'a, Buf> IntoIterator for &'a YarnSet<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = YarnRef<'a, Buf>;
+  type IntoIter = YarnSetIter<'a, Buf>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<Buf> IntoIterator for YarnSet<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Item = YarnBox<'static, Buf>;
+  type IntoIter = vec::IntoIter<YarnBox<'static, Buf>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.keys.into_iter()
+  }
+}
+
+impl<K, Buf> FromIterator<K> for YarnSet<Buf>
+where
+  K: Into<YarnBox<'static, Buf>>,
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+    let mut set = Self::new();
+    set.extend(iter);
+    set
+  }
+}
+
+impl<K, Buf> Extend<K> for YarnSet<Buf>
+where
+  K: Into<YarnBox<'static, Buf>>,
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+    // Sort everything at once rather than inserting one at a time. The sort
+    // is stable, so of any duplicates, the yarn that was inserted first wins.
+    self.keys.extend(iter.into_iter().map(Into::into));
+    self.keys.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+    self.keys.dedup_by(|a, b| a == b);
+  }
+}
+
+impl<Buf> Default for YarnSet<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Buf> Clone for YarnSet<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn clone(&self) -> Self {
+    Self {
+      keys: self.keys.clone(),
+    }
+  }
+}
+
+impl<Buf> PartialEq for YarnSet<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn eq(&self, that: &Self) -> bool {
+    self.keys == that.keys
+  }
+}
+
+impl<Buf> Eq for YarnSet<Buf> where Buf: crate::Buf + ?Sized + 'static {}
+
+impl<Buf> fmt::Debug for YarnSet<Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
@@ -0,0 +1,559 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker::PhantomData;
+use std::mem;
+use std::mem::ManuallyDrop;
+use std::num::NonZeroU32;
+use std::ops::Deref;
+
+use crate::raw::RawYarn;
+use crate::TooLong;
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A yarn with a 32-bit length, for when billions of small strings need to be
+/// as compact as possible.
+///
+/// A `SmallYarn` is a pointer plus a `u32`, and is only four-byte aligned, so
+/// it is 12 bytes wide on 64-bit architectures, rather than 16. Like
+/// [`YarnBox`], it can inline short strings (up to 11 bytes on 64-bit
+/// architectures), borrow, point to static data, or own a heap allocation, and
+/// `Option<SmallYarn>` is the same size as `SmallYarn`.
+///
+/// The price is that a `SmallYarn` can be at most [`SmallYarn::MAX_LEN`] bytes
+/// long, and that reading its pointer may be an unaligned load.
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::mem::size_of;
+///
+/// #[cfg(target_pointer_width = "64")]
+/// {
+///   assert_eq!(size_of::<SmallYarn>(), 12);
+///   assert_eq!(size_of::<Option<SmallYarn>>(), 12);
+///
+///   // A token with a one-word payload still fits in 16 bytes.
+///   struct Token { text: SmallYarn<'static, str>, kind: u32 }
+///   assert_eq!(size_of::<Token>(), 16);
+///
+///   // So does an enum with a yarn payload and one other variant.
+///   enum Lexeme { Text(SmallYarn<'static, str>), Eof }
+///   assert_eq!(size_of::<Lexeme>(), 12);
+/// }
+///
+/// let ident = SmallYarn::<str>::new("identifier");
+/// assert_eq!(ident, "identifier");
+/// ```
+#[repr(transparent)]
+pub struct SmallYarn<'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  raw: Packed,
+  _ph: PhantomData<&'a Buf>,
+}
+
+/// The out-of-line layout of a `SmallYarn`.
+///
+/// `len` is stored little-endian, so that its top byte, which holds the tag,
+/// always overlaps `Inline::len`.
+#[repr(C, packed(4))]
+#[derive(Copy, Clone)]
+struct Packed {
+  ptr: *const u8,
+  len: NonZeroU32,
+}
+
+/// The inlined layout of a `SmallYarn`.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Inline {
+  data: [u8; SSO_LEN],
+  len: u8,
+}
+
+/// The longest string a `SmallYarn` can inline.
+const SSO_LEN: usize = mem::size_of::<Packed>() - 1;
+
+/// The longest string a `SmallYarn` can hold at all.
+const MAX_LEN: usize = (u32::MAX >> 2) as usize;
+
+const _: () = assert!(mem::size_of::<Packed>() == mem::size_of::<Inline>());
+
+// SmallYarn does not expose &mut through &self.
+unsafe impl<Buf: crate::Buf + ?Sized> Send for SmallYarn<'_, Buf> {}
+unsafe impl<Buf: crate::Buf + ?Sized> Sync for SmallYarn<'_, Buf> {}
+
+impl<'a, Buf> SmallYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// The longest string, in bytes, that a `SmallYarn` can inline.
+  ///
+  /// This is 11 on 64-bit architectures and 7 on 32-bit ones.
+  pub const SSO_LEN: usize = SSO_LEN;
+
+  /// The longest string, in bytes, that a `SmallYarn` can hold.
+  pub const MAX_LEN: usize = MAX_LEN;
+
+  /// Returns a yarn pointing to the given slice, without copying.
+  ///
+  /// Short slices are inlined, instead.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `buf` is longer than [`SmallYarn::MAX_LEN`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let foo = SmallYarn::<str>::new("Byzantium");
+  /// assert_eq!(foo.len(), 9);
+  /// ```
+  pub const fn new(buf: &'a Buf) -> Self {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.len() <= SSO_LEN {
+      return Self::from_inline(bytes);
+    }
+
+    unsafe {
+      // SAFETY: We copy the lifetime from buf into self.
+      Self::from_ptr_len_tag(bytes.as_ptr(), bytes.len(), RawYarn::ALIASED)
+    }
+  }
+
+  /// Like [`SmallYarn::new()`], but returns an error instead of panicking if
+  /// `buf` is longer than [`SmallYarn::MAX_LEN`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let foo = SmallYarn::<str>::try_new("Byzantium").unwrap();
+  /// assert_eq!(foo.len(), 9);
+  /// ```
+  pub const fn try_new(buf: &'a Buf) -> Result<Self, TooLong> {
+    let len = YarnRef::<Buf>::buf2raw(buf).len();
+    if let Err(e) = TooLong::check(len, MAX_LEN) {
+      return Err(e);
+    }
+    Ok(Self::new(buf))
+  }
+
+  /// Returns a new yarn containing the contents of the given slice, or `None`
+  /// if it is longer than [`SmallYarn::SSO_LEN`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let smol = SmallYarn::<str>::inlined("smol");
+  /// assert_eq!(smol.unwrap(), "smol");
+  ///
+  /// let big = SmallYarn::<str>::inlined("biiiiiiiiiiig");
+  /// assert!(big.is_none());
+  /// ```
+  pub const fn inlined(buf: &Buf) -> Option<Self> {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.len() > SSO_LEN {
+      return None;
+    }
+
+    Some(Self::from_inline(bytes))
+  }
+
+  /// Converts a yarn into a `SmallYarn`, without copying if possible.
+  ///
+  /// Yarns that [`YarnBox`] inlines but `SmallYarn` cannot are copied to the
+  /// heap.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `yarn` is longer than [`SmallYarn::MAX_LEN`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = Yarn::from_string("a string that is too long to inline".into());
+  /// let ptr = yarn.as_ptr();
+  ///
+  /// let small = SmallYarn::from_box(yarn);
+  /// assert_eq!(small.as_ptr(), ptr);
+  /// ```
+  pub fn from_box(yarn: YarnBox<'a, Buf>) -> Self {
+    if yarn.len() <= SSO_LEN {
+      return Self::from_inline(yarn.as_bytes());
+    }
+
+    let raw = yarn.into_raw().out_of_line();
+    unsafe {
+      // SAFETY: raw is not SMALL, and carries the same obligations as yarn
+      // did, which we take over.
+      Self::from_ptr_len_tag(raw.as_ptr(), raw.len(), raw.kind())
+    }
+  }
+
+  /// Like [`SmallYarn::from_box()`], but returns an error instead of panicking
+  /// if `yarn` is longer than [`SmallYarn::MAX_LEN`].
+  pub fn try_from_box(yarn: YarnBox<'a, Buf>) -> Result<Self, TooLong> {
+    TooLong::check(yarn.len(), MAX_LEN)?;
+    Ok(Self::from_box(yarn))
+  }
+
+  /// Converts this yarn into an ordinary [`YarnBox`], without copying.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let small = SmallYarn::<str>::copy("tabby");
+  /// let yarn: Yarn = small.into_box();
+  /// assert_eq!(yarn, "tabby");
+  /// ```
+  pub fn into_box(self) -> YarnBox<'a, Buf> {
+    let this = ManuallyDrop::new(self);
+    let raw = match this.tag() {
+      RawYarn::SMALL => RawYarn::copy_slice(this.as_bytes()),
+      tag => unsafe {
+        // SAFETY: We're transferring our obligations to a raw yarn.
+        RawYarn::from_ptr_len_tag(this.as_bytes().as_ptr(), this.len(), tag)
+      },
+    };
+
+    unsafe {
+      // SAFETY: raw is either a fresh SMALL yarn, or the one we were
+      // holding, which lives for 'a.
+      YarnBox::from_raw(raw)
+    }
+  }
+
+  /// Extends the lifetime of this yarn if this yarn is dynamically known to
+  /// point to immortal memory.
+  ///
+  /// If it doesn't, the contents are copied into a fresh heap allocation.
+  pub fn immortalize(self) -> SmallYarn<'static, Buf> {
+    if self.tag() != RawYarn::ALIASED {
+      let this = ManuallyDrop::new(self);
+      return SmallYarn {
+        raw: this.raw,
+        _ph: PhantomData,
+      };
+    }
+
+    SmallYarn::from_box(self.into_box().immortalize())
+  }
+
+  /// Checks whether this yarn is empty.
+  pub const fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Returns the length of this yarn, in bytes.
+  pub const fn len(&self) -> usize {
+    match self.tag() {
+      RawYarn::SMALL => (self.inline().len & !(0b11 << 6)) as usize,
+      _ => self.len_word() as usize & MAX_LEN,
+    }
+  }
+
+  /// Converts this yarn into a slice.
+  pub const fn as_slice(&self) -> &Buf {
+    unsafe {
+      // SAFETY: Our bytes were either copied out of a Buf, or point into one.
+      YarnRef::<Buf>::raw2buf(self.as_bytes())
+    }
+  }
+
+  /// Converts this yarn into a byte slice.
+  pub const fn as_bytes(&self) -> &[u8] {
+    match self.tag() {
+      RawYarn::SMALL => {
+        let (bytes, _) = self.inline().data.split_at(self.len());
+        bytes
+      }
+      _ => unsafe {
+        // SAFETY: Non-SMALL yarns point to self.len() valid bytes.
+        std::slice::from_raw_parts(self.raw.ptr, self.len())
+      },
+    }
+  }
+
+  /// Returns a reference to this yarn's contents as a [`YarnRef`].
+  pub fn as_ref(&self) -> YarnRef<Buf> {
+    match self.tag() {
+      RawYarn::STATIC => unsafe {
+        // SAFETY: Static yarns never dangle, so they stay static.
+        YarnRef::from_raw(RawYarn::from_ptr_len_tag(
+          self.raw.ptr,
+          self.len(),
+          RawYarn::STATIC,
+        ))
+      },
+      _ => YarnRef::new(self.as_slice()),
+    }
+  }
+
+  /// Inlines `bytes`, which must be at most `SSO_LEN` bytes long.
+  const fn from_inline(bytes: &[u8]) -> Self {
+    let mut inline = Inline {
+      data: [0; SSO_LEN],
+      len: bytes.len() as u8 | RawYarn::SMALL << 6,
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+      inline.data[i] = bytes[i];
+      i += 1;
+    }
+
+    Self {
+      raw: unsafe {
+        // SAFETY: Inline and Packed are the same size, and both are POD. The
+        // length byte is never zero, so neither is Packed::len.
+        mem::transmute::<Inline, Packed>(inline)
+      },
+      _ph: PhantomData,
+    }
+  }
+
+  /// Creates a new, non-`SMALL` yarn with the given pointer, length, and tag.
+  ///
+  /// # Safety
+  ///
+  /// See [`RawYarn::from_ptr_len_tag()`].
+  const unsafe fn from_ptr_len_tag(
+    ptr: *const u8,
+    len: usize,
+    tag: u8,
+  ) -> Self {
+    assert!(
+      len <= MAX_LEN,
+      "small yarns cannot be longer than 2^30 - 1 bytes"
+    );
+    debug_assert!(tag != RawYarn::SMALL);
+    debug_assert!(len > SSO_LEN);
+
+    let word = len as u32 | (tag as u32) << 30;
+    Self {
+      raw: Packed {
+        ptr,
+        len: NonZeroU32::new_unchecked(word.to_le()),
+      },
+      _ph: PhantomData,
+    }
+  }
+
+  /// Returns this yarn's tag; one of the `RawYarn` constants.
+  const fn tag(&self) -> u8 {
+    self.inline().len >> 6
+  }
+
+  const fn len_word(&self) -> u32 {
+    u32::from_le(self.raw.len.get())
+  }
+
+  const fn inline(&self) -> &Inline {
+    unsafe {
+      // SAFETY: Packed and Inline are the same size, and Inline has
+      // alignment 1. Every byte of Packed is initialized.
+      mem::transmute::<&Packed, &Inline>(&self.raw)
+    }
+  }
+}
+
+impl<Buf> SmallYarn<'static, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns a yarn pointing to the given static slice, without copying.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `buf` is longer than [`SmallYarn::MAX_LEN`].
+  pub const fn from_static(buf: &'static Buf) -> Self {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.len() <= SSO_LEN {
+      return Self::from_inline(bytes);
+    }
+
+    unsafe {
+      // SAFETY: buf is static.
+      Self::from_ptr_len_tag(bytes.as_ptr(), bytes.len(), RawYarn::STATIC)
+    }
+  }
+
+  /// Like [`SmallYarn::from_static()`], but returns an error instead of
+  /// panicking if `buf` is longer than [`SmallYarn::MAX_LEN`].
+  pub const fn try_from_static(buf: &'static Buf) -> Result<Self, TooLong> {
+    let len = YarnRef::<Buf>::buf2raw(buf).len();
+    if let Err(e) = TooLong::check(len, MAX_LEN) {
+      return Err(e);
+    }
+    Ok(Self::from_static(buf))
+  }
+
+  /// Copies `buf` and returns an immortal yarn.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `buf` is longer than [`SmallYarn::MAX_LEN`].
+  pub fn copy(buf: &Buf) -> Self {
+    SmallYarn::new(buf).immortalize()
+  }
+}
+
+impl SmallYarn<'_, str> {
+  /// Converts this yarn into a string slice.
+  pub fn as_str(&self) -> &str {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Deref for SmallYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Target = Buf;
+  fn deref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Drop for SmallYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn drop(&mut self) {
+    match self.tag() {
+      RawYarn::HEAP => unsafe {
+        // SAFETY: We own this allocation, and nothing will touch it after
+        // this.
+        let raw =
+          RawYarn::from_ptr_len_tag(self.raw.ptr, self.len(), RawYarn::HEAP);
+        drop(YarnBox::<Buf>::from_raw(raw))
+      },
+      #[cfg(feature = "zeroize")]
+      RawYarn::SMALL => unsafe {
+        // SAFETY: Packed and Inline are the same size, and Inline has
+        // alignment 1.
+        let inline = mem::transmute::<&mut Packed, &mut Inline>(&mut self.raw);
+        ::zeroize::Zeroize::zeroize(&mut inline.data)
+      },
+      _ => {}
+    }
+  }
+}
+
+impl<Buf> Clone for SmallYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn clone(&self) -> Self {
+    if self.tag() != RawYarn::HEAP {
+      return Self {
+        raw: self.raw,
+        _ph: PhantomData,
+      };
+    }
+
+    let copy = RawYarn::copy_slice(self.as_bytes());
+    unsafe {
+      // SAFETY: copy_slice never aliases, and our bytes came out of a Buf.
+      Self::from_box(YarnBox::from_raw(copy))
+    }
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Debug for SmallYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Display for SmallYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<Slice, Buf> PartialEq<Slice> for SmallYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn eq(&self, that: &Slice) -> bool {
+    self.as_slice() == that.as_ref()
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Eq for SmallYarn<'_, Buf> {}
+
+impl<Slice, Buf> PartialOrd<Slice> for SmallYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn partial_cmp(&self, that: &Slice) -> Option<Ordering> {
+    self.as_slice().partial_cmp(that.as_ref())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Ord for SmallYarn<'_, Buf> {
+  fn cmp(&self, that: &Self) -> Ordering {
+    self.as_slice().cmp(that.as_slice())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Hash for SmallYarn<'_, Buf> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_slice().hash(state)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Default for SmallYarn<'_, Buf> {
+  fn default() -> Self {
+    Self::from_inline(&[])
+  }
+}
+
+impl<Buf> AsRef<Buf> for SmallYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn as_ref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Borrow<Buf> for SmallYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn borrow(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<'a, Buf> From<YarnBox<'a, Buf>> for SmallYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnBox<'a, Buf>) -> Self {
+    Self::from_box(y)
+  }
+}
+
+impl<'a, Buf> From<YarnRef<'a, Buf>> for SmallYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnRef<'a, Buf>) -> Self {
+    Self::from_box(y.to_box())
+  }
+}
+
+impl<'a, Buf> From<SmallYarn<'a, Buf>> for YarnBox<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: SmallYarn<'a, Buf>) -> Self {
+    y.into_box()
+  }
+}
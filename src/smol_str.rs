@@ -0,0 +1,58 @@
+//! Integration with the `smol_str` crate.
+
+use smol_str::SmolStr;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> From<SmolStr> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts a `SmolStr` into a yarn.
+  ///
+  /// `SmolStr`s share their heap buffers, so long strings must be copied;
+  /// short ones are inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use smol_str::SmolStr;
+  ///
+  /// let yarn = Yarn::from(SmolStr::new("smol"));
+  /// assert_eq!(yarn, "smol");
+  /// ```
+  fn from(s: SmolStr) -> Self {
+    unsafe {
+      // SAFETY: both [u8] and str can be safely constructed from a str, and
+      // copy_slice() returns a uniquely-owned raw yarn.
+      YarnBox::from_raw(RawYarn::copy_slice(s.as_bytes()))
+    }
+  }
+}
+
+impl From<YarnBox<'_, str>> for SmolStr {
+  /// Converts a yarn into a `SmolStr`, copying it unless it is static.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use smol_str::SmolStr;
+  ///
+  /// let lit = Yarn::from_static("a static string that is quite long");
+  /// let smol = SmolStr::from(lit);
+  /// assert!(!smol.is_heap_allocated());
+  /// ```
+  fn from(y: YarnBox<str>) -> Self {
+    SmolStr::from(y.as_ref())
+  }
+}
+
+impl From<YarnRef<'_, str>> for SmolStr {
+  /// Converts a yarn into a `SmolStr`, copying it unless it is static.
+  fn from(y: YarnRef<str>) -> Self {
+    match y.as_static_slice() {
+      Some(s) => SmolStr::new_static(s),
+      None => SmolStr::new(y.as_str()),
+    }
+  }
+}
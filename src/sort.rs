@@ -0,0 +1,101 @@
+use std::mem;
+
+use crate::YarnBox;
+
+/// Sorts a slice of yarns, usually faster than [`slice::sort()`] when there
+/// are many yarns and most of them are short.
+///
+/// This first radix-sorts the yarns by their first eight bytes, which for
+/// short yarns are stored inline and can be read without chasing a pointer,
+/// and then sorts runs of yarns that share those bytes by comparing them in
+/// full. Like [`slice::sort()`], the sort is stable, and the yarns end up in
+/// the same order as [`Ord`] would put them in.
+///
+/// Small slices are handed to [`slice::sort()`] directly, since the radix
+/// passes do not pay for themselves there.
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut words = "the quick brown fox jumps over the lazy dog"
+///   .split(' ')
+///   .cycle()
+///   .take(1000)
+///   .map(Yarn::from)
+///   .collect::<Vec<_>>();
+///
+/// let mut expected = words.clone();
+/// expected.sort();
+///
+/// sort_yarns(&mut words);
+/// assert_eq!(words, expected);
+/// ```
+pub fn sort_yarns<Buf>(yarns: &mut [YarnBox<Buf>])
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Below this many yarns, just use a comparison sort.
+  const MIN_RADIX_LEN: usize = 64;
+
+  if yarns.len() < MIN_RADIX_LEN {
+    yarns.sort();
+    return;
+  }
+
+  // Sort (key, index) pairs by key, one byte at a time, starting from the
+  // least significant byte; each pass is a stable counting sort.
+  let mut keys = yarns
+    .iter()
+    .enumerate()
+    .map(|(i, y)| (radix_key(y.as_bytes()), i))
+    .collect::<Vec<_>>();
+  let mut scratch = keys.clone();
+  for shift in (0..64).step_by(8) {
+    let digit = |key: u64| (key >> shift) as u8 as usize;
+
+    let mut counts = [0usize; 256];
+    for &(key, _) in &keys {
+      counts[digit(key)] += 1;
+    }
+    if counts.iter().any(|&n| n == keys.len()) {
+      // Every key has the same digit here, so this pass would be a no-op.
+      continue;
+    }
+
+    let mut offset = 0;
+    for count in &mut counts {
+      offset += mem::replace(count, offset);
+    }
+    for &(key, i) in &keys {
+      let slot = &mut counts[digit(key)];
+      scratch[*slot] = (key, i);
+      *slot += 1;
+    }
+    mem::swap(&mut keys, &mut scratch);
+  }
+
+  let mut taken = yarns.iter_mut().map(mem::take).collect::<Vec<_>>();
+  for (yarn, &(_, i)) in yarns.iter_mut().zip(&keys) {
+    *yarn = mem::take(&mut taken[i]);
+  }
+
+  // Yarns with equal keys share their first eight bytes (or are shorter and
+  // padded with zeros), so they still need to be compared in full.
+  let mut start = 0;
+  while start < keys.len() {
+    let key = keys[start].0;
+    let run = keys[start..].iter().take_while(|&&(k, _)| k == key).count();
+    if run > 1 {
+      yarns[start..start + run].sort();
+    }
+    start += run;
+  }
+}
+
+/// Returns the first eight bytes of `bytes`, padded with zeros, as a
+/// big-endian integer, so that comparing keys agrees with comparing bytes.
+fn radix_key(bytes: &[u8]) -> u64 {
+  let mut key = [0; 8];
+  let n = bytes.len().min(8);
+  key[..n].copy_from_slice(&bytes[..n]);
+  u64::from_be_bytes(key)
+}
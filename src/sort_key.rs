@@ -0,0 +1,160 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A yarn paired with a case-folded copy of itself, which it is ordered by.
+///
+/// Sorting yarns case-insensitively with a comparison function folds both
+/// yarns on every comparison. A `SortKey` folds its yarn once, when it is
+/// created, and keeps the result, so that comparisons are just byte
+/// comparisons. Folded keys that are short enough are inlined, and yarns that
+/// are already folded are not copied at all.
+///
+/// String yarns are folded by lowercasing them, and byte yarns by lowercasing
+/// their ASCII letters.
+///
+/// Yarns that fold to the same key, like `"Apple"` and `"apple"`, are ordered
+/// by their original bytes, so that the ordering agrees with `Eq`, which
+/// compares the original yarns.
+///
+/// ```
+/// # use byteyarn::*;
+/// let words = ["banana", "Cherry", "apple", "Banana", "ÉCLAIR", "date"];
+/// let mut keys = words
+///   .iter()
+///   .map(|&w| SortKey::new(Yarn::from(w)))
+///   .collect::<Vec<_>>();
+/// keys.sort();
+///
+/// let sorted = keys.iter().map(SortKey::yarn).collect::<Vec<_>>();
+/// assert_eq!(sorted, ["apple", "Banana", "banana", "Cherry", "date", "ÉCLAIR"]);
+/// assert_eq!(keys[5].key(), "éclair");
+/// ```
+pub struct SortKey<'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  yarn: YarnBox<'a, Buf>,
+  /// The folded yarn, or `None` if folding would not change it.
+  folded: Option<YarnBox<'a, Buf>>,
+}
+
+impl<'a, Buf> SortKey<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Folds `yarn` and wraps it in a new sort key.
+  pub fn new(yarn: YarnBox<'a, Buf>) -> Self {
+    let folded = fold::<Buf>(yarn.as_bytes()).map(|bytes| unsafe {
+      // SAFETY: Lowercasing a valid Buf produces a valid Buf.
+      YarnBox::from_raw(RawYarn::from_heap(bytes.into()))
+    });
+
+    Self { yarn, folded }
+  }
+
+  /// Returns the original yarn.
+  pub fn yarn(&self) -> YarnRef<Buf> {
+    self.yarn.as_ref()
+  }
+
+  /// Returns the folded yarn that this key is ordered by.
+  pub fn key(&self) -> YarnRef<Buf> {
+    self.folded.as_ref().unwrap_or(&self.yarn).as_ref()
+  }
+
+  /// Unwraps the original yarn.
+  pub fn into_inner(self) -> YarnBox<'a, Buf> {
+    self.yarn
+  }
+}
+
+/// Returns the folded form of `bytes`, or `None` if it is already folded.
+fn fold<Buf>(bytes: &[u8]) -> Option<Vec<u8>>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  if !<Buf as crate::z::Sealed>::UTF8 {
+    if !bytes.iter().any(u8::is_ascii_uppercase) {
+      return None;
+    }
+    return Some(bytes.to_ascii_lowercase());
+  }
+
+  let s = unsafe {
+    // SAFETY: If Buf is UTF-8, so are the bytes.
+    std::str::from_utf8_unchecked(bytes)
+  };
+  let folded = s.chars().all(|c| {
+    let mut lower = c.to_lowercase();
+    lower.next() == Some(c) && lower.next().is_none()
+  });
+  if folded {
+    return None;
+  }
+  Some(s.to_lowercase().into_bytes())
+}
+
+impl<'a, Buf> From<YarnBox<'a, Buf>> for SortKey<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(yarn: YarnBox<'a, Buf>) -> Self {
+    Self::new(yarn)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Clone for SortKey<'_, Buf> {
+  fn clone(&self) -> Self {
+    Self {
+      yarn: self.yarn.clone(),
+      folded: self.folded.clone(),
+    }
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> PartialEq for SortKey<'_, Buf> {
+  fn eq(&self, that: &Self) -> bool {
+    self.yarn == that.yarn
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Eq for SortKey<'_, Buf> {}
+
+impl<Buf: crate::Buf + ?Sized> PartialOrd for SortKey<'_, Buf> {
+  fn partial_cmp(&self, that: &Self) -> Option<Ordering> {
+    Some(self.cmp(that))
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Ord for SortKey<'_, Buf> {
+  fn cmp(&self, that: &Self) -> Ordering {
+    let (a, b) = (self.key(), that.key());
+    a.as_bytes()
+      .cmp(b.as_bytes())
+      .then_with(|| self.yarn.as_bytes().cmp(that.yarn.as_bytes()))
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Hash for SortKey<'_, Buf> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.yarn.hash(state)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Debug for SortKey<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(&self.yarn, f)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Display for SortKey<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.yarn, f)
+  }
+}
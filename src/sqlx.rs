@@ -0,0 +1,220 @@
+//! Integration with the `sqlx` crate.
+//!
+//! String yarns have the same SQL types as `str`, and byte yarns the same as
+//! `[u8]`, on every backend. [`YarnRef`]s decode by borrowing from the row,
+//! while [`YarnBox`]es copy, unless they are short enough to inline.
+//!
+//! ```
+//! # use byteyarn::*;
+//! use sqlx::postgres::PgRow;
+//! use sqlx::FromRow;
+//! use sqlx::Postgres;
+//! use sqlx::Row;
+//! use sqlx::Sqlite;
+//! use sqlx::Type;
+//!
+//! struct User {
+//!   name: Yarn,
+//!   avatar: ByteYarn,
+//! }
+//!
+//! impl FromRow<'_, PgRow> for User {
+//!   fn from_row(row: &PgRow) -> sqlx::Result<Self> {
+//!     Ok(Self {
+//!       name: row.try_get("name")?,
+//!       avatar: row.try_get("avatar")?,
+//!     })
+//!   }
+//! }
+//!
+//! let name = yarn!("user{}", 42);
+//! let insert = sqlx::query::<Sqlite>("INSERT INTO users VALUES (?, ?)")
+//!   .bind(name.as_ref())
+//!   .bind(ByteYarn::new(b"\x89PNG"));
+//!
+//! assert_eq!(
+//!   <Yarn as Type<Postgres>>::type_info(),
+//!   <String as Type<Postgres>>::type_info(),
+//! );
+//! ```
+
+use std::borrow::Cow;
+
+use sqlx::database::HasArguments;
+use sqlx::database::HasValueRef;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::SqliteArgumentValue;
+use sqlx::Database;
+use sqlx::Decode;
+use sqlx::Encode;
+use sqlx::MySql;
+use sqlx::Postgres;
+use sqlx::Sqlite;
+use sqlx::Type;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<DB, Buf> Type<DB> for YarnRef<'_, Buf>
+where
+  DB: Database,
+  Buf: crate::Buf + Type<DB> + ?Sized,
+{
+  fn type_info() -> DB::TypeInfo {
+    Buf::type_info()
+  }
+
+  fn compatible(ty: &DB::TypeInfo) -> bool {
+    Buf::compatible(ty)
+  }
+}
+
+impl<DB, Buf> Type<DB> for YarnBox<'_, Buf>
+where
+  DB: Database,
+  Buf: crate::Buf + Type<DB> + ?Sized,
+{
+  fn type_info() -> DB::TypeInfo {
+    Buf::type_info()
+  }
+
+  fn compatible(ty: &DB::TypeInfo) -> bool {
+    Buf::compatible(ty)
+  }
+}
+
+impl<'r, DB, Buf> Decode<'r, DB> for YarnRef<'r, Buf>
+where
+  DB: Database,
+  Buf: crate::Buf + ?Sized + 'r,
+  &'r Buf: Decode<'r, DB>,
+{
+  fn decode(
+    value: <DB as HasValueRef<'r>>::ValueRef,
+  ) -> Result<Self, BoxDynError> {
+    <&Buf>::decode(value).map(YarnRef::new)
+  }
+}
+
+impl<'r, DB, Buf> Decode<'r, DB> for YarnBox<'_, Buf>
+where
+  DB: Database,
+  Buf: crate::Buf + ?Sized + 'r,
+  &'r Buf: Decode<'r, DB>,
+{
+  fn decode(
+    value: <DB as HasValueRef<'r>>::ValueRef,
+  ) -> Result<Self, BoxDynError> {
+    let yarn = YarnRef::<Buf>::decode(value)?;
+    Ok(unsafe {
+      // SAFETY: yarn is a valid Buf.
+      YarnBox::from_raw(RawYarn::copy_slice(yarn.as_bytes()))
+    })
+  }
+}
+
+// Postgres and MySQL copy arguments into a buffer, so yarns can be encoded
+// the same way slices are.
+macro_rules! impl_encode_by_copy {
+  ($($db:ty),*) => {$(
+    impl<'q, Buf> Encode<'q, $db> for YarnRef<'_, Buf>
+    where
+      Buf: crate::Buf + ?Sized,
+      for<'a> &'a Buf: Encode<'q, $db>,
+    {
+      fn encode_by_ref(
+        &self,
+        buf: &mut <$db as HasArguments<'q>>::ArgumentBuffer,
+      ) -> IsNull {
+        <&Buf>::encode(self.as_slice(), buf)
+      }
+
+      fn size_hint(&self) -> usize {
+        <&Buf>::size_hint(&self.as_slice())
+      }
+    }
+
+    impl<'q, Buf> Encode<'q, $db> for YarnBox<'_, Buf>
+    where
+      Buf: crate::Buf + ?Sized,
+      for<'a> &'a Buf: Encode<'q, $db>,
+    {
+      fn encode_by_ref(
+        &self,
+        buf: &mut <$db as HasArguments<'q>>::ArgumentBuffer,
+      ) -> IsNull {
+        <&Buf>::encode(self.as_slice(), buf)
+      }
+
+      fn size_hint(&self) -> usize {
+        <&Buf>::size_hint(&self.as_slice())
+      }
+    }
+  )*};
+}
+
+impl_encode_by_copy!(Postgres, MySql);
+
+// SQLite holds onto its arguments until the query runs, so yarns that are
+// not inlined are passed by reference, and the rest are copied (or, for heap
+// yarns being moved, handed over).
+
+impl<'q, 'a: 'q, Buf> Encode<'q, Sqlite> for YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn encode(self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+    args.push(sqlite_value::<Buf>(match self.as_borrowed_slice() {
+      Some(buf) => Cow::Borrowed(YarnRef::buf2raw(buf)),
+      None => Cow::Owned(self.to_vec()),
+    }));
+    IsNull::No
+  }
+
+  fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+    self.encode(args)
+  }
+}
+
+impl<'q, 'a: 'q, Buf> Encode<'q, Sqlite> for YarnBox<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn encode(self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+    if let Some(yarn) = self.to_ref() {
+      return yarn.encode(args);
+    }
+
+    args.push(sqlite_value::<Buf>(Cow::Owned(self.into_vec())));
+    IsNull::No
+  }
+
+  fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+    if let Some(yarn) = self.to_ref() {
+      return yarn.encode(args);
+    }
+
+    args.push(sqlite_value::<Buf>(Cow::Owned(self.as_bytes().to_vec())));
+    IsNull::No
+  }
+}
+
+/// Wraps `bytes` as a `TEXT` value if `Buf` is `str`, and a `BLOB` otherwise.
+fn sqlite_value<Buf>(bytes: Cow<[u8]>) -> SqliteArgumentValue
+where
+  Buf: crate::Buf + ?Sized,
+{
+  if !<Buf as crate::z::Sealed>::UTF8 {
+    return SqliteArgumentValue::Blob(bytes);
+  }
+
+  SqliteArgumentValue::Text(unsafe {
+    // SAFETY: If Buf is UTF-8, so are the bytes.
+    match bytes {
+      Cow::Borrowed(b) => Cow::Borrowed(std::str::from_utf8_unchecked(b)),
+      Cow::Owned(b) => Cow::Owned(String::from_utf8_unchecked(b)),
+    }
+  })
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+use std::ops::Deref;
+
+use crate::YarnBox;
+
+/// A yarn whose contents are never inlined.
+///
+/// Moving an inlined yarn moves its contents along with it, so the slice
+/// returned by [`YarnBox::as_slice()`] is generally not stable. A `StableYarn`
+/// is a yarn that is known to keep its contents out of line: on the heap, in
+/// static memory, or in some borrowed buffer. Hence, its contents stay put even
+/// as the `StableYarn` itself moves around, which is what self-referential
+/// structs need.
+///
+/// When the `stable_deref_trait` feature is enabled, this type implements
+/// `StableDeref`, so it can be used with crates like `ouroboros` and
+/// `owning_ref`.
+///
+/// ```
+/// # use byteyarn::*;
+/// let stable = yarn!("smol").into_stable();
+/// let ptr = stable.as_ptr();
+///
+/// let moved = Box::new(stable);
+/// assert_eq!(moved.as_ptr(), ptr);
+/// ```
+#[repr(transparent)]
+pub struct StableYarn<'a, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  yarn: YarnBox<'a, Buf>,
+}
+
+impl<'a, Buf> YarnBox<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts this yarn into a [`StableYarn`], copying it to the heap if it is
+  /// inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let big = Yarn::from_string("big heap allocated yarn".into());
+  /// let ptr = big.as_ptr();
+  /// assert_eq!(big.into_stable().as_ptr(), ptr);
+  /// ```
+  pub fn into_stable(self) -> StableYarn<'a, Buf> {
+    unsafe {
+      // SAFETY: out_of_line() preserves the contents and lifetime of the raw
+      // yarn, and takes ownership of it, since we called into_raw().
+      let raw = self.into_raw().out_of_line();
+      StableYarn {
+        yarn: YarnBox::from_raw(raw),
+      }
+    }
+  }
+
+  /// Converts this yarn into a [`StableYarn`] if it is not inlined, and returns
+  /// it unchanged otherwise.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// assert!(Yarn::from_static("a long static string").try_into_stable().is_ok());
+  /// assert!(yarn!("smol").try_into_stable().is_err());
+  /// ```
+  pub fn try_into_stable(self) -> Result<StableYarn<'a, Buf>, Self> {
+    let raw = self.into_raw();
+    unsafe {
+      // SAFETY: We're just putting the raw yarn back where it came from.
+      let yarn = YarnBox::from_raw(raw);
+      match raw.is_small() {
+        true => Err(yarn),
+        false => Ok(StableYarn { yarn }),
+      }
+    }
+  }
+}
+
+impl<'a, Buf> StableYarn<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns a reference to the underlying yarn.
+  pub const fn as_yarn(&self) -> &YarnBox<'a, Buf> {
+    &self.yarn
+  }
+
+  /// Converts this yarn back into an ordinary yarn.
+  pub fn into_inner(self) -> YarnBox<'a, Buf> {
+    self.yarn
+  }
+}
+
+impl<Buf> Deref for StableYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Target = Buf;
+  fn deref(&self) -> &Buf {
+    self.yarn.as_slice()
+  }
+}
+
+impl<Buf> Clone for StableYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn clone(&self) -> Self {
+    // Cloning may inline the copy, so it needs to be moved back out of line.
+    self.yarn.clone().into_stable()
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Debug for StableYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(&self.yarn, f)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Display for StableYarn<'_, Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.yarn, f)
+  }
+}
+
+impl<'a, Buf> From<StableYarn<'a, Buf>> for YarnBox<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: StableYarn<'a, Buf>) -> Self {
+    y.into_inner()
+  }
+}
+
+// SAFETY: A StableYarn's raw yarn is never SMALL, so it always points outside
+// of itself, to memory that does not move for as long as the yarn is alive.
+#[cfg(feature = "stable_deref_trait")]
+unsafe impl<Buf> stable_deref_trait::StableDeref for StableYarn<'_, Buf> where
+  Buf: crate::Buf + ?Sized
+{
+}
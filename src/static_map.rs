@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A lookup table from static yarns to values, built at compile time.
+///
+/// Tables are declared with [`static_yarn_map!()`], which sorts the keys
+/// during constant evaluation, so a lookup is a binary search and nothing
+/// needs to be set up when the program starts.
+///
+/// ```
+/// # use byteyarn::*;
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Token { Fn, Let, If, Else }
+///
+/// static_yarn_map! {
+///   static KEYWORDS: StaticYarnMap<Token, str> = {
+///     "let" => Token::Let,
+///     "fn" => Token::Fn,
+///     "if" => Token::If,
+///     "else" => Token::Else,
+///   };
+/// }
+///
+/// assert_eq!(KEYWORDS.get("fn"), Some(&Token::Fn));
+/// assert_eq!(KEYWORDS.get("loop"), None);
+///
+/// let keys = KEYWORDS.keys().collect::<Vec<_>>();
+/// assert_eq!(keys, ["else", "fn", "if", "let"]);
+/// ```
+pub struct StaticYarnMap<V: 'static, Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  entries: &'static [(YarnRef<'static, Buf>, V)],
+  order: &'static [usize],
+}
+
+impl<V, Buf> StaticYarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  #[doc(hidden)]
+  pub const fn __new(
+    entries: &'static [(YarnRef<'static, Buf>, V)],
+    order: &'static [usize],
+  ) -> Self {
+    Self { entries, order }
+  }
+
+  /// Returns the number of entries in this table.
+  pub const fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  /// Checks whether this table is empty.
+  pub const fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// Looks up the value associated with `key`.
+  pub fn get(&self, key: impl AsRef<Buf>) -> Option<&'static V> {
+    self.get_key_value(key).map(|(_, v)| v)
+  }
+
+  /// Checks whether this table contains `key`.
+  pub fn contains_key(&self, key: impl AsRef<Buf>) -> bool {
+    self.get(key).is_some()
+  }
+
+  /// Looks up the entry for `key`, returning the key as stored in this table.
+  pub fn get_key_value(
+    &self,
+    key: impl AsRef<Buf>,
+  ) -> Option<(YarnRef<'static, Buf>, &'static V)> {
+    let key = YarnRef::buf2raw(key.as_ref());
+    let idx = self
+      .order
+      .binary_search_by(|&i| self.entries[i].0.as_bytes().cmp(key))
+      .ok()?;
+
+    let (k, v) = &self.entries[self.order[idx]];
+    Some((*k, v))
+  }
+
+  /// Returns an iterator over the entries of this table, sorted by key.
+  pub fn iter(
+    &self,
+  ) -> impl Iterator<Item = (YarnRef<'static, Buf>, &'static V)> + '_ {
+    let entries = self.entries;
+    self
+      .order
+      .iter()
+      .map(move |&i| (entries[i].0, &entries[i].1))
+  }
+
+  /// Returns an iterator over the keys of this table, in sorted order.
+  pub fn keys(&self) -> impl Iterator<Item = YarnRef<'static, Buf>> + '_ {
+    self.iter().map(|(k, _)| k)
+  }
+
+  /// Returns an iterator over the values of this table, sorted by key.
+  pub fn values(&self) -> impl Iterator<Item = &'static V> + '_ {
+    self.iter().map(|(_, v)| v)
+  }
+}
+
+impl<V: fmt::Debug, Buf> fmt::Debug for StaticYarnMap<V, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+/// The type of a table's entries, as written out by [`static_yarn_map!()`].
+#[doc(hidden)]
+pub type Entries<V, Buf = [u8]> = [(YarnRef<'static, Buf>, V)];
+
+/// Computes the order in which to visit `entries` so that their keys are
+/// sorted, panicking (and thus failing compilation) if any key is repeated.
+#[doc(hidden)]
+pub const fn sort_order<V, Buf, const N: usize>(
+  entries: &Entries<V, Buf>,
+) -> [usize; N]
+where
+  Buf: crate::Buf + ?Sized,
+{
+  assert!(entries.len() == N);
+
+  let mut order = [0; N];
+  let mut i = 0;
+  while i < N {
+    // Insertion sort; these tables are small, and this only runs at compile
+    // time.
+    let key = entries[i].0.as_bytes();
+    let mut j = i;
+    while j > 0 {
+      let prev = entries[order[j - 1]].0.as_bytes();
+      match cmp_bytes(prev, key) {
+        0 => panic!("duplicate key in static_yarn_map!()"),
+        c if c < 0 => break,
+        _ => {}
+      }
+      order[j] = order[j - 1];
+      j -= 1;
+    }
+    order[j] = i;
+    i += 1;
+  }
+  order
+}
+
+/// Compares two byte strings lexicographically, in a `const` context.
+const fn cmp_bytes(a: &[u8], b: &[u8]) -> i8 {
+  let mut i = 0;
+  while i < a.len() && i < b.len() {
+    if a[i] != b[i] {
+      return if a[i] < b[i] { -1 } else { 1 };
+    }
+    i += 1;
+  }
+
+  if a.len() == b.len() {
+    0
+  } else if a.len() < b.len() {
+    -1
+  } else {
+    1
+  }
+}
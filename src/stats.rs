@@ -0,0 +1,105 @@
+//! Process-wide counters of what yarns do with memory.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use crate::raw::RawYarn;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A snapshot of process-wide yarn statistics, as returned by [`stats()`].
+///
+/// These are intended for checking assumptions about a workload in
+/// production, such as "most of our strings are small enough to inline". The
+/// counters are only approximately synchronized with each other, since they
+/// are updated independently.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+  /// The number of inlined yarns constructed.
+  pub constructed_small: usize,
+  /// The number of yarns constructed owning a heap allocation.
+  pub constructed_heap: usize,
+
+  /// The number of heap allocations taken over or made by yarns.
+  pub heap_allocs: usize,
+  /// The total size of those allocations, in bytes.
+  pub heap_bytes: usize,
+
+  /// The number of times a yarn's contents were copied, either into an
+  /// inlined yarn or into a new heap allocation.
+  pub copies: usize,
+  /// The total number of bytes copied.
+  pub copied_bytes: usize,
+}
+
+static CONSTRUCTED_SMALL: AtomicUsize = AtomicUsize::new(0);
+static CONSTRUCTED_HEAP: AtomicUsize = AtomicUsize::new(0);
+static HEAP_ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static HEAP_BYTES: AtomicUsize = AtomicUsize::new(0);
+static COPIES: AtomicUsize = AtomicUsize::new(0);
+static COPIED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a snapshot of the process-wide yarn statistics.
+///
+/// Constructions are counted by the storage class the new yarn starts out
+/// with, whether it is a [`YarnBox`] or a [`YarnRef`], and regardless of
+/// whether it is later dropped, leaked, or forgotten.
+///
+/// Borrowed and static yarns are not counted: they are built by `const fn`s,
+/// such as [`YarnBox::new()`] and [`YarnBox::from_static()`], which cannot
+/// update a counter, even when called at runtime. Small yarns built by
+/// `const fn`s, such as [`YarnBox::from_char()`], are not counted either.
+///
+/// ```
+/// # use byteyarn::*;
+/// let before = byteyarn::stats();
+///
+/// let yarn = Yarn::from_string("a very long heap-allocated string".into());
+/// let copy = yarn.clone();
+/// let small = yarn!("{}", 42);
+/// let bytes = ByteYarn::from_vec(vec![0; 64]);
+/// std::mem::forget((yarn, copy, small, bytes));
+///
+/// let after = byteyarn::stats();
+/// assert_eq!(after.constructed_heap - before.constructed_heap, 3);
+/// assert_eq!(after.constructed_small - before.constructed_small, 1);
+/// assert_eq!(after.heap_allocs - before.heap_allocs, 3);
+/// assert_eq!(after.copies - before.copies, 1);
+/// assert_eq!(after.copied_bytes - before.copied_bytes, 33);
+/// ```
+pub fn stats() -> Stats {
+  let load = |n: &AtomicUsize| n.load(Ordering::Relaxed);
+  Stats {
+    constructed_small: load(&CONSTRUCTED_SMALL),
+    constructed_heap: load(&CONSTRUCTED_HEAP),
+    heap_allocs: load(&HEAP_ALLOCS),
+    heap_bytes: load(&HEAP_BYTES),
+    copies: load(&COPIES),
+    copied_bytes: load(&COPIED_BYTES),
+  }
+}
+
+/// Records that a yarn with the given `RawYarn` tag was constructed.
+///
+/// Only small and heap yarns are counted; see [`stats()`].
+pub(crate) fn record_new(kind: u8) {
+  let counter = match kind {
+    RawYarn::SMALL => &CONSTRUCTED_SMALL,
+    RawYarn::HEAP => &CONSTRUCTED_HEAP,
+    _ => return,
+  };
+  counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records that a yarn took ownership of a heap allocation of `len` bytes.
+pub(crate) fn record_heap(len: usize) {
+  HEAP_ALLOCS.fetch_add(1, Ordering::Relaxed);
+  HEAP_BYTES.fetch_add(len, Ordering::Relaxed);
+}
+
+/// Records that `len` bytes were copied into a new yarn.
+pub(crate) fn record_copy(len: usize) {
+  COPIES.fetch_add(1, Ordering::Relaxed);
+  COPIED_BYTES.fetch_add(len, Ordering::Relaxed);
+}
@@ -0,0 +1,62 @@
+//! Integration with the `string_cache` crate.
+
+use string_cache::Atom;
+use string_cache::StaticAtomSet;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Static, Buf> From<Atom<Static>> for YarnBox<'_, Buf>
+where
+  Static: StaticAtomSet,
+  Buf: crate::Buf + ?Sized,
+{
+  /// Converts an `Atom` into a yarn.
+  ///
+  /// Atoms are reference-counted or interned, so long atoms must be copied;
+  /// short ones are inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use string_cache::DefaultAtom;
+  ///
+  /// let yarn = Yarn::from(DefaultAtom::from("href"));
+  /// assert_eq!(yarn, "href");
+  /// ```
+  fn from(a: Atom<Static>) -> Self {
+    unsafe {
+      // SAFETY: both [u8] and str can be safely constructed from a str, and
+      // copy_slice() returns a uniquely-owned raw yarn.
+      YarnBox::from_raw(RawYarn::copy_slice(a.as_bytes()))
+    }
+  }
+}
+
+impl<Static> From<YarnBox<'_, str>> for Atom<Static>
+where
+  Static: StaticAtomSet,
+{
+  /// Interns a yarn as an `Atom`.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use string_cache::DefaultAtom;
+  ///
+  /// let atom = DefaultAtom::from(yarn!("div"));
+  /// assert_eq!(atom, DefaultAtom::from("div"));
+  /// ```
+  fn from(y: YarnBox<str>) -> Self {
+    Atom::from(y.as_str())
+  }
+}
+
+impl<Static> From<YarnRef<'_, str>> for Atom<Static>
+where
+  Static: StaticAtomSet,
+{
+  /// Interns a yarn as an `Atom`.
+  fn from(y: YarnRef<str>) -> Self {
+    Atom::from(y.as_str())
+  }
+}
@@ -0,0 +1,165 @@
+//! Integration with the `strsim` crate.
+//!
+//! String yarns are compared by `char`, and byte yarns by byte.
+
+use std::slice;
+use std::str;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns the Levenshtein distance between this yarn and `that`: the
+  /// minimum number of insertions, deletions, and substitutions needed to turn
+  /// one into the other.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = YarnRef::<str>::new("kitten");
+  /// assert_eq!(yarn.levenshtein("sitting"), 3);
+  /// assert_eq!(yarn.levenshtein("kitten"), 0);
+  ///
+  /// // One char, but two bytes.
+  /// assert_eq!(YarnRef::<str>::new("é").levenshtein("e"), 1);
+  /// assert_eq!(YarnRef::<[u8]>::new("é".as_bytes()).levenshtein(b"e"), 2);
+  /// ```
+  pub fn levenshtein(&self, that: impl AsRef<Buf>) -> usize {
+    let (a, b) = (self.as_bytes(), YarnRef::buf2raw(that.as_ref()));
+    match as_strs::<Buf>(a, b) {
+      Some((a, b)) => strsim::levenshtein(a, b),
+      None => strsim::generic_levenshtein(&Bytes(a), &Bytes(b)),
+    }
+  }
+
+  /// Returns the Jaro-Winkler similarity between this yarn and `that`, from
+  /// `0.0` for no similarity to `1.0` for identical yarns.
+  ///
+  /// Jaro-Winkler favors yarns that share a prefix, which makes it a good fit
+  /// for short strings like names and identifiers.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = YarnRef::<str>::new("martha");
+  /// assert!(yarn.jaro_winkler("marhta") > 0.96);
+  /// assert_eq!(yarn.jaro_winkler("martha"), 1.0);
+  /// assert_eq!(yarn.jaro_winkler("xyz"), 0.0);
+  /// ```
+  pub fn jaro_winkler(&self, that: impl AsRef<Buf>) -> f64 {
+    let (a, b) = (self.as_bytes(), YarnRef::buf2raw(that.as_ref()));
+    match as_strs::<Buf>(a, b) {
+      Some((a, b)) => strsim::jaro_winkler(a, b),
+      None => strsim::generic_jaro_winkler(&Bytes(a), &Bytes(b)),
+    }
+  }
+
+  /// Returns the candidate closest to this yarn, for "did you mean?"
+  /// suggestions.
+  ///
+  /// The closest candidate is the one with the smallest
+  /// [Levenshtein distance][YarnRef::levenshtein], with ties going to the
+  /// earliest candidate. Candidates more than a third of this yarn's length
+  /// away (but at least one edit) are not considered close, so if none are
+  /// within that distance, this returns `None`.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut names = Interner::<str>::new();
+  /// for name in ["length", "width", "height"] {
+  ///   names.intern(name);
+  /// }
+  ///
+  /// let candidates = || names.iter().map(|(_, name)| name);
+  /// let typo = YarnRef::<str>::new("lenght");
+  /// assert_eq!(typo.closest_match(candidates()).unwrap(), "length");
+  ///
+  /// let nonsense = YarnRef::<str>::new("volume");
+  /// assert!(nonsense.closest_match(candidates()).is_none());
+  /// ```
+  pub fn closest_match<T: AsRef<Buf>>(
+    &self,
+    candidates: impl IntoIterator<Item = T>,
+  ) -> Option<T> {
+    let len = match as_str::<Buf>(self.as_bytes()) {
+      Some(s) => s.chars().count(),
+      None => self.len(),
+    };
+    let max = (len / 3).max(1);
+
+    let mut best = None;
+    for candidate in candidates {
+      let dist = self.levenshtein(&candidate);
+      if dist <= max && best.as_ref().map_or(true, |&(d, _)| dist < d) {
+        best = Some((dist, candidate));
+      }
+    }
+    best.map(|(_, c)| c)
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns the Levenshtein distance between this yarn and `that`.
+  ///
+  /// See [`YarnRef::levenshtein()`].
+  pub fn levenshtein(&self, that: impl AsRef<Buf>) -> usize {
+    self.as_ref().levenshtein(that)
+  }
+
+  /// Returns the Jaro-Winkler similarity between this yarn and `that`.
+  ///
+  /// See [`YarnRef::jaro_winkler()`].
+  pub fn jaro_winkler(&self, that: impl AsRef<Buf>) -> f64 {
+    self.as_ref().jaro_winkler(that)
+  }
+
+  /// Returns the candidate closest to this yarn.
+  ///
+  /// See [`YarnRef::closest_match()`].
+  pub fn closest_match<T: AsRef<Buf>>(
+    &self,
+    candidates: impl IntoIterator<Item = T>,
+  ) -> Option<T> {
+    self.as_ref().closest_match(candidates)
+  }
+}
+
+/// Views `a` and `b` as strings if `Buf` is `str`.
+fn as_strs<'a, Buf>(a: &'a [u8], b: &'a [u8]) -> Option<(&'a str, &'a str)>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  Some((as_str::<Buf>(a)?, as_str::<Buf>(b)?))
+}
+
+/// Views `bytes` as a string if `Buf` is `str`.
+fn as_str<Buf>(bytes: &[u8]) -> Option<&str>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  if !<Buf as crate::z::Sealed>::UTF8 {
+    return None;
+  }
+
+  unsafe {
+    // SAFETY: If Buf is UTF-8, so are the bytes.
+    Some(str::from_utf8_unchecked(bytes))
+  }
+}
+
+/// A byte slice that `strsim`'s generic functions can iterate over by
+/// reference.
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> IntoIterator for &Bytes<'a> {
+  type Item = u8;
+  type IntoIter = std::iter::Copied<slice::Iter<'a, u8>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.0.iter().copied()
+  }
+}
@@ -0,0 +1,365 @@
+use std::fmt;
+use std::ops::Index;
+use std::ops::IndexMut;
+
+use crate::Symbol;
+
+#[cfg(doc)]
+use crate::*;
+
+/// A map from [`Symbol`]s to values, stored densely by symbol index.
+///
+/// An [`Interner`] assigns symbols consecutively from zero, so a `Vec` indexed
+/// by symbol makes a compact side table, with constant-time access and no
+/// hashing. This is the usual way for compiler passes to attach data, such as
+/// types or definitions, to interned names.
+///
+/// The map's storage grows to fit the largest symbol inserted into it, so it
+/// is best used with symbols from a single interner.
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut names = Interner::<str>::new();
+/// let x = names.intern("x");
+/// let y = names.intern("y");
+/// let z = names.intern("z");
+///
+/// let mut types = SymbolMap::new();
+/// types.insert(x, "i32");
+/// types.insert(z, "bool");
+///
+/// assert_eq!(types.get(x), Some(&"i32"));
+/// assert_eq!(types.get(y), None);
+/// assert_eq!(types[z], "bool");
+///
+/// let entries = types.iter().collect::<Vec<_>>();
+/// assert_eq!(entries, [(x, &"i32"), (z, &"bool")]);
+/// assert_eq!(names.resolve(entries[1].0), "z");
+/// ```
+pub struct SymbolMap<V> {
+  slots: Vec<Option<V>>,
+  len: usize,
+}
+
+impl<V> SymbolMap<V> {
+  /// Returns a new, empty map.
+  pub const fn new() -> Self {
+    Self {
+      slots: Vec::new(),
+      len: 0,
+    }
+  }
+
+  /// Returns a new, empty map with room for symbols with indices less than
+  /// `capacity`, such as every symbol from an interner of that length.
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      slots: Vec::with_capacity(capacity),
+      len: 0,
+    }
+  }
+
+  /// Returns the number of entries in this map.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Checks whether this map is empty.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Removes every entry from this map.
+  pub fn clear(&mut self) {
+    self.slots.clear();
+    self.len = 0;
+  }
+
+  /// Inserts a value for `sym`, returning the value previously associated
+  /// with it, if any.
+  pub fn insert(&mut self, sym: Symbol, value: V) -> Option<V> {
+    let old = self.slot(sym).replace(value);
+    if old.is_none() {
+      self.len += 1;
+    }
+    old
+  }
+
+  /// Returns the value for `sym`, inserting one computed by `f` if there is
+  /// none yet.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut interner = Interner::<str>::new();
+  /// let mut uses = SymbolMap::<Vec<usize>>::new();
+  /// for (line, name) in ["a", "b", "a"].into_iter().enumerate() {
+  ///   let sym = interner.intern(name);
+  ///   uses.get_or_insert_with(sym, Vec::new).push(line);
+  /// }
+  ///
+  /// assert_eq!(uses[interner.get("a").unwrap()], [0, 2]);
+  /// ```
+  pub fn get_or_insert_with(
+    &mut self,
+    sym: Symbol,
+    f: impl FnOnce() -> V,
+  ) -> &mut V {
+    if !self.contains_key(sym) {
+      self.insert(sym, f());
+    }
+    self.get_mut(sym).unwrap()
+  }
+
+  /// Looks up the value for `sym`.
+  pub fn get(&self, sym: Symbol) -> Option<&V> {
+    self.slots.get(sym.index())?.as_ref()
+  }
+
+  /// Looks up the value for `sym`, mutably.
+  pub fn get_mut(&mut self, sym: Symbol) -> Option<&mut V> {
+    self.slots.get_mut(sym.index())?.as_mut()
+  }
+
+  /// Checks whether this map has a value for `sym`.
+  pub fn contains_key(&self, sym: Symbol) -> bool {
+    self.get(sym).is_some()
+  }
+
+  /// Removes the value for `sym`, returning it, if it was present.
+  pub fn remove(&mut self, sym: Symbol) -> Option<V> {
+    let old = self.slots.get_mut(sym.index())?.take();
+    if old.is_some() {
+      self.len -= 1;
+    }
+    old
+  }
+
+  /// Returns an iterator over the entries of this map, in symbol order.
+  pub fn iter(&self) -> impl Iterator<Item = (Symbol, &V)> + '_ {
+    self
+      .slots
+      .iter()
+      .enumerate()
+      .filter_map(|(i, v)| Some((Symbol(i as u32), v.as_ref()?)))
+  }
+
+  /// Returns an iterator over the entries of this map, in symbol order, with
+  /// mutable references to the values.
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (Symbol, &mut V)> + '_ {
+    self
+      .slots
+      .iter_mut()
+      .enumerate()
+      .filter_map(|(i, v)| Some((Symbol(i as u32), v.as_mut()?)))
+  }
+
+  /// Returns an iterator over the symbols in this map, in order.
+  pub fn keys(&self) -> impl Iterator<Item = Symbol> + '_ {
+    self.iter().map(|(k, _)| k)
+  }
+
+  /// Returns an iterator over the values of this map, in symbol order.
+  pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+    self.slots.iter().flatten()
+  }
+
+  /// Returns the slot for `sym`, growing the map to fit it if necessary.
+  fn slot(&mut self, sym: Symbol) -> &mut Option<V> {
+    let idx = sym.index();
+    if idx >= self.slots.len() {
+      self.slots.resize_with(idx + 1, || None);
+    }
+    &mut self.slots[idx]
+  }
+}
+
+impl<V> Index<Symbol> for SymbolMap<V> {
+  type Output = V;
+
+  fn index(&self, sym: Symbol) -> &V {
+    self.get(sym).expect("symbol not present in SymbolMap")
+  }
+}
+
+impl<V> IndexMut<Symbol> for SymbolMap<V> {
+  fn index_mut(&mut self, sym: Symbol) -> &mut V {
+    self.get_mut(sym).expect("symbol not present in SymbolMap")
+  }
+}
+
+impl<V> FromIterator<(Symbol, V)> for SymbolMap<V> {
+  fn from_iter<I: IntoIterator<Item = (Symbol, V)>>(iter: I) -> Self {
+    let mut map = Self::new();
+    map.extend(iter);
+    map
+  }
+}
+
+impl<V> Extend<(Symbol, V)> for SymbolMap<V> {
+  fn extend<I: IntoIterator<Item = (Symbol, V)>>(&mut self, iter: I) {
+    for (sym, v) in iter {
+      self.insert(sym, v);
+    }
+  }
+}
+
+impl<V> Default for SymbolMap<V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<V: Clone> Clone for SymbolMap<V> {
+  fn clone(&self) -> Self {
+    Self {
+      slots: self.slots.clone(),
+      len: self.len,
+    }
+  }
+}
+
+impl<V: PartialEq> PartialEq for SymbolMap<V> {
+  fn eq(&self, that: &Self) -> bool {
+    self.len == that.len && self.iter().eq(that.iter())
+  }
+}
+
+impl<V: Eq> Eq for SymbolMap<V> {}
+
+impl<V: fmt::Debug> fmt::Debug for SymbolMap<V> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+/// A set of [`Symbol`]s, stored as a bitset indexed by symbol.
+///
+/// See [`SymbolMap`].
+///
+/// ```
+/// # use byteyarn::*;
+/// let mut names = Interner::<str>::new();
+/// let [a, b, c] = ["a", "b", "c"].map(|n| names.intern(n));
+///
+/// let mut live = SymbolSet::new();
+/// assert!(live.insert(a));
+/// assert!(live.insert(c));
+/// assert!(!live.insert(a));
+///
+/// assert!(live.contains(c));
+/// assert!(!live.contains(b));
+/// assert_eq!(live.iter().collect::<Vec<_>>(), [a, c]);
+/// ```
+#[derive(Clone, Default)]
+pub struct SymbolSet {
+  words: Vec<u64>,
+  len: usize,
+}
+
+impl SymbolSet {
+  /// Returns a new, empty set.
+  pub const fn new() -> Self {
+    Self {
+      words: Vec::new(),
+      len: 0,
+    }
+  }
+
+  /// Returns the number of symbols in this set.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Checks whether this set is empty.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Removes every symbol from this set.
+  pub fn clear(&mut self) {
+    self.words.clear();
+    self.len = 0;
+  }
+
+  /// Adds `sym` to this set, returning whether it was newly added.
+  pub fn insert(&mut self, sym: Symbol) -> bool {
+    let (word, bit) = Self::locate(sym);
+    if word >= self.words.len() {
+      self.words.resize(word + 1, 0);
+    }
+
+    let added = self.words[word] & bit == 0;
+    self.words[word] |= bit;
+    self.len += added as usize;
+    added
+  }
+
+  /// Checks whether `sym` is in this set.
+  pub fn contains(&self, sym: Symbol) -> bool {
+    let (word, bit) = Self::locate(sym);
+    self.words.get(word).is_some_and(|w| w & bit != 0)
+  }
+
+  /// Removes `sym` from this set, returning whether it was present.
+  pub fn remove(&mut self, sym: Symbol) -> bool {
+    let (word, bit) = Self::locate(sym);
+    let Some(w) = self.words.get_mut(word) else {
+      return false;
+    };
+
+    let removed = *w & bit != 0;
+    *w &= !bit;
+    self.len -= removed as usize;
+    removed
+  }
+
+  /// Returns an iterator over the symbols in this set, in order.
+  pub fn iter(&self) -> impl Iterator<Item = Symbol> + '_ {
+    self.words.iter().enumerate().flat_map(|(i, &w)| {
+      let mut w = w;
+      std::iter::from_fn(move || {
+        if w == 0 {
+          return None;
+        }
+        let bit = w.trailing_zeros();
+        w &= w - 1;
+        Some(Symbol(i as u32 * 64 + bit))
+      })
+    })
+  }
+
+  /// Returns the index of the word holding `sym`, and its bit in that word.
+  fn locate(sym: Symbol) -> (usize, u64) {
+    (sym.index() / 64, 1 << (sym.index() % 64))
+  }
+}
+
+impl FromIterator<Symbol> for SymbolSet {
+  fn from_iter<I: IntoIterator<Item = Symbol>>(iter: I) -> Self {
+    let mut set = Self::new();
+    set.extend(iter);
+    set
+  }
+}
+
+impl Extend<Symbol> for SymbolSet {
+  fn extend<I: IntoIterator<Item = Symbol>>(&mut self, iter: I) {
+    for sym in iter {
+      self.insert(sym);
+    }
+  }
+}
+
+impl PartialEq for SymbolSet {
+  fn eq(&self, that: &Self) -> bool {
+    self.len == that.len && self.iter().eq(that.iter())
+  }
+}
+
+impl Eq for SymbolSet {}
+
+impl fmt::Debug for SymbolSet {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
@@ -0,0 +1,235 @@
+//! Text-layout utilities for string yarns.
+
+use std::iter;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl YarnBox<'_, str> {
+  /// Removes the leading whitespace common to every line of this yarn.
+  ///
+  /// Lines consisting only of spaces and tabs do not count towards the common
+  /// margin, and lose as much of it as they have. Tabs and spaces are not
+  /// considered equal, so a line indented with a tab and one indented with
+  /// spaces have no margin in common.
+  ///
+  /// If there is no common margin, this yarn is returned untouched.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("    fn main() {{\n      hello();\n\n    }}\n");
+  /// assert_eq!(yarn.dedent(), "fn main() {\n  hello();\n\n}\n");
+  /// ```
+  pub fn dedent(self) -> Self {
+    let mut margin = None::<&str>;
+    for line in self.as_str().split_inclusive('\n') {
+      if is_blank(line) {
+        continue;
+      }
+
+      let rest = line.trim_start_matches([' ', '\t']);
+
+      let indent = &line[..line.len() - rest.len()];
+      margin = Some(match margin {
+        Some(m) => &m[..common_prefix(m, indent)],
+        None => indent,
+      });
+    }
+
+    let margin = margin.unwrap_or_default();
+    if margin.is_empty() {
+      return self;
+    }
+
+    let mut out = String::with_capacity(self.len());
+    for line in self.as_str().split_inclusive('\n') {
+      // The margin is all ASCII, so this is on a character boundary.
+      out.push_str(&line[common_prefix(margin, line)..]);
+    }
+    Self::from_string(out)
+  }
+
+  /// Adds `prefix` to the start of every line of this yarn that does not
+  /// consist only of spaces and tabs, the same lines that [`YarnBox::dedent()`]
+  /// takes into account.
+  ///
+  /// If no lines are changed, this yarn is returned untouched.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("fn main() {{\n  hello();\n\n}}\n");
+  /// assert_eq!(yarn.indent("// "), "// fn main() {\n//   hello();\n\n// }\n");
+  ///
+  /// let nbsp = Yarn::from_static("a\n \t\n\u{a0}\n");
+  /// assert_eq!(nbsp.indent("> "), "> a\n \t\n> \u{a0}\n");
+  /// ```
+  pub fn indent(self, prefix: &str) -> Self {
+    if prefix.is_empty() || self.as_str().split('\n').all(is_blank) {
+      return self;
+    }
+
+    let mut out = String::with_capacity(self.len());
+    for line in self.as_str().split_inclusive('\n') {
+      if !is_blank(line) {
+        out.push_str(prefix);
+      }
+      out.push_str(line);
+    }
+    Self::from_string(out)
+  }
+
+  /// Replaces each tab in this yarn with enough spaces to reach the next
+  /// column that is a multiple of `width`.
+  ///
+  /// Columns are counted in `char`s, starting over after each line break. A
+  /// `width` of zero deletes tabs.
+  ///
+  /// If this yarn contains no tabs, it is returned untouched.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("a\tbc\td\n\tλ\te");
+  /// assert_eq!(yarn.expand_tabs(4), "a   bc  d\n    λ   e");
+  /// ```
+  pub fn expand_tabs(self, width: usize) -> Self {
+    if !self.as_str().contains('\t') {
+      return self;
+    }
+
+    let mut out = String::with_capacity(self.len());
+    let mut col = 0;
+    for c in self.as_str().chars() {
+      match c {
+        '\t' if width == 0 => {}
+        '\t' => {
+          let n = width - col % width;
+          out.extend(iter::repeat(' ').take(n));
+          col += n;
+        }
+        '\n' | '\r' => {
+          out.push(c);
+          col = 0;
+        }
+        _ => {
+          out.push(c);
+          col += 1;
+        }
+      }
+    }
+    Self::from_string(out)
+  }
+
+  /// Returns an iterator over the lines of this yarn, wrapped to `width`.
+  ///
+  /// See [`YarnRef::wrap()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = yarn!("the quick brown fox jumps over the lazy dog");
+  /// let lines = yarn.wrap(12).collect::<Vec<_>>();
+  /// assert_eq!(lines, ["the quick", "brown fox", "jumps over", "the lazy dog"]);
+  /// ```
+  pub fn wrap(&self, width: usize) -> Wrap {
+    self.as_ref().wrap(width)
+  }
+}
+
+impl<'a> YarnRef<'a, str> {
+  /// Returns an iterator over the lines of this yarn, wrapped to `width`.
+  ///
+  /// Lines are broken greedily at whitespace, so that each is at most `width`
+  /// `char`s long, unless it consists of a single word that is longer than
+  /// that. Existing line breaks are kept, and whitespace at the start and end
+  /// of each line is removed.
+  ///
+  /// The lines are subyarns of this one, so they do not copy unless this yarn
+  /// is inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = YarnRef::<str>::new("usage: yarn [options]\n\n  --help  print this message and exit\n");
+  /// let lines = yarn.wrap(16).collect::<Vec<_>>();
+  /// assert_eq!(lines, [
+  ///   "usage: yarn",
+  ///   "[options]",
+  ///   "",
+  ///   "--help  print",
+  ///   "this message and",
+  ///   "exit",
+  /// ]);
+  /// ```
+  pub fn wrap(self, width: usize) -> Wrap<'a> {
+    Wrap {
+      yarn: self,
+      pos: (!self.is_empty()).then_some(0),
+      width,
+    }
+  }
+}
+
+/// An iterator over the lines of a yarn, wrapped to some width.
+///
+/// See [`YarnRef::wrap()`].
+#[derive(Copy, Clone)]
+pub struct Wrap<'a> {
+  yarn: YarnRef<'a, str>,
+  pos: Option<usize>,
+  width: usize,
+}
+
+impl<'a> Iterator for Wrap<'a> {
+  type Item = YarnRef<'a, str>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let pos = self.pos?;
+    let yarn = self.yarn;
+    let s = yarn.as_str();
+    let line_end = s[pos..].find('\n').map_or(s.len(), |i| pos + i);
+
+    // If we don't stop mid-line, the next line starts after the line break,
+    // unless that is the end of the yarn.
+    self.pos = Some(line_end + 1).filter(|&next| next < s.len());
+
+    let (start, mut end) = next_word(s, pos, line_end).unwrap_or((pos, pos));
+    let mut cols = s[start..end].chars().count();
+    while let Some((word_start, word_end)) = next_word(s, end, line_end) {
+      let more = s[end..word_end].chars().count();
+      if cols + more > self.width {
+        self.pos = Some(word_start);
+        break;
+      }
+
+      cols += more;
+      end = word_end;
+    }
+
+    unsafe {
+      // SAFETY: start and end are the boundaries of words, which are on
+      // character boundaries.
+      Some(yarn.slice_unchecked(start..end))
+    }
+  }
+}
+
+/// Returns the byte range of the first word in `s[from..to]`.
+fn next_word(s: &str, from: usize, to: usize) -> Option<(usize, usize)> {
+  let text = &s[from..to];
+  let start = text.find(|c: char| !c.is_whitespace())?;
+  let len = text[start..]
+    .find(char::is_whitespace)
+    .unwrap_or(text.len() - start);
+  Some((from + start, from + start + len))
+}
+
+/// Checks whether `line` consists only of spaces and tabs, ignoring its line
+/// terminator.
+fn is_blank(line: &str) -> bool {
+  line
+    .bytes()
+    .all(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n'))
+}
+
+/// Returns the length of the longest common prefix of `a` and `b`, in bytes.
+fn common_prefix(a: &str, b: &str) -> usize {
+  a.bytes().zip(b.bytes()).take_while(|(a, b)| a == b).count()
+}
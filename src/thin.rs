@@ -0,0 +1,319 @@
+use std::alloc;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Deref;
+use std::ptr;
+use std::ptr::NonNull;
+use std::slice;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// A one-word yarn, which stores its length on the heap, next to its data.
+///
+/// This is for embedding strings in extremely size-sensitive structs, such as
+/// AST nodes, where even a two-word [`YarnBox`] is too much. The price is that
+/// a `ThinYarn` cannot inline or borrow: every non-empty `ThinYarn` owns a heap
+/// allocation, and reading its length requires a load.
+///
+/// A `ThinYarn` can be viewed as a [`YarnRef`] without copying, and converted
+/// to and from a [`YarnBox`] by copying.
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::mem::size_of;
+/// assert_eq!(size_of::<ThinYarn>(), size_of::<usize>());
+/// assert_eq!(size_of::<Option<ThinYarn>>(), size_of::<usize>());
+///
+/// let thin = ThinYarn::<str>::new("a thin little yarn");
+/// assert_eq!(thin.len(), 18);
+///
+/// let yarn: Yarn = thin.to_box();
+/// assert_eq!(yarn, thin);
+/// ```
+pub struct ThinYarn<Buf = [u8]>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  ptr: NonNull<Header>,
+  _ph: PhantomData<Box<Buf>>,
+}
+
+/// The header at the start of every `ThinYarn` allocation; the data follows
+/// immediately after it.
+#[repr(C)]
+struct Header {
+  len: usize,
+}
+
+/// The header used by every empty `ThinYarn`, which is never freed.
+const EMPTY: &Header = &Header { len: 0 };
+
+// ThinYarn does not expose &mut through &self.
+unsafe impl<Buf: crate::Buf + ?Sized> Send for ThinYarn<Buf> {}
+unsafe impl<Buf: crate::Buf + ?Sized> Sync for ThinYarn<Buf> {}
+
+impl<Buf> ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Returns an empty yarn, without allocating.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let empty = ThinYarn::<str>::empty();
+  /// assert_eq!(empty, "");
+  /// ```
+  pub const fn empty() -> Self {
+    Self {
+      ptr: unsafe {
+        // SAFETY: References are never null. EMPTY is never written through
+        // this pointer, since empty yarns are never freed.
+        NonNull::new_unchecked(EMPTY as *const Header as *mut Header)
+      },
+      _ph: PhantomData,
+    }
+  }
+
+  /// Returns a new yarn containing a copy of the given slice.
+  ///
+  /// This allocates unless `buf` is empty.
+  pub fn new(buf: &Buf) -> Self {
+    let bytes = YarnRef::<Buf>::buf2raw(buf);
+    if bytes.is_empty() {
+      return Self::empty();
+    }
+
+    let layout = Self::layout(bytes.len());
+    unsafe {
+      // SAFETY: layout is never zero-sized, since it includes the header.
+      let Some(ptr) = NonNull::new(alloc::alloc(layout).cast::<Header>()) else {
+        alloc::handle_alloc_error(layout)
+      };
+
+      // SAFETY: ptr was allocated with enough room for the header followed by
+      // bytes.len() bytes.
+      ptr.as_ptr().write(Header { len: bytes.len() });
+      ptr::copy_nonoverlapping(
+        bytes.as_ptr(),
+        ptr.as_ptr().add(1).cast::<u8>(),
+        bytes.len(),
+      );
+
+      Self {
+        ptr,
+        _ph: PhantomData,
+      }
+    }
+  }
+
+  /// Checks whether this yarn is empty.
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Returns the length of this yarn, in bytes.
+  pub fn len(&self) -> usize {
+    unsafe {
+      // SAFETY: self.ptr always points to a valid header.
+      self.ptr.as_ref().len
+    }
+  }
+
+  /// Converts this yarn into a slice.
+  pub fn as_slice(&self) -> &Buf {
+    unsafe {
+      // SAFETY: Our bytes were copied out of a Buf.
+      YarnRef::<Buf>::raw2buf(self.as_bytes())
+    }
+  }
+
+  /// Converts this yarn into a byte slice.
+  pub fn as_bytes(&self) -> &[u8] {
+    unsafe {
+      // SAFETY: The header is followed by self.len() initialized bytes; for
+      // the empty header, a zero-length slice just past it is fine.
+      let data = self.ptr.as_ptr().add(1).cast::<u8>();
+      slice::from_raw_parts(data, self.len())
+    }
+  }
+
+  /// Returns a reference to this yarn's contents as a [`YarnRef`], without
+  /// copying.
+  pub fn as_ref(&self) -> YarnRef<Buf> {
+    YarnRef::new(self.as_slice())
+  }
+
+  /// Copies this yarn into an immortal [`YarnBox`].
+  ///
+  /// This does not allocate if the contents are short enough for a
+  /// [`YarnBox`] to inline.
+  pub fn to_box<'a>(&self) -> YarnBox<'a, Buf> {
+    unsafe {
+      // SAFETY: copy_slice never aliases, and our bytes came out of a Buf.
+      YarnBox::from_raw(RawYarn::copy_slice(self.as_bytes()))
+    }
+  }
+
+  /// Returns the layout of an allocation holding `len` bytes of data.
+  fn layout(len: usize) -> alloc::Layout {
+    let size = mem::size_of::<Header>()
+      .checked_add(len)
+      .expect("yarns cannot be larger than the address space");
+    alloc::Layout::from_size_align(size, mem::align_of::<Header>())
+      .expect("yarns cannot be larger than the address space")
+  }
+}
+
+impl ThinYarn<str> {
+  /// Converts this yarn into a string slice.
+  pub fn as_str(&self) -> &str {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Deref for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type Target = Buf;
+  fn deref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Drop for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn drop(&mut self) {
+    let len = self.len();
+    if len == 0 {
+      return;
+    }
+
+    unsafe {
+      // SAFETY: Non-empty yarns always own an allocation of this layout, and
+      // nothing will touch it after this.
+      #[cfg(feature = "zeroize")]
+      ::zeroize::Zeroize::zeroize(slice::from_raw_parts_mut(
+        self.ptr.as_ptr().add(1).cast::<u8>(),
+        len,
+      ));
+
+      alloc::dealloc(self.ptr.as_ptr().cast(), Self::layout(len))
+    }
+  }
+}
+
+impl<Buf> Clone for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn clone(&self) -> Self {
+    Self::new(self.as_slice())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Debug for ThinYarn<Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Debug::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> fmt::Display for ThinYarn<Buf> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.as_ref(), f)
+  }
+}
+
+impl<Slice, Buf> PartialEq<Slice> for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn eq(&self, that: &Slice) -> bool {
+    self.as_slice() == that.as_ref()
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Eq for ThinYarn<Buf> {}
+
+impl<Slice, Buf> PartialOrd<Slice> for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  Slice: AsRef<Buf> + ?Sized,
+{
+  fn partial_cmp(&self, that: &Slice) -> Option<Ordering> {
+    self.as_slice().partial_cmp(that.as_ref())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Ord for ThinYarn<Buf> {
+  fn cmp(&self, that: &Self) -> Ordering {
+    self.as_slice().cmp(that.as_slice())
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Hash for ThinYarn<Buf> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.as_slice().hash(state)
+  }
+}
+
+impl<Buf: crate::Buf + ?Sized> Default for ThinYarn<Buf> {
+  fn default() -> Self {
+    Self::empty()
+  }
+}
+
+impl<Buf> AsRef<Buf> for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn as_ref(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> Borrow<Buf> for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn borrow(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> From<YarnBox<'_, Buf>> for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnBox<Buf>) -> Self {
+    Self::new(y.as_slice())
+  }
+}
+
+impl<Buf> From<YarnRef<'_, Buf>> for ThinYarn<Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: YarnRef<Buf>) -> Self {
+    Self::new(y.as_slice())
+  }
+}
+
+impl<Buf> From<ThinYarn<Buf>> for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn from(y: ThinYarn<Buf>) -> Self {
+    y.to_box()
+  }
+}
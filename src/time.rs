@@ -0,0 +1,67 @@
+//! Integration with the `time` crate.
+
+use time::error::Format;
+use time::format_description::well_known::Rfc3339;
+use time::formatting::Formattable;
+use time::OffsetDateTime;
+
+use crate::raw::RawYarn;
+use crate::YarnBox;
+
+impl YarnBox<'_, str> {
+  /// Returns a yarn containing a timestamp, rendered according to `format`.
+  ///
+  /// This writes the timestamp straight into a buffer that becomes the yarn,
+  /// rather than going through a `String`. Timestamps that are short enough,
+  /// such as bare dates, are inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use time::format_description::well_known::Iso8601;
+  /// use time::macros::format_description;
+  /// use time::OffsetDateTime;
+  ///
+  /// let ts = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+  ///
+  /// let iso = Yarn::from_timestamp(&ts, &Iso8601::DEFAULT).unwrap();
+  /// assert_eq!(iso, "2023-11-14T22:13:20.000000000Z");
+  ///
+  /// let date = format_description!("[year]-[month]-[day]");
+  /// let day = Yarn::from_timestamp(&ts, date).unwrap();
+  /// assert_eq!(day, "2023-11-14");
+  /// assert!(day.is_inlined());
+  /// ```
+  pub fn from_timestamp(
+    ts: &OffsetDateTime,
+    format: &(impl Formattable + ?Sized),
+  ) -> Result<Self, Format> {
+    let mut buf = Vec::new();
+    ts.format_into(&mut buf, format)?;
+    Ok(unsafe {
+      // SAFETY: time's formatters only ever write UTF-8.
+      YarnBox::from_raw(RawYarn::from_heap(buf.into()))
+    })
+  }
+
+  /// Returns a yarn containing a timestamp in RFC 3339 format, such as
+  /// `2023-11-14T22:13:20Z`.
+  ///
+  /// Fails if the timestamp cannot be represented in RFC 3339, such as if its
+  /// year is outside of `0..=9999`.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use time::OffsetDateTime;
+  /// use time::UtcOffset;
+  ///
+  /// let ts = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+  /// assert_eq!(Yarn::from_rfc3339(&ts).unwrap(), "2023-11-14T22:13:20Z");
+  ///
+  /// let tz = UtcOffset::from_hms(-5, 0, 0).unwrap();
+  /// let local = Yarn::from_rfc3339(&ts.to_offset(tz)).unwrap();
+  /// assert_eq!(local, "2023-11-14T17:13:20-05:00");
+  /// ```
+  pub fn from_rfc3339(ts: &OffsetDateTime) -> Result<Self, Format> {
+    Self::from_timestamp(ts, &Rfc3339)
+  }
+}
@@ -0,0 +1,136 @@
+//! Integration with the `tokio` crate.
+
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+
+use crate::ByteYarn;
+use crate::YarnBox;
+
+fn too_long(limit: usize) -> io::Error {
+  io::Error::new(
+    io::ErrorKind::InvalidData,
+    format!("payload exceeds limit of {limit} bytes"),
+  )
+}
+
+impl YarnBox<'static, [u8]> {
+  /// Reads `r` to the end into a new yarn, failing if it produces more than
+  /// `limit` bytes.
+  ///
+  /// At most `limit + 1` bytes are ever read from `r`, so an oversized payload
+  /// is rejected without buffering all of it. Short payloads are inlined.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+  /// let body = ByteYarn::from_async_reader(&b"hello"[..], 16).await.unwrap();
+  /// assert_eq!(body, b"hello");
+  ///
+  /// let err = ByteYarn::from_async_reader(&b"too long!"[..], 4).await.unwrap_err();
+  /// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+  /// # });
+  /// ```
+  pub async fn from_async_reader<R>(r: R, limit: usize) -> io::Result<ByteYarn>
+  where
+    R: AsyncRead + Unpin,
+  {
+    let mut buf = Vec::new();
+    r.take((limit as u64).saturating_add(1))
+      .read_to_end(&mut buf)
+      .await?;
+    if buf.len() > limit {
+      return Err(too_long(limit));
+    }
+
+    Ok(Self::from_vec(buf))
+  }
+}
+
+/// An [`AsyncWrite`] sink that collects everything written to it into a
+/// [`ByteYarn`].
+///
+/// Writes that would take the total past the writer's limit fail with
+/// [`io::ErrorKind::InvalidData`], and leave the writer's contents unchanged.
+///
+/// ```
+/// # use byteyarn::*;
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// use tokio::io::AsyncWriteExt;
+///
+/// let mut w = YarnWriter::with_limit(32);
+/// w.write_all(b"Answer: ").await.unwrap();
+/// w.write_all(b"42").await.unwrap();
+/// assert_eq!(w.into_yarn(), b"Answer: 42");
+/// # });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct YarnWriter {
+  buf: Vec<u8>,
+  limit: Option<usize>,
+}
+
+impl YarnWriter {
+  /// Returns a new, empty writer with no size limit.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns a new, empty writer that accepts at most `limit` bytes.
+  pub fn with_limit(limit: usize) -> Self {
+    Self {
+      buf: Vec::new(),
+      limit: Some(limit),
+    }
+  }
+
+  /// Returns the bytes written so far.
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.buf
+  }
+
+  /// Converts this writer into a yarn containing everything written to it.
+  pub fn into_yarn(self) -> ByteYarn {
+    ByteYarn::from_vec(self.buf)
+  }
+}
+
+impl AsyncWrite for YarnWriter {
+  fn poll_write(
+    self: Pin<&mut Self>,
+    _: &mut Context,
+    data: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    let this = self.get_mut();
+    if let Some(limit) = this.limit {
+      if this.buf.len() + data.len() > limit {
+        return Poll::Ready(Err(too_long(limit)));
+      }
+    }
+
+    this.buf.extend_from_slice(data);
+    Poll::Ready(Ok(data.len()))
+  }
+
+  fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn poll_shutdown(
+    self: Pin<&mut Self>,
+    _: &mut Context,
+  ) -> Poll<io::Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+}
+
+impl From<YarnWriter> for ByteYarn {
+  fn from(w: YarnWriter) -> Self {
+    w.into_yarn()
+  }
+}
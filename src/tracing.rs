@@ -0,0 +1,41 @@
+//! Integration with the `tracing` crate.
+//!
+//! Yarns already implement `Display`, so `%yarn` records them without
+//! allocating; the functions here record them as native string (or byte)
+//! values, instead, which subscribers can handle without formatting at all.
+
+use tracing::Value;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + Value + ?Sized,
+{
+  /// Returns this yarn as a `tracing` field value.
+  ///
+  /// String yarns are recorded with `record_str()`, and byte yarns with
+  /// `record_bytes()`.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let user = yarn!("user{}", 42);
+  /// tracing::info!(user = user.as_field_value(), "logged in");
+  /// ```
+  pub fn as_field_value(&self) -> &Buf {
+    self.as_slice()
+  }
+}
+
+impl<Buf> YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + Value + ?Sized,
+{
+  /// Returns this yarn as a `tracing` field value.
+  ///
+  /// See [`YarnBox::as_field_value()`].
+  pub fn as_field_value(&self) -> &Buf {
+    self.as_slice()
+  }
+}
@@ -1,7 +1,12 @@
 //! UTF-8 utilities not provided by the standard library.
 
+use std::fmt;
+use std::fmt::Write;
+use std::iter::FusedIterator;
 use std::str;
 
+use crate::YarnRef;
+
 #[cfg(doc)]
 use crate::*;
 
@@ -93,6 +98,247 @@ impl<'a> Iterator for Utf8Chunks<'a> {
   }
 }
 
+/// An iterator over the `char`s in a byte buffer, with invalid UTF-8 replaced
+/// by U+FFFD REPLACEMENT CHARACTER.
+///
+/// Each invalid sequence produces one replacement character, the same as
+/// [`String::from_utf8_lossy()`].
+///
+/// See [`ByteYarn::chars_lossy()`].
+#[derive(Clone)]
+pub struct CharsLossy<'a> {
+  chunks: Utf8Chunks<'a>,
+  chars: str::Chars<'a>,
+}
+
+impl<'a> CharsLossy<'a> {
+  pub(crate) fn new(buf: &'a [u8]) -> Self {
+    Self {
+      chunks: Utf8Chunks::new(buf),
+      chars: "".chars(),
+    }
+  }
+}
+
+impl Iterator for CharsLossy<'_> {
+  type Item = char;
+
+  fn next(&mut self) -> Option<char> {
+    loop {
+      if let Some(c) = self.chars.next() {
+        return Some(c);
+      }
+
+      match self.chunks.next()? {
+        Ok(utf8) => self.chars = utf8.chars(),
+        Err(_) => return Some(char::REPLACEMENT_CHARACTER),
+      }
+    }
+  }
+}
+
+impl FusedIterator for CharsLossy<'_> {}
+
+/// An iterator over the `char`s in a byte buffer and where they are, with
+/// invalid UTF-8 replaced by U+FFFD REPLACEMENT CHARACTER.
+///
+/// Each item is a byte offset, the `char` found there, and the number of bytes
+/// it was decoded from. For a replacement character, that is the length of the
+/// invalid sequence it stands for, rather than of its UTF-8 encoding.
+///
+/// See [`ByteYarn::char_indices_lossy()`].
+#[derive(Clone)]
+pub struct CharIndicesLossy<'a> {
+  chunks: Utf8Chunks<'a>,
+  chars: str::CharIndices<'a>,
+  /// The offset of the chunk `chars` is iterating over.
+  base: usize,
+  /// The offset of the next chunk.
+  offset: usize,
+}
+
+impl<'a> CharIndicesLossy<'a> {
+  pub(crate) fn new(buf: &'a [u8]) -> Self {
+    Self {
+      chunks: Utf8Chunks::new(buf),
+      chars: "".char_indices(),
+      base: 0,
+      offset: 0,
+    }
+  }
+}
+
+impl Iterator for CharIndicesLossy<'_> {
+  type Item = (usize, char, usize);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some((i, c)) = self.chars.next() {
+        return Some((self.base + i, c, c.len_utf8()));
+      }
+
+      let start = self.offset;
+      match self.chunks.next()? {
+        Ok(utf8) => {
+          self.base = start;
+          self.offset += utf8.len();
+          self.chars = utf8.char_indices();
+        }
+        Err(bytes) => {
+          self.offset += bytes.len();
+          return Some((start, char::REPLACEMENT_CHARACTER, bytes.len()));
+        }
+      }
+    }
+  }
+}
+
+impl FusedIterator for CharIndicesLossy<'_> {}
+
+/// A precomputed index for translating between byte offsets and character
+/// offsets in a string yarn.
+///
+/// [`Yarn::char_to_byte_index()`] and [`Yarn::byte_to_char_index()`] scan the
+/// yarn from the start. A `CharIndex` records the byte offset of every 64th
+/// character, so that each query only needs to scan a short stretch of the
+/// yarn. This is useful for editors and language servers, which must
+/// translate many positions within the same document.
+///
+/// ```
+/// # use byteyarn::*;
+/// let doc = yarn!("fn main() {{ println!(\"¡hola, señor!\"); }}");
+/// let index = doc.char_index();
+///
+/// assert_eq!(index.char_count(), 40);
+/// assert_eq!(index.char_to_byte(31), Some(32));
+/// assert_eq!(index.byte_to_char(32), Some(31));
+///
+/// // Not on a character boundary.
+/// assert_eq!(index.byte_to_char(33), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CharIndex<'a> {
+  yarn: YarnRef<'a, str>,
+  checkpoints: Vec<usize>,
+  chars: usize,
+}
+
+impl<'a> CharIndex<'a> {
+  const STRIDE: usize = 64;
+
+  pub(crate) fn new(yarn: YarnRef<'a, str>) -> Self {
+    let mut checkpoints = Vec::new();
+    let mut chars = 0;
+    for (i, _) in yarn.as_str().char_indices() {
+      if chars % Self::STRIDE == 0 {
+        checkpoints.push(i);
+      }
+      chars += 1;
+    }
+
+    Self {
+      yarn,
+      checkpoints,
+      chars,
+    }
+  }
+
+  /// Returns the yarn this index was built from.
+  pub fn yarn(&self) -> YarnRef<'a, str> {
+    self.yarn
+  }
+
+  /// Returns the number of characters in the indexed yarn.
+  pub fn char_count(&self) -> usize {
+    self.chars
+  }
+
+  /// Returns the byte offset of the `n`th character.
+  ///
+  /// Returns the length of the yarn if `n` is the number of characters in it,
+  /// and `None` if `n` is greater than that.
+  pub fn char_to_byte(&self, n: usize) -> Option<usize> {
+    if n >= self.chars {
+      return (n == self.chars).then_some(self.yarn.len());
+    }
+
+    let start = self.checkpoints[n / Self::STRIDE];
+    let (i, _) = self.yarn.as_str()[start..]
+      .char_indices()
+      .nth(n % Self::STRIDE)?;
+    Some(start + i)
+  }
+
+  /// Returns the number of characters before byte offset `idx`.
+  ///
+  /// Returns `None` if `idx` is out of bounds or not on a character boundary.
+  pub fn byte_to_char(&self, idx: usize) -> Option<usize> {
+    if !self.yarn.is_char_boundary(idx) {
+      return None;
+    }
+
+    let k = self
+      .checkpoints
+      .partition_point(|&c| c <= idx)
+      .saturating_sub(1);
+    let start = self.checkpoints.get(k).copied().unwrap_or(0);
+    Some(k * Self::STRIDE + count_chars(&self.yarn.as_bytes()[start..idx]))
+  }
+}
+
+/// Formats `bytes` as a quoted string, escaping invalid UTF-8 as `\xNN`.
+pub(crate) fn debug(bytes: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+  write!(f, "\"")?;
+  for chunk in Utf8Chunks::new(bytes) {
+    match chunk {
+      Ok(utf8) => write!(f, "{}", utf8.escape_debug())?,
+      Err(bytes) => {
+        for b in bytes {
+          write!(f, "\\x{:02X}", b)?;
+        }
+      }
+    }
+  }
+  write!(f, "\"")
+}
+
+/// Formats `bytes` as a string, replacing invalid UTF-8 with U+FFFD.
+pub(crate) fn display(bytes: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+  for chunk in Utf8Chunks::new(bytes) {
+    match chunk {
+      Ok(utf8) => f.write_str(utf8)?,
+      Err(..) => f.write_char(char::REPLACEMENT_CHARACTER)?,
+    }
+  }
+
+  Ok(())
+}
+
+/// Counts the characters in a UTF-8 buffer.
+///
+/// Every character has exactly one byte that is not a continuation byte
+/// (`0b10xx_xxxx`), so this counts those a word at a time, which compilers
+/// vectorize well.
+pub(crate) fn count_chars(bytes: &[u8]) -> usize {
+  const LSB: usize = usize::MAX / 0xff;
+  let is_cont = |b: &u8| (*b as i8) < -0x40;
+
+  let (head, words, tail) = unsafe {
+    // SAFETY: Any bit pattern is a valid usize.
+    bytes.align_to::<usize>()
+  };
+
+  let mut conts = head.iter().filter(|b| is_cont(b)).count()
+    + tail.iter().filter(|b| is_cont(b)).count();
+  for &w in words {
+    // A byte is a continuation byte iff its top bit is set and the one below
+    // it is clear.
+    conts += ((w >> 7) & !(w >> 6) & LSB).count_ones() as usize;
+  }
+
+  bytes.len() - conts
+}
+
 /// `const`-enabled UTF-8 encoding.
 ///
 /// Returns the encoded bytes in a static array, and the number of those bytes
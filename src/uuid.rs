@@ -0,0 +1,98 @@
+//! Integration with the `uuid` crate.
+
+use uuid::fmt::Hyphenated;
+use uuid::fmt::Simple;
+use uuid::Uuid;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+#[cfg(doc)]
+use crate::FatYarn;
+
+impl YarnBox<'_, str> {
+  /// Returns a yarn containing the hyphenated, lowercase form of a UUID, such
+  /// as `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+  ///
+  /// This produces the same string as `yarn!("{uuid}")`, but bypasses
+  /// `core::fmt` and encodes directly into the yarn's allocation.
+  ///
+  /// Textual UUIDs are too long to inline. The 16-byte binary form fits in a
+  /// [`FatYarn`] without allocating, if the UUID needs to be stored rather
+  /// than displayed.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use uuid::Uuid;
+  ///
+  /// let id = Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+  /// let yarn = Yarn::from_uuid(&id);
+  /// assert_eq!(yarn, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+  /// assert_eq!(yarn.to_uuid(), Ok(id));
+  ///
+  /// # #[cfg(target_pointer_width = "64")]
+  /// assert!(FatYarn::<[u8]>::inlined(id.as_bytes()).is_some());
+  /// ```
+  pub fn from_uuid(uuid: &Uuid) -> Self {
+    Self::from_ascii_fn(Hyphenated::LENGTH, |buf| {
+      uuid.hyphenated().encode_lower(buf);
+    })
+  }
+
+  /// Returns a yarn containing the simple, lowercase form of a UUID, which has
+  /// no hyphens, such as `67e5504410b1426f9247bb680e5fe0c8`.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use uuid::Uuid;
+  ///
+  /// let id = Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+  /// let yarn = Yarn::from_uuid_simple(&id);
+  /// assert_eq!(yarn, "67e5504410b1426f9247bb680e5fe0c8");
+  /// assert_eq!(yarn.to_uuid(), Ok(id));
+  /// ```
+  pub fn from_uuid_simple(uuid: &Uuid) -> Self {
+    Self::from_ascii_fn(Simple::LENGTH, |buf| {
+      uuid.simple().encode_lower(buf);
+    })
+  }
+
+  fn from_ascii_fn(len: usize, f: impl FnOnce(&mut [u8])) -> Self {
+    let bytes = YarnBox::<[u8]>::from_fn(len, f);
+    unsafe {
+      // SAFETY: UUID encoders only ever write ASCII.
+      YarnBox::from_raw(bytes.into_raw())
+    }
+  }
+}
+
+impl<Buf> YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Parses this yarn as a UUID, in any of the textual forms accepted by
+  /// [`Uuid::try_parse()`].
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let yarn = YarnRef::<[u8]>::new(b"urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8");
+  /// assert_eq!(yarn.to_uuid().unwrap().as_u128(), 0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+  ///
+  /// assert!(YarnRef::<str>::new("not a uuid").to_uuid().is_err());
+  /// ```
+  pub fn to_uuid(&self) -> Result<Uuid, uuid::Error> {
+    Uuid::try_parse_ascii(self.as_bytes())
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Parses this yarn as a UUID.
+  ///
+  /// See [`YarnRef::to_uuid()`].
+  pub fn to_uuid(&self) -> Result<Uuid, uuid::Error> {
+    Uuid::try_parse_ascii(self.as_bytes())
+  }
+}
@@ -0,0 +1,83 @@
+//! Integration with the `valuable` crate.
+
+use valuable::Listable;
+use valuable::Valuable;
+use valuable::Value;
+use valuable::Visit;
+
+use crate::YarnBox;
+use crate::YarnRef;
+
+/// String yarns are string values.
+///
+/// ```
+/// # use byteyarn::*;
+/// use valuable::Valuable;
+/// use valuable::Value;
+///
+/// let yarn = yarn!("tabby");
+/// assert!(matches!(yarn.as_value(), Value::String("tabby")));
+/// ```
+impl Valuable for YarnBox<'_, str> {
+  fn as_value(&self) -> Value {
+    Value::String(self.as_str())
+  }
+
+  fn visit(&self, visit: &mut dyn Visit) {
+    visit.visit_value(self.as_value())
+  }
+}
+
+/// String yarns are string values.
+impl Valuable for YarnRef<'_, str> {
+  fn as_value(&self) -> Value {
+    Value::String(self.as_str())
+  }
+
+  fn visit(&self, visit: &mut dyn Visit) {
+    visit.visit_value(self.as_value())
+  }
+}
+
+/// Byte yarns are lists of bytes, like `[u8]`.
+///
+/// ```
+/// # use byteyarn::*;
+/// use valuable::Valuable;
+/// use valuable::Value;
+///
+/// let yarn = ByteYarn::new(b"tabby");
+/// assert!(matches!(yarn.as_value(), Value::Listable(l) if l.size_hint() == (5, Some(5))));
+/// ```
+impl Valuable for YarnBox<'_, [u8]> {
+  fn as_value(&self) -> Value {
+    Value::Listable(self)
+  }
+
+  fn visit(&self, visit: &mut dyn Visit) {
+    u8::visit_slice(self.as_bytes(), visit)
+  }
+}
+
+impl Listable for YarnBox<'_, [u8]> {
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len(), Some(self.len()))
+  }
+}
+
+/// Byte yarns are lists of bytes, like `[u8]`.
+impl Valuable for YarnRef<'_, [u8]> {
+  fn as_value(&self) -> Value {
+    Value::Listable(self)
+  }
+
+  fn visit(&self, visit: &mut dyn Visit) {
+    u8::visit_slice(self.as_bytes(), visit)
+  }
+}
+
+impl Listable for YarnRef<'_, [u8]> {
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len(), Some(self.len()))
+  }
+}
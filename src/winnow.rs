@@ -0,0 +1,203 @@
+//! Integration with the `winnow` crate.
+//!
+//! Yarn references can be used directly as parser input; every slice a parser
+//! hands back is a subyarn that borrows from the original buffer, or is static
+//! if the original yarn was.
+
+use std::fmt;
+use std::ops::Range;
+
+use winnow::error::Needed;
+use winnow::stream::Compare;
+use winnow::stream::CompareResult;
+use winnow::stream::FindSlice;
+use winnow::stream::Offset;
+use winnow::stream::SliceLen;
+use winnow::stream::Stream;
+use winnow::stream::StreamIsPartial;
+use winnow::stream::UpdateSlice;
+
+use crate::elements::Decode;
+use crate::ElementIndices;
+use crate::YarnRef;
+
+/// Yarns can be consumed directly by `winnow` parsers.
+///
+/// The checkpoint type is the yarn itself, since yarn references are `Copy`.
+///
+/// ```
+/// # use byteyarn::*;
+/// use winnow::ascii::alpha1;
+/// use winnow::ascii::digit1;
+/// use winnow::prelude::*;
+///
+/// fn ident<'a>(input: &mut YarnRef<'a, str>) -> ModalResult<YarnRef<'a, str>> {
+///   (alpha1, digit1).take().parse_next(input)
+/// }
+///
+/// let mut input = YarnRef::from_static("cat9 = tabby");
+/// let word = ident(&mut input).unwrap();
+/// assert_eq!(word, "cat9");
+/// assert_eq!(input, " = tabby");
+///
+/// // Subyarns of static yarns are static too.
+/// assert!(input.immortalize().is_some());
+/// ```
+impl<'a, Buf> Stream for YarnRef<'a, Buf>
+where
+  Buf: Decode + ?Sized,
+  Buf::Item: fmt::Debug,
+{
+  type Token = Buf::Item;
+  type Slice = Self;
+  type IterOffsets = ElementIndices<'a, Buf>;
+  type Checkpoint = Self;
+
+  fn iter_offsets(&self) -> Self::IterOffsets {
+    ElementIndices::new(*self)
+  }
+
+  fn eof_offset(&self) -> usize {
+    self.len()
+  }
+
+  fn next_token(&mut self) -> Option<Self::Token> {
+    let (token, len) = Buf::decode(self.as_slice(), 0)?;
+    *self = self.split_at(len).1;
+    Some(token)
+  }
+
+  fn peek_token(&self) -> Option<Self::Token> {
+    Buf::decode(self.as_slice(), 0).map(|(token, _)| token)
+  }
+
+  fn offset_for<P>(&self, predicate: P) -> Option<usize>
+  where
+    P: Fn(Self::Token) -> bool,
+  {
+    self
+      .iter_offsets()
+      .find(|&(_, token)| predicate(token))
+      .map(|(offset, _)| offset)
+  }
+
+  fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+    let mut have = 0;
+    for (offset, _) in self.iter_offsets() {
+      if have == tokens {
+        return Ok(offset);
+      }
+      have += 1;
+    }
+
+    if have == tokens {
+      return Ok(self.len());
+    }
+    match Buf::BYTEWISE {
+      true => Err(Needed::new(tokens - have)),
+      false => Err(Needed::Unknown),
+    }
+  }
+
+  fn next_slice(&mut self, offset: usize) -> Self::Slice {
+    let (slice, next) = self.split_at(offset);
+    *self = next;
+    slice
+  }
+
+  fn peek_slice(&self, offset: usize) -> Self::Slice {
+    self.split_at(offset).0
+  }
+
+  fn checkpoint(&self) -> Self::Checkpoint {
+    *self
+  }
+
+  fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+    *self = *checkpoint;
+  }
+
+  fn raw(&self) -> &dyn fmt::Debug {
+    self
+  }
+}
+
+impl<Buf> StreamIsPartial for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  type PartialState = ();
+
+  fn complete(&mut self) -> Self::PartialState {}
+
+  fn restore_partial(&mut self, _: Self::PartialState) {}
+
+  fn is_partial_supported() -> bool {
+    false
+  }
+}
+
+impl<Buf> UpdateSlice for YarnRef<'_, Buf>
+where
+  Buf: Decode + ?Sized,
+  Buf::Item: fmt::Debug,
+{
+  fn update_slice(self, inner: Self::Slice) -> Self {
+    inner
+  }
+}
+
+/// Winnow only ever asks for the offset of a suffix of a yarn, which is what
+/// this implementation assumes; it does not compare pointers, since subyarns
+/// of inlined yarns are copies.
+impl<Buf> Offset for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn offset_from(&self, start: &Self) -> usize {
+    debug_assert!(self.len() <= start.len());
+    start.len() - self.len()
+  }
+}
+
+impl<Buf> SliceLen for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn slice_len(&self) -> usize {
+    self.len()
+  }
+}
+
+impl<Buf, T> Compare<T> for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  for<'c> &'c Buf: Compare<T>,
+{
+  fn compare(&self, t: T) -> CompareResult {
+    self.as_slice().compare(t)
+  }
+}
+
+/// ```
+/// # use byteyarn::*;
+/// use winnow::prelude::*;
+/// use winnow::token::take_until;
+///
+/// fn line<'a>(input: &mut YarnRef<'a, [u8]>) -> ModalResult<YarnRef<'a, [u8]>> {
+///   take_until(0.., "\r\n").parse_next(input)
+/// }
+///
+/// let mut input = YarnRef::<[u8]>::new(b"GET / HTTP/1.1\r\nHost: x");
+/// assert_eq!(line(&mut input).unwrap(), b"GET / HTTP/1.1");
+/// assert_eq!(input, b"\r\nHost: x");
+/// ```
+impl<Buf, T> FindSlice<T> for YarnRef<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+  for<'c> &'c Buf: FindSlice<T>,
+{
+  fn find_slice(&self, substr: T) -> Option<Range<usize>> {
+    self.as_slice().find_slice(substr)
+  }
+}
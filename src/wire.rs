@@ -0,0 +1,124 @@
+//! A compact, length-prefixed binary encoding for yarns.
+//!
+//! An encoded yarn is its length, as an unsigned LEB128 varint, followed by
+//! its bytes. Short yarns thus cost a single byte of overhead.
+
+use std::str;
+
+use crate::raw::RawYarn;
+use crate::WireError;
+use crate::YarnBox;
+use crate::YarnRef;
+
+impl<'a, Buf> YarnRef<'a, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Appends this yarn's wire encoding to `out`: its length as a varint,
+  /// followed by its bytes.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut out = Vec::new();
+  /// YarnRef::<str>::new("hi").to_wire(&mut out);
+  /// assert_eq!(out, b"\x02hi");
+  /// ```
+  pub fn to_wire(self, out: &mut Vec<u8>) {
+    write_varint(out, self.len());
+    out.extend_from_slice(self.as_bytes());
+  }
+
+  /// Decodes a yarn written by [`YarnRef::to_wire()`] from the start of
+  /// `bytes`, returning it and the rest of the input.
+  ///
+  /// The yarn borrows from `bytes`, rather than copying out of it.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// let mut msg = Vec::new();
+  /// YarnRef::<str>::new("GET").to_wire(&mut msg);
+  /// YarnRef::<str>::new("/index.html").to_wire(&mut msg);
+  ///
+  /// let (method, rest) = YarnRef::<str>::from_wire(&msg).unwrap();
+  /// let (path, rest) = YarnRef::<str>::from_wire(rest).unwrap();
+  /// assert_eq!((method, path), ("GET".into(), "/index.html".into()));
+  /// assert!(rest.is_empty());
+  ///
+  /// assert_eq!(
+  ///   YarnRef::<str>::from_wire(&msg[..3]),
+  ///   Err(WireError::Truncated),
+  /// );
+  /// assert!(YarnRef::<str>::from_wire(b"\x01\xff").is_err());
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// Returns [`WireError::Truncated`] if `bytes` ends before the yarn does,
+  /// which, when reading from a stream, means that more input is needed.
+  pub fn from_wire(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), WireError> {
+    let (len, rest) = read_varint(bytes)?;
+
+    if len > RawYarn::MAX_LEN {
+      return Err(WireError::BadLength);
+    }
+    if rest.len() < len {
+      return Err(WireError::Truncated);
+    }
+
+    let (data, rest) = rest.split_at(len);
+    if <Buf as crate::z::Sealed>::UTF8 {
+      str::from_utf8(data).map_err(WireError::Utf8)?;
+    }
+
+    let yarn = unsafe {
+      // SAFETY: We just checked that data is valid UTF-8 if Buf needs it.
+      YarnRef::<Buf>::raw2buf(data)
+    };
+    Ok((YarnRef::new(yarn), rest))
+  }
+}
+
+impl<Buf> YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Appends this yarn's wire encoding to `out`.
+  ///
+  /// See [`YarnRef::to_wire()`].
+  pub fn to_wire(&self, out: &mut Vec<u8>) {
+    self.as_ref().to_wire(out)
+  }
+}
+
+/// Writes `n` as an unsigned LEB128 varint.
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut n: usize) {
+  loop {
+    let byte = (n & 0x7f) as u8;
+    n >>= 7;
+    if n == 0 {
+      out.push(byte);
+      break;
+    }
+    out.push(byte | 0x80);
+  }
+}
+
+/// Reads a varint written by [`write_varint()`] from the start of `bytes`,
+/// returning it and the rest of the input.
+pub(crate) fn read_varint(bytes: &[u8]) -> Result<(usize, &[u8]), WireError> {
+  let mut n = 0usize;
+  let mut shift = 0;
+  for (i, &byte) in bytes.iter().enumerate() {
+    let bits = (byte & 0x7f) as usize;
+    if shift >= usize::BITS || bits << shift >> shift != bits {
+      return Err(WireError::BadLength);
+    }
+    n |= bits << shift;
+    shift += 7;
+
+    if byte & 0x80 == 0 {
+      return Ok((n, &bytes[i + 1..]));
+    }
+  }
+  Err(WireError::Truncated)
+}
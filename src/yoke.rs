@@ -0,0 +1,51 @@
+//! Integration with the `yoke` crate.
+
+use yoke::Yokeable;
+
+use crate::YarnRef;
+
+/// A [`YarnRef`] can be yoked to the cart that owns the buffer it borrows.
+///
+/// ```
+/// # use byteyarn::*;
+/// use std::rc::Rc;
+/// use yoke::Yoke;
+///
+/// let cart = Rc::new(String::from("key = a borrowed value"));
+/// let yoke = Yoke::<YarnRef<'static, str>, _>::attach_to_cart(cart, |s| {
+///   YarnRef::new(&s[6..])
+/// });
+///
+/// assert_eq!(*yoke.get(), "a borrowed value");
+/// ```
+unsafe impl<'a, Buf> Yokeable<'a> for YarnRef<'static, Buf>
+where
+  Buf: crate::Buf + ?Sized + 'static,
+{
+  type Output = YarnRef<'a, Buf>;
+
+  fn transform(&'a self) -> &'a Self::Output {
+    self
+  }
+
+  fn transform_owned(self) -> Self::Output {
+    self
+  }
+
+  unsafe fn make(from: Self::Output) -> Self {
+    // SAFETY: These types differ only in lifetime, and our caller promises that
+    // the result will not be used past the end of the output's lifetime.
+    (&from as *const Self::Output).cast::<Self>().read()
+  }
+
+  fn transform_mut<F>(&'a mut self, f: F)
+  where
+    F: 'static + for<'b> FnOnce(&'b mut Self::Output),
+  {
+    unsafe {
+      // SAFETY: The closure is 'static and is generic over 'b, so it cannot
+      // smuggle a reference of the shorter lifetime back out of this function.
+      f(&mut *(self as *mut Self).cast::<Self::Output>())
+    }
+  }
+}
@@ -0,0 +1,73 @@
+//! Integration with the `zeroize` crate.
+//!
+//! When this feature is enabled, dropping a [`YarnBox`] also scrubs any bytes
+//! it owns, i.e., inlined bytes and heap allocations. Borrowed bytes belong to
+//! someone else, and are never touched.
+
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+
+use crate::raw::RawYarn;
+use crate::SecretYarn;
+use crate::YarnBox;
+
+impl<Buf> Zeroize for YarnBox<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  /// Scrubs any bytes owned by this yarn, and then replaces it with an empty
+  /// yarn.
+  ///
+  /// A yarn that borrows its contents, or points to static memory, cannot
+  /// scrub them, so they are left untouched. Since that is almost certainly a
+  /// bug, this panics in debug builds.
+  ///
+  /// ```
+  /// # use byteyarn::*;
+  /// use zeroize::Zeroize;
+  ///
+  /// let mut key = Yarn::from_string(String::from("hunter2hunter2hunter2"));
+  /// key.zeroize();
+  /// assert!(key.is_empty());
+  /// ```
+  ///
+  /// ```should_panic
+  /// # use byteyarn::*;
+  /// use zeroize::Zeroize;
+  ///
+  /// let mut key = Yarn::from_static("hunter2hunter2hunter2");
+  /// key.zeroize();  // Panics: nothing we can do about the static bytes.
+  /// ```
+  fn zeroize(&mut self) {
+    unsafe {
+      // SAFETY: We destroy the old raw yarn before replacing it with an empty
+      // one, which is always valid.
+      let raw = self.raw_mut();
+      let scrubbed = raw.zeroize();
+      debug_assert!(
+        scrubbed,
+        "byteyarn: cannot zeroize a yarn that does not own its contents"
+      );
+      raw.destroy();
+      *raw = *RawYarn::empty();
+    }
+  }
+}
+
+/// The destructor of a [`YarnBox`] scrubs its owned bytes when the `zeroize`
+/// feature is enabled.
+impl<Buf> ZeroizeOnDrop for YarnBox<'_, Buf> where Buf: crate::Buf + ?Sized {}
+
+impl<Buf> Zeroize for SecretYarn<'_, Buf>
+where
+  Buf: crate::Buf + ?Sized,
+{
+  fn zeroize(&mut self) {
+    // Taking the yarn out leaves an empty one behind, which is what zeroizing
+    // the yarn in place would have done anyways.
+    let mut yarn = std::mem::take(self).into_inner();
+    yarn.zeroize();
+  }
+}
+
+impl<Buf> ZeroizeOnDrop for SecretYarn<'_, Buf> where Buf: crate::Buf + ?Sized {}